@@ -0,0 +1,101 @@
+use crate::components::business_components::config_file::{config_entries, ConfigFile};
+use std::path::PathBuf;
+
+const APPEARANCE_CONFIG_PATH: &str = "crm_appearance.conf";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            ThemeMode::Dark => ThemeMode::Light,
+            ThemeMode::Light => ThemeMode::Dark,
+        }
+    }
+
+    fn to_config_file_content(self) -> String {
+        match self {
+            ThemeMode::Dark => String::from("theme=dark\n"),
+            ThemeMode::Light => String::from("theme=light\n"),
+        }
+    }
+
+    // unknown keys are ignored and a missing or invalid theme keeps the default
+    fn from_config_file_content(content: &str) -> Self {
+        let mut theme_mode = Self::default();
+        for (key, value) in config_entries(content) {
+            match (key, value) {
+                ("theme", "dark") => theme_mode = ThemeMode::Dark,
+                ("theme", "light") => theme_mode = ThemeMode::Light,
+                _ => {}
+            }
+        }
+        theme_mode
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Appearance {
+    config_file: ConfigFile,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self::new(PathBuf::from(APPEARANCE_CONFIG_PATH))
+    }
+}
+
+impl Appearance {
+    pub fn new(config_path: PathBuf) -> Self {
+        Self {
+            config_file: ConfigFile::new(config_path),
+        }
+    }
+
+    pub fn theme_mode(&self) -> ThemeMode {
+        ThemeMode::from_config_file_content(&self.config_file.read())
+    }
+
+    pub fn save_theme_mode(&self, theme_mode: ThemeMode) -> Result<(), String> {
+        self.config_file.write(&theme_mode.to_config_file_content())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_round_trip() {
+        for theme_mode in [ThemeMode::Dark, ThemeMode::Light] {
+            assert_eq!(
+                ThemeMode::from_config_file_content(&theme_mode.to_config_file_content()),
+                theme_mode
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_config_file_keeps_default() {
+        assert_eq!(
+            ThemeMode::from_config_file_content("theme=sepia\nfont=mono\n"),
+            ThemeMode::Dark
+        );
+    }
+
+    #[test]
+    fn test_save_theme_mode() {
+        let config_path =
+            std::env::temp_dir().join(format!("crm_appearance_test_{}.conf", std::process::id()));
+        let appearance = Appearance::new(config_path.clone());
+        appearance.save_theme_mode(ThemeMode::Light).unwrap();
+        assert_eq!(appearance.theme_mode(), ThemeMode::Light);
+        std::fs::remove_file(config_path).unwrap();
+        assert_eq!(appearance.theme_mode(), ThemeMode::Dark);
+    }
+}
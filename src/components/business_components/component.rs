@@ -1,8 +1,14 @@
+use crate::components::business_components::appearance::ThemeMode;
+use crate::components::business_components::database::console::LogLevel;
+use crate::components::business_components::database::database::ConnectionConfig;
 use crate::components::business_components::database::models::ColumnsInfo;
 use crate::components::business_components::database::schemas::{
-    Column, ColumnForeignKey, Condition, Constraint, DataType, RowColumnValue, RowInsertData,
-    TableChangeEvents, TableDataChangeEvents, TableGeneral, TableIn, TableInsertedData,
+    ChangeSummary, Column, ColumnForeignKey, ColumnStats, CompositeForeignKey, Condition,
+    Constraint, DataType, Index, Operator, Page, ReferentialAction, RowColumnValue, RowDiff,
+    RowInsertData, SortDirection, StatementResult, TableChangeEvents, TableDataChangeEvents,
+    TableGeneral, TableIn, TableInsertedData, TableMetadata,
 };
+use crate::components::business_components::session::SessionState;
 use crate::components::business_components::tables::{
     table_data::table_data::TableData, table_info::table_info::TableInfo,
 };
@@ -17,19 +23,37 @@ pub type BTableData = TableData;
 pub type BTableGeneral = TableGeneral;
 pub type BConstraint = Constraint;
 pub type BColumnForeignKey = ColumnForeignKey;
+pub type BCompositeForeignKey = CompositeForeignKey;
+pub type BIndex = Index;
+pub type BTableMetadata = TableMetadata;
+pub type BColumnStats = ColumnStats;
+pub type BChangeSummary = ChangeSummary;
+pub type BRowDiff = RowDiff;
 pub type BCondition = Condition;
 pub type BTableInsertedData = TableInsertedData;
 pub type BRowColumnValue = RowColumnValue;
 pub type BRowInsertData = RowInsertData;
+pub type BPage = Page;
+pub type BStatementResult = StatementResult;
+pub type BSortDirection = SortDirection;
+pub type BOperator = Operator;
+pub type BReferentialAction = ReferentialAction;
+pub type BLogLevel = LogLevel;
+pub type BConnectionConfig = ConnectionConfig;
+pub type BThemeMode = ThemeMode;
+pub type BSessionState = SessionState;
 
 pub trait BusinessComponent {
     async fn initialize_component(&self) {}
+    // releases what was loaded once the ui leaves the component
+    async fn teardown_component(&self) {}
 }
 
 pub(super) mod repository_module {
     use crate::components::business_components::database::console::RepositoryConsole;
-    use crate::components::business_components::database::repository::Repository;
+    use crate::components::business_components::database::repository::{DbPool, Repository};
 
     pub type BRepository = Repository;
+    pub type BDbPool = DbPool;
     pub type BRepositoryConsole = RepositoryConsole;
 }
@@ -1,8 +1,9 @@
 use crate::components::business_components::component::repository_module::{
-    BRepository, BRepositoryConsole,
+    BDbPool, BRepository, BRepositoryConsole,
 };
 use crate::components::business_components::{
-    console::Console, home::Home, tables::tables::Tables,
+    appearance::Appearance, connection::Connection, console::Console, home::Home,
+    query_runner::QueryRunner, session::Session, tables::tables::Tables,
 };
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
@@ -10,23 +11,33 @@ use tokio::sync::Mutex as AsyncMutex;
 pub type BusinessHome = Home;
 pub type BusinessTables = Tables;
 pub type BusinessConsole = Console;
+pub type BusinessQueryRunner = QueryRunner;
+pub type BusinessConnection = Connection;
+pub type BusinessAppearance = Appearance;
+pub type BusinessSession = Session;
 
 #[derive(Debug, Clone)]
 pub struct BusinessComponents {
     pub home: Arc<BusinessHome>,
     pub tables: Arc<BusinessTables>,
     pub console: Arc<Console>,
+    pub query_runner: Arc<BusinessQueryRunner>,
 }
 
 impl BusinessComponents {
-    pub async fn new() -> Self {
+    pub async fn new(pool: BDbPool) -> Self {
         let repository_console = Arc::new(BRepositoryConsole::new());
-        let repository = Arc::new(BRepository::new(None, repository_console.clone()).await);
+        let repository = Arc::new(BRepository::new(pool, repository_console.clone()).await);
+        repository.log_pool_settings().await;
         let console = Arc::new(Console::new(repository_console.clone()));
         Self {
             home: Arc::new(BusinessHome::new(repository.clone(), console.clone())),
             tables: Arc::new(BusinessTables::new(repository.clone(), console.clone())),
             console: console.clone(),
+            query_runner: Arc::new(BusinessQueryRunner::new(
+                repository.clone(),
+                console.clone(),
+            )),
         }
     }
 }
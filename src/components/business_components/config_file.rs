@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+// settings remembered between runs, stored next to the binary
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    path: PathBuf,
+}
+
+impl ConfigFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    // a missing or unreadable file reads as empty so every setting keeps its default
+    pub fn read(&self) -> String {
+        std::fs::read_to_string(&self.path).unwrap_or_default()
+    }
+
+    // failing to remember settings only means the defaults are used next time,
+    // so the error is returned for the caller to report instead of failing its action
+    pub fn write(&self, content: &str) -> Result<(), String> {
+        std::fs::write(&self.path, content)
+            .map_err(|error| format!("Could not save {}: {}", self.path.display(), error))
+    }
+}
+
+// for files of key=value lines, lines without a '=' are skipped, keys and values are trimmed
+pub fn config_entries(content: &str) -> impl Iterator<Item = (&str, &str)> {
+    content.lines().filter_map(|line| {
+        line.split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_entries() {
+        assert_eq!(
+            config_entries(" host = localhost\nport\n\nurl=a=b\n").collect::<Vec<_>>(),
+            vec![("host", "localhost"), ("url", "a=b")]
+        );
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let config_file = ConfigFile::new(
+            std::env::temp_dir().join(format!("crm_config_file_test_{}.conf", std::process::id())),
+        );
+        config_file.write("theme=light\n").unwrap();
+        assert_eq!(config_file.read(), "theme=light\n");
+        std::fs::remove_file(&config_file.path).unwrap();
+        assert_eq!(config_file.read(), "");
+        assert!(ConfigFile::new(PathBuf::from("/nonexistent/crm.conf"))
+            .write("theme=light\n")
+            .is_err());
+    }
+}
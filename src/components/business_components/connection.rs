@@ -0,0 +1,162 @@
+use crate::components::business_components::component::{BConnectionConfig, BLogLevel};
+use crate::components::business_components::components::BusinessComponents;
+use crate::components::business_components::config_file::ConfigFile;
+use crate::components::business_components::database::database::{
+    connect_postgres, create_database_pool, is_database_configured_by_env,
+};
+use std::path::PathBuf;
+use tokio::task;
+
+const CONNECTION_CONFIG_PATH: &str = "crm_connection.conf";
+
+#[derive(Debug, Clone)]
+pub struct Connection {
+    config_file: ConfigFile,
+}
+
+impl Default for Connection {
+    fn default() -> Self {
+        Self::new(PathBuf::from(CONNECTION_CONFIG_PATH))
+    }
+}
+
+impl Connection {
+    pub fn new(config_path: PathBuf) -> Self {
+        Self {
+            config_file: ConfigFile::new(config_path),
+        }
+    }
+
+    pub fn is_configured_by_env(&self) -> bool {
+        is_database_configured_by_env()
+    }
+
+    // the defaults are used until a connection succeeds once
+    pub fn last_connection_config(&self) -> BConnectionConfig {
+        BConnectionConfig::from_config_file_content(&self.config_file.read())
+    }
+
+    pub async fn connect(
+        &self,
+        config: BConnectionConfig,
+        password: String,
+    ) -> Result<BusinessComponents, String> {
+        let pool = connect_postgres(&config, &password)
+            .await
+            .map_err(|error| format!("Could not connect: {}", error))?;
+        let business_components = BusinessComponents::new(pool).await;
+        if let Err(error) = self.config_file.write(&config.to_config_file_content()) {
+            let console = business_components.console.clone();
+            let _ = task::spawn_blocking(move || {
+                console.write(BLogLevel::Warning, error);
+            })
+            .await;
+        }
+        Ok(business_components)
+    }
+
+    pub async fn connect_from_env(&self) -> Result<BusinessComponents, String> {
+        let pool = create_database_pool()
+            .await
+            .map_err(|error| format!("Could not connect: {}", error))?;
+        Ok(BusinessComponents::new(pool).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::business_components::database::database::{
+        is_transient_error, PoolConfig, RetryConfig,
+    };
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[test]
+    fn test_config_file_round_trip() {
+        let config = BConnectionConfig {
+            host: String::from("db.example.com"),
+            port: 6543,
+            username: String::from("crm"),
+            database: String::from("crm_production"),
+        };
+        let content = config.to_config_file_content();
+        assert!(!content.contains("password"));
+        assert_eq!(
+            BConnectionConfig::from_config_file_content(&content),
+            config
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_file_keeps_defaults() {
+        assert_eq!(
+            BConnectionConfig::from_config_file_content("port=not a port\nunknown=1\nhost"),
+            BConnectionConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_pool_config_from_env_values() {
+        let env_values = HashMap::from([
+            ("DATABASE_MAX_CONNECTIONS", "20"),
+            ("DATABASE_ACQUIRE_TIMEOUT_SECONDS", "not a number"),
+            ("DATABASE_IDLE_TIMEOUT_SECONDS", "0"),
+        ]);
+        let pool_config =
+            PoolConfig::from_env_values(|key| env_values.get(key).map(|value| value.to_string()));
+        assert_eq!(
+            pool_config,
+            PoolConfig {
+                max_connections: 20,
+                acquire_timeout: Duration::from_secs(30),
+                idle_timeout: None,
+            }
+        );
+        assert_eq!(PoolConfig::from_env_values(|_| None), PoolConfig::default());
+    }
+
+    #[test]
+    fn test_retry_config_from_env_values() {
+        let env_values = HashMap::from([
+            ("DATABASE_MAX_RETRIES", "0"),
+            ("DATABASE_RETRY_BACKOFF_MILLISECONDS", "50"),
+        ]);
+        let retry_config =
+            RetryConfig::from_env_values(|key| env_values.get(key).map(|value| value.to_string()));
+        assert_eq!(
+            retry_config,
+            RetryConfig {
+                max_retries: 0,
+                initial_backoff: Duration::from_millis(50),
+            }
+        );
+        assert_eq!(
+            RetryConfig::from_env_values(|_| None),
+            RetryConfig::default()
+        );
+        assert_eq!(retry_config.backoff(0), Duration::from_millis(50));
+        assert_eq!(retry_config.backoff(3), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_is_transient_error() {
+        assert!(is_transient_error(&sqlx::Error::PoolTimedOut));
+        assert!(is_transient_error(&sqlx::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset
+        ))));
+        assert!(!is_transient_error(&sqlx::Error::RowNotFound));
+        assert!(!is_transient_error(&sqlx::Error::Protocol(String::from(
+            "syntax error"
+        ))));
+    }
+
+    #[test]
+    fn test_missing_config_file() {
+        let connection = Connection::new(PathBuf::from("/nonexistent/crm_connection.conf"));
+        assert_eq!(
+            connection.last_connection_config(),
+            BConnectionConfig::default()
+        );
+    }
+}
@@ -1,25 +1,49 @@
-use crate::components::business_components::component::repository_module::BRepositoryConsole;
+use crate::components::business_components::component::{
+    repository_module::BRepositoryConsole, BLogLevel,
+};
+use crate::components::business_components::database::console::{
+    evict_oldest_messages, DEFAULT_MAX_MESSAGES,
+};
+use std::fs;
+use std::io;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 
 #[derive(Debug, Clone)]
 pub struct Console {
-    pub messages: Arc<AsyncMutex<Vec<String>>>,
+    pub messages: Arc<AsyncMutex<Vec<(BLogLevel, String)>>>,
+    max_messages: Arc<AsyncMutex<usize>>,
     repository_console: Arc<BRepositoryConsole>,
+    // rejected input and failures not yet shown to the user
+    unseen_error: Arc<AsyncMutex<Option<String>>>,
 }
 
 impl Console {
     pub fn new(repository_console: Arc<BRepositoryConsole>) -> Self {
         Self {
             messages: Arc::new(AsyncMutex::new(Vec::new())),
+            max_messages: Arc::new(AsyncMutex::new(DEFAULT_MAX_MESSAGES)),
             repository_console,
+            unseen_error: Arc::new(AsyncMutex::new(None)),
         }
     }
 
-    pub fn get_messages(&self) -> Vec<String> {
+    pub fn max_messages(&self) -> usize {
+        *self.max_messages.blocking_lock()
+    }
+
+    // applies to the business and database buffers
+    pub fn set_max_messages(&self, max_messages: usize) {
+        *self.max_messages.blocking_lock() = max_messages;
+        evict_oldest_messages(&mut self.messages.blocking_lock(), max_messages);
+        self.repository_console.set_max_messages(max_messages);
+    }
+
+    pub fn get_messages(&self) -> Vec<(BLogLevel, String)> {
         self.messages.blocking_lock().clone()
     }
-    pub fn get_database_messages(&self) -> Vec<String> {
+    pub fn get_database_messages(&self) -> Vec<(BLogLevel, String)> {
         self.repository_console.messages.blocking_lock().clone()
     }
 
@@ -31,7 +55,115 @@ impl Console {
         self.repository_console.clear_messages();
     }
 
-    pub fn write(&self, message: String) {
-        self.messages.blocking_lock().push(message);
+    pub fn write(&self, level: BLogLevel, message: String) {
+        if level >= BLogLevel::Warning {
+            self.unseen_error
+                .blocking_lock()
+                .get_or_insert_with(|| message.clone());
+        }
+        let max_messages = self.max_messages();
+        let mut locked_messages = self.messages.blocking_lock();
+        locked_messages.push((level, message));
+        evict_oldest_messages(&mut locked_messages, max_messages);
+    }
+
+    // the ui console keeps its own buffer but shares the business console's cap
+    pub fn evict_oldest_messages(&self, messages: &mut Vec<(BLogLevel, String)>) {
+        evict_oldest_messages(messages, self.max_messages());
+    }
+
+    pub fn write_info(&self, message: String) {
+        self.write(BLogLevel::Info, message);
+    }
+
+    // both consoles are cleared, the business message explains the database one
+    pub fn take_error(&self) -> Option<String> {
+        let error = self.unseen_error.blocking_lock().take();
+        let database_error = self.repository_console.take_error();
+        error.or(database_error)
+    }
+
+    // the ui messages are owned by the ui console so they are passed in
+    pub fn export_log(
+        &self,
+        path: &Path,
+        ui_messages: &Vec<(BLogLevel, String)>,
+    ) -> io::Result<()> {
+        let log = [
+            ("UI Messages", ui_messages.clone()),
+            ("Business Messages", self.get_messages()),
+            ("Database Messages", self.get_database_messages()),
+        ]
+        .iter()
+        .map(|(title, messages)| format_log_section(title, messages))
+        .collect::<Vec<String>>()
+        .join("\n");
+        fs::write(path, log)
+    }
+}
+
+fn format_log_section(title: &str, messages: &Vec<(BLogLevel, String)>) -> String {
+    let mut section = format!("===== {} =====\n", title);
+    for (level, message) in messages {
+        section.push_str(&format!("[{}] {}\n", level, message));
+    }
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_messages() {
+        let console = Console::new(Arc::new(BRepositoryConsole::new()));
+        console.set_max_messages(3);
+        for index in 0..5 {
+            console.write_info(format!("message {}", index));
+        }
+        let messages: Vec<String> = console
+            .get_messages()
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect();
+        assert_eq!(messages, vec!["message 2", "message 3", "message 4"]);
+
+        console.set_max_messages(1);
+        assert_eq!(console.get_messages().len(), 1);
+    }
+
+    #[test]
+    fn test_take_error() {
+        let repository_console = Arc::new(BRepositoryConsole::new());
+        let console = Console::new(repository_console.clone());
+        console.write_info(String::from("Table created"));
+        assert_eq!(console.take_error(), None);
+
+        repository_console.write(BLogLevel::Error, String::from("syntax error"));
+        console.write(BLogLevel::Error, String::from("Could not save table data"));
+        console.write(BLogLevel::Warning, String::from("Rejected row"));
+        assert_eq!(
+            console.take_error(),
+            Some(String::from("Could not save table data"))
+        );
+        assert_eq!(console.take_error(), None);
+
+        repository_console.write(BLogLevel::Error, String::from("syntax error"));
+        assert_eq!(console.take_error(), Some(String::from("syntax error")));
+    }
+
+    #[test]
+    fn test_format_log_section() {
+        let messages = vec![
+            (BLogLevel::Info, String::from("Table created")),
+            (
+                BLogLevel::Error,
+                String::from("Could not delete table users"),
+            ),
+        ];
+        assert_eq!(
+            format_log_section("Business Messages", &messages),
+            "===== Business Messages =====\n[Info] Table created\n[Error] Could not delete table users\n"
+        );
     }
 }
@@ -1,23 +1,81 @@
+use std::fmt;
 use std::sync::Arc;
 use tokio::sync::Mutex as AsyncMutex;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogLevel::Info => write!(f, "Info"),
+            LogLevel::Warning => write!(f, "Warning"),
+            LogLevel::Error => write!(f, "Error"),
+        }
+    }
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 3] = [LogLevel::Info, LogLevel::Warning, LogLevel::Error];
+}
+
+pub const DEFAULT_MAX_MESSAGES: usize = 1000;
+
+// the oldest messages are evicted once the buffer is full
+pub fn evict_oldest_messages<T>(messages: &mut Vec<T>, max_messages: usize) {
+    if messages.len() > max_messages {
+        let excess_message_count = messages.len() - max_messages;
+        messages.drain(..excess_message_count);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RepositoryConsole {
-    pub messages: Arc<AsyncMutex<Vec<String>>>,
+    pub messages: Arc<AsyncMutex<Vec<(LogLevel, String)>>>,
+    max_messages: Arc<AsyncMutex<usize>>,
+    // the first problem since the last take_error, later ones usually follow from it
+    unseen_error: Arc<AsyncMutex<Option<String>>>,
 }
 
 impl RepositoryConsole {
     pub fn new() -> Self {
         Self {
             messages: Arc::new(AsyncMutex::new(Vec::new())),
+            max_messages: Arc::new(AsyncMutex::new(DEFAULT_MAX_MESSAGES)),
+            unseen_error: Arc::new(AsyncMutex::new(None)),
         }
     }
 
-    pub fn write(&self, message: String) {
-        self.messages.blocking_lock().push(message);
+    pub fn set_max_messages(&self, max_messages: usize) {
+        *self.max_messages.blocking_lock() = max_messages;
+        evict_oldest_messages(&mut self.messages.blocking_lock(), max_messages);
+    }
+
+    pub fn write(&self, level: LogLevel, message: String) {
+        if level >= LogLevel::Warning {
+            self.unseen_error
+                .blocking_lock()
+                .get_or_insert_with(|| message.clone());
+        }
+        let max_messages = *self.max_messages.blocking_lock();
+        let mut locked_messages = self.messages.blocking_lock();
+        locked_messages.push((level, message));
+        evict_oldest_messages(&mut locked_messages, max_messages);
+    }
+
+    pub fn write_info(&self, message: String) {
+        self.write(LogLevel::Info, message);
     }
 
     pub fn clear_messages(&self) {
         *self.messages.blocking_lock() = vec![];
     }
+
+    pub fn take_error(&self) -> Option<String> {
+        self.unseen_error.blocking_lock().take()
+    }
 }
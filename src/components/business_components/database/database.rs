@@ -1,13 +1,220 @@
+use crate::components::business_components::config_file::config_entries;
+use crate::components::business_components::database::repository::DbPool;
 use dotenvy::dotenv;
-use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::pool::PoolOptions;
+use sqlx::postgres::PgConnectOptions;
 use std::env;
+use std::time::Duration;
 
-pub async fn create_database_pool() -> PgPool {
+// the password is asked for on every start and never stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub database: String,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            host: String::from("localhost"),
+            port: 5432,
+            username: String::from("postgres"),
+            database: String::from("postgres"),
+        }
+    }
+}
+
+impl ConnectionConfig {
+    pub fn to_config_file_content(&self) -> String {
+        format!(
+            "host={}\nport={}\nusername={}\ndatabase={}\n",
+            self.host, self.port, self.username, self.database
+        )
+    }
+
+    // unknown keys are ignored and missing or invalid values keep their default
+    pub fn from_config_file_content(content: &str) -> Self {
+        let mut config = Self::default();
+        for (key, value) in config_entries(content) {
+            match key {
+                "host" => config.host = value.to_string(),
+                "port" => config.port = value.parse().unwrap_or(config.port),
+                "username" => config.username = value.to_string(),
+                "database" => config.database = value.to_string(),
+                _ => {}
+            }
+        }
+        config
+    }
+}
+
+// sqlx defaults apart from max_connections, which has always been 5 here
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        Self::from_env_values(|key| env::var(key).ok())
+    }
+
+    // missing or invalid values keep their default, an idle timeout of 0 disables it
+    pub fn from_env_values(get_value: impl Fn(&str) -> Option<String>) -> Self {
+        let default = Self::default();
+        let get_number =
+            |key: &str| get_value(key).and_then(|value| value.trim().parse::<u64>().ok());
+        Self {
+            max_connections: get_number("DATABASE_MAX_CONNECTIONS")
+                .and_then(|value| u32::try_from(value).ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(default.max_connections),
+            acquire_timeout: get_number("DATABASE_ACQUIRE_TIMEOUT_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.acquire_timeout),
+            idle_timeout: match get_number("DATABASE_IDLE_TIMEOUT_SECONDS") {
+                Some(0) => None,
+                Some(seconds) => Some(Duration::from_secs(seconds)),
+                None => default.idle_timeout,
+            },
+        }
+    }
+
+    fn pool_options<DB: sqlx::Database>(&self) -> PoolOptions<DB> {
+        PoolOptions::new()
+            .max_connections(self.max_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+    }
+}
+
+// reads that failed on the connection rather than the query are retried,
+// waiting twice as long before each further attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        Self::from_env_values(|key| env::var(key).ok())
+    }
+
+    // missing or invalid values keep their default, 0 retries disables retrying
+    pub fn from_env_values(get_value: impl Fn(&str) -> Option<String>) -> Self {
+        let default = Self::default();
+        let get_number =
+            |key: &str| get_value(key).and_then(|value| value.trim().parse::<u64>().ok());
+        Self {
+            max_retries: get_number("DATABASE_MAX_RETRIES")
+                .and_then(|value| u32::try_from(value).ok())
+                .unwrap_or(default.max_retries),
+            initial_backoff: get_number("DATABASE_RETRY_BACKOFF_MILLISECONDS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.initial_backoff),
+        }
+    }
+
+    // how long to wait before the given retry, counting from 0
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+    }
+}
+
+// a lost connection or an exhausted pool may work on the next attempt,
+// errors reported by the database itself (syntax, constraints) won't
+pub fn is_transient_error(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed
+    )
+}
+
+// a database set up through the environment skips the connection form
+pub fn is_database_configured_by_env() -> bool {
+    dotenv().ok();
+    env::var("DATABASE_URL").is_ok()
+        || env::var("DATABASE_BACKEND").is_ok_and(|backend| backend.eq_ignore_ascii_case("sqlite"))
+}
+
+pub async fn connect_postgres(
+    config: &ConnectionConfig,
+    password: &str,
+) -> Result<DbPool, sqlx::Error> {
+    let connect_options = PgConnectOptions::new()
+        .host(&config.host)
+        .port(config.port)
+        .username(&config.username)
+        .password(password)
+        .database(&config.database);
+    let pool = PoolConfig::from_env()
+        .pool_options()
+        .connect_with(connect_options)
+        .await?;
+    Ok(DbPool::Postgres(pool))
+}
+
+// DATABASE_BACKEND selects between postgres (default) and a local sqlite file
+pub async fn create_database_pool() -> Result<DbPool, sqlx::Error> {
+    dotenv().ok();
+    let backend = env::var("DATABASE_BACKEND").unwrap_or_else(|_| String::from("postgres"));
+    if backend.eq_ignore_ascii_case("sqlite") {
+        let database_url = env::var("SQLITE_DATABASE_URL")
+            .unwrap_or_else(|_| String::from("sqlite://crm.db?mode=rwc"));
+        let pool = PoolConfig::from_env()
+            .pool_options()
+            .connect(database_url.as_str())
+            .await?;
+        Ok(DbPool::Sqlite(pool))
+    } else {
+        let database_url = env::var("DATABASE_URL").map_err(|_| {
+            sqlx::Error::Configuration("Env variable: DATABASE_URL must be set".into())
+        })?;
+        let pool = PoolConfig::from_env()
+            .pool_options()
+            .connect(database_url.as_str())
+            .await?;
+        Ok(DbPool::Postgres(pool))
+    }
+}
+
+// a value of 0 disables the keep-alive ping
+pub fn get_keep_alive_interval() -> Option<Duration> {
     dotenv().ok();
-    let database_url = env::var("DATABASE_URL").expect("Env variable: DATABASE_URL must be set");
-    PgPoolOptions::new()
-        .max_connections(5)
-        .connect(database_url.as_str())
-        .await
-        .unwrap()
+    let seconds = env::var("KEEP_ALIVE_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(60);
+    if seconds == 0 {
+        None
+    } else {
+        Some(Duration::from_secs(seconds))
+    }
 }
@@ -1,5 +1,5 @@
 pub mod console;
-mod database;
+pub mod database;
 pub mod models;
 pub mod repository;
 pub mod schemas;
@@ -4,9 +4,16 @@ use std::collections::HashMap;
 pub struct ColumnsInfo {
     pub column_name: String,
     pub data_type: String,
+    pub is_nullable: String,
+    pub column_default: Option<String>,
     pub constraint_types: Vec<Option<String>>,
     pub referenced_tables: Vec<Option<String>>,
     pub referenced_columns: Vec<Option<String>>,
+    pub delete_rules: Vec<Option<String>>,
+    pub column_comment: Option<String>,
+    pub generation_expression: Option<String>,
+    // identity and computed columns, the database assigns their values
+    pub is_generated: bool,
 }
 
 #[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
@@ -21,3 +28,10 @@ pub struct TableGeneralInfo {
 pub struct PrimaryKeyConstraint {
     pub conname: String,
 }
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct CompositeForeignKeyInfo {
+    pub column_names: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_column_names: Vec<String>,
+}
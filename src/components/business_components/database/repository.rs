@@ -1,73 +1,1032 @@
 use crate::components::business_components::database::{
-    console::RepositoryConsole,
-    database::create_database_pool,
-    models::{ColumnsInfo, PrimaryKeyConstraint, TableGeneralInfo},
+    console::{LogLevel, RepositoryConsole},
+    database::{get_keep_alive_interval, is_transient_error, RetryConfig},
+    models::{ColumnsInfo, CompositeForeignKeyInfo, PrimaryKeyConstraint, TableGeneralInfo},
     schemas::{
-        ColumnForeignKey, Condition, Constraint, DataType, TableChangeEvents,
-        TableDataChangeEvents, TableIn, TableInsertedData,
+        Column, ColumnForeignKey, ColumnStats, CompositeForeignKey, Condition, Constraint,
+        DataType, Index, Operator, Page, ReferentialAction, RowInsertData, SortDirection,
+        StatementResult, TableChangeEvents, TableDataChangeEvents, TableIn, TableInsertedData,
+        TableMetadata,
     },
 };
-use sqlx::{postgres::PgRow, Executor, PgPool, Postgres, Row, Transaction};
-use std::collections::HashMap;
+use sqlx::{
+    postgres::{PgRow, Postgres},
+    sqlite::{Sqlite, SqlitePool, SqliteRow},
+    Column as SqlxColumn, Executor, PgPool, Row, Transaction, ValueRef,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
 use std::iter::zip;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::task;
 
+// keeps a single multi-row INSERT well under the statement size limits of both backends
+const MAX_INSERT_BATCH_ROWS: usize = 500;
+// the most common duplicates are enough to see what needs cleaning up
+const MAX_DUPLICATE_GROUPS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub enum DbPool {
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+}
+
+impl DbPool {
+    // schema the tables live in until another one is selected
+    fn default_schema(&self) -> &'static str {
+        match self {
+            DbPool::Postgres(_) => "public",
+            DbPool::Sqlite(_) => "main",
+        }
+    }
+
+    // positional query parameter in the syntax of the backend
+    fn parameter(&self, index: usize) -> String {
+        match self {
+            DbPool::Postgres(_) => format!("${}", index),
+            DbPool::Sqlite(_) => format!("?{}", index),
+        }
+    }
+
+    async fn execute(&self, query: &str) -> Result<(), sqlx::Error> {
+        match self {
+            DbPool::Postgres(pool) => {
+                sqlx::query(query).execute(pool).await?;
+            }
+            DbPool::Sqlite(pool) => {
+                sqlx::query(query).execute(pool).await?;
+            }
+        }
+        Ok(())
+    }
+
+    // every selected column has to be TEXT
+    async fn fetch_text_rows(
+        &self,
+        query: &str,
+        parameters: &[&str],
+    ) -> Result<Vec<Vec<String>>, sqlx::Error> {
+        let rows = self.fetch_optional_text_rows(query, parameters).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Option::unwrap_or_default).collect())
+            .collect())
+    }
+
+    // NULL values are kept as None
+    async fn fetch_optional_text_rows(
+        &self,
+        query: &str,
+        parameters: &[&str],
+    ) -> Result<Vec<Vec<Option<String>>>, sqlx::Error> {
+        match self {
+            DbPool::Postgres(pool) => {
+                let mut pg_query = sqlx::query(query);
+                for parameter in parameters {
+                    pg_query = pg_query.bind(*parameter);
+                }
+                let rows = pg_query.fetch_all(pool).await?;
+                Ok(rows
+                    .iter()
+                    .map(|row| (0..row.len()).map(|index| row.get(index)).collect())
+                    .collect())
+            }
+            DbPool::Sqlite(pool) => {
+                let mut sqlite_query = sqlx::query(query);
+                for parameter in parameters {
+                    sqlite_query = sqlite_query.bind(*parameter);
+                }
+                let rows = sqlite_query.fetch_all(pool).await?;
+                Ok(rows
+                    .iter()
+                    .map(|row| (0..row.len()).map(|index| row.get(index)).collect())
+                    .collect())
+            }
+        }
+    }
+
+    // unlike fetch_text_rows the selected columns can have any type, NULL becomes None
+    async fn fetch_query_rows(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<Option<String>>>), sqlx::Error> {
+        match self {
+            DbPool::Postgres(pool) => {
+                let column_names = pool
+                    .describe(query)
+                    .await?
+                    .columns()
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect();
+                // the simple query protocol returns every value in its text form
+                let rows = sqlx::raw_sql(query).fetch_all(pool).await?;
+                let rows = rows
+                    .iter()
+                    .map(pg_row_values)
+                    .collect::<Result<Vec<Vec<Option<String>>>, sqlx::Error>>()?;
+                Ok((column_names, rows))
+            }
+            DbPool::Sqlite(pool) => {
+                let column_names = pool
+                    .describe(query)
+                    .await?
+                    .columns()
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect();
+                let rows = sqlx::query(query).fetch_all(pool).await?;
+                let rows = rows
+                    .iter()
+                    .map(sqlite_row_values)
+                    .collect::<Result<Vec<Vec<Option<String>>>, sqlx::Error>>()?;
+                Ok((column_names, rows))
+            }
+        }
+    }
+
+    // the database rejects writes, so a DELETE inside a WITH or a SELECT INTO fails too
+    async fn fetch_read_only_query_rows(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<Option<String>>>), sqlx::Error> {
+        match self {
+            DbPool::Postgres(pool) => {
+                let mut transaction = pool.begin().await?;
+                transaction.execute("SET TRANSACTION READ ONLY").await?;
+                let column_names = transaction
+                    .describe(query)
+                    .await?
+                    .columns()
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect();
+                let rows = transaction.fetch_all(query).await?;
+                let rows = rows
+                    .iter()
+                    .map(pg_row_values)
+                    .collect::<Result<Vec<Vec<Option<String>>>, sqlx::Error>>()?;
+                transaction.rollback().await?;
+                Ok((column_names, rows))
+            }
+            DbPool::Sqlite(pool) => {
+                let mut connection = pool.acquire().await?;
+                connection.execute("PRAGMA query_only = ON").await?;
+                let result = async {
+                    let column_names = connection
+                        .describe(query)
+                        .await?
+                        .columns()
+                        .iter()
+                        .map(|column| column.name().to_string())
+                        .collect();
+                    let rows = connection.fetch_all(query).await?;
+                    let rows = rows
+                        .iter()
+                        .map(sqlite_row_values)
+                        .collect::<Result<Vec<Vec<Option<String>>>, sqlx::Error>>()?;
+                    Ok((column_names, rows))
+                }
+                .await;
+                // the connection goes back to the pool, later writes must not be refused
+                connection.execute("PRAGMA query_only = OFF").await?;
+                result
+            }
+        }
+    }
+}
+
+// statements that describe no columns only report how many rows they changed,
+// the transaction is handed back so the next statement runs in it
+async fn run_pg_statement(
+    mut transaction: Transaction<'static, Postgres>,
+    statement: String,
+) -> (
+    Transaction<'static, Postgres>,
+    Result<StatementResult, sqlx::Error>,
+) {
+    let column_names = match transaction.describe(statement.as_str()).await {
+        Ok(describe) => describe
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<String>>(),
+        Err(error) => return (transaction, Err(error)),
+    };
+    let result = if column_names.is_empty() {
+        transaction
+            .execute(statement.as_str())
+            .await
+            .map(|result| StatementResult::RowsAffected(result.rows_affected()))
+    } else {
+        transaction
+            .fetch_all(statement.as_str())
+            .await
+            .and_then(|rows| {
+                rows.iter()
+                    .map(pg_row_values)
+                    .collect::<Result<Vec<Vec<Option<String>>>, sqlx::Error>>()
+            })
+            .map(|rows| StatementResult::Rows(query_result(column_names, rows)))
+    };
+    (transaction, result)
+}
+
+async fn run_sqlite_statement(
+    mut transaction: Transaction<'static, Sqlite>,
+    statement: String,
+) -> (
+    Transaction<'static, Sqlite>,
+    Result<StatementResult, sqlx::Error>,
+) {
+    let column_names = match transaction.describe(statement.as_str()).await {
+        Ok(describe) => describe
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<String>>(),
+        Err(error) => return (transaction, Err(error)),
+    };
+    let result = if column_names.is_empty() {
+        transaction
+            .execute(statement.as_str())
+            .await
+            .map(|result| StatementResult::RowsAffected(result.rows_affected()))
+    } else {
+        transaction
+            .fetch_all(statement.as_str())
+            .await
+            .and_then(|rows| {
+                rows.iter()
+                    .map(sqlite_row_values)
+                    .collect::<Result<Vec<Vec<Option<String>>>, sqlx::Error>>()
+            })
+            .map(|rows| StatementResult::Rows(query_result(column_names, rows)))
+    };
+    (transaction, result)
+}
+
+// every value is TEXT so the result can be shown like table data
+fn query_result(column_names: Vec<String>, rows: Vec<Vec<Option<String>>>) -> TableInsertedData {
+    TableInsertedData {
+        table_name: String::new(),
+        data_types: vec![DataType::TEXT; column_names.len()],
+        read_only_columns: vec![false; column_names.len()],
+        column_names,
+        total_row_count: rows.len() as i64,
+        rows,
+        page: Page::default(),
+    }
+}
+
+// values of a row fetched with the simple query protocol, which sends them all as text
+fn pg_row_values(row: &PgRow) -> Result<Vec<Option<String>>, sqlx::Error> {
+    (0..row.len())
+        .map(|index| {
+            let value = row.try_get_raw(index)?;
+            if value.is_null() {
+                return Ok(None);
+            }
+            value
+                .as_str()
+                .map(|value| Some(value.to_string()))
+                .map_err(sqlx::Error::Decode)
+        })
+        .collect()
+}
+
+fn sqlite_row_values(row: &SqliteRow) -> Result<Vec<Option<String>>, sqlx::Error> {
+    (0..row.len())
+        .map(|index| {
+            // sqlite values are dynamically typed so each storage class is tried
+            row.try_get::<Option<String>, _>(index)
+                .or_else(|_| {
+                    row.try_get::<Option<i64>, _>(index)
+                        .map(|value| value.map(|value| value.to_string()))
+                })
+                .or_else(|_| {
+                    row.try_get::<Option<f64>, _>(index)
+                        .map(|value| value.map(|value| value.to_string()))
+                })
+                .or_else(|_| {
+                    row.try_get::<Option<Vec<u8>>, _>(index)
+                        .map(|value| value.map(|value| String::from_utf8_lossy(&value).to_string()))
+                })
+        })
+        .collect()
+}
+
+fn get_referenced_table_names(table_in: &TableIn) -> Vec<String> {
+    let column_referenced_table_names = table_in.columns.iter().flat_map(|column| {
+        column.constraints.iter().filter_map(|constraint| {
+            if let Constraint::ForeignKey { table, .. } = constraint {
+                Some(table.clone())
+            } else {
+                None
+            }
+        })
+    });
+    let composite_referenced_table_names = table_in
+        .composite_foreign_keys
+        .iter()
+        .map(|composite_foreign_key| composite_foreign_key.referenced_table.clone());
+    column_referenced_table_names
+        .chain(composite_referenced_table_names)
+        // self references don't affect the order
+        .filter(|referenced_table_name| *referenced_table_name != table_in.table_name)
+        .collect()
+}
+
+// topological sort over the foreign key graph, tables that are part of or depend on a cycle
+// are returned separately in their original order
+fn order_tables_by_dependency(tables_in: Vec<TableIn>) -> (Vec<TableIn>, Vec<TableIn>) {
+    let table_names: HashSet<String> = tables_in
+        .iter()
+        .map(|table_in| table_in.table_name.clone())
+        .collect();
+    let mut remaining_referenced_table_names: Vec<HashSet<String>> = tables_in
+        .iter()
+        .map(|table_in| {
+            get_referenced_table_names(table_in)
+                .into_iter()
+                .filter(|referenced_table_name| table_names.contains(referenced_table_name))
+                .collect()
+        })
+        .collect();
+    let mut ready_indexes: VecDeque<usize> = remaining_referenced_table_names
+        .iter()
+        .enumerate()
+        .filter(|(_, referenced_table_names)| referenced_table_names.is_empty())
+        .map(|(index, _)| index)
+        .collect();
+    let mut ordered_indexes = vec![];
+    while let Some(index) = ready_indexes.pop_front() {
+        ordered_indexes.push(index);
+        let table_name = &tables_in[index].table_name;
+        for (other_index, referenced_table_names) in
+            remaining_referenced_table_names.iter_mut().enumerate()
+        {
+            if referenced_table_names.remove(table_name) && referenced_table_names.is_empty() {
+                ready_indexes.push_back(other_index);
+            }
+        }
+    }
+    let mut tables_in: Vec<Option<TableIn>> = tables_in.into_iter().map(Some).collect();
+    let ordered_tables_in = ordered_indexes
+        .into_iter()
+        .filter_map(|index| tables_in[index].take())
+        .collect();
+    let cyclic_tables_in = tables_in.into_iter().flatten().collect();
+    (ordered_tables_in, cyclic_tables_in)
+}
+
+fn join_schema_queries(queries: &[String]) -> String {
+    queries
+        .iter()
+        .map(|query| format!("{};\n", query))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn without_foreign_keys(table_in: &TableIn) -> TableIn {
+    let mut table_in = table_in.clone();
+    for column in &mut table_in.columns {
+        column
+            .constraints
+            .retain(|constraint| !matches!(constraint, Constraint::ForeignKey { .. }));
+    }
+    table_in.composite_foreign_keys.clear();
+    table_in
+}
+
+// sqlite has no schemas besides the attached databases so its names are left unqualified
+fn qualified_table_name(schema: Option<&str>, table_name: &str) -> String {
+    match schema {
+        Some(schema) => format!("\"{}\".\"{}\"", schema, table_name),
+        None => format!("\"{}\"", table_name),
+    }
+}
+
+fn composite_foreign_key_definition(
+    schema: Option<&str>,
+    composite_foreign_key: &CompositeForeignKey,
+) -> String {
+    format!(
+        "FOREIGN KEY ({}) REFERENCES {} ({})",
+        composite_foreign_key
+            .column_names
+            .iter()
+            .map(|col| format!("\"{}\"", col))
+            .collect::<Vec<_>>()
+            .join(", "),
+        qualified_table_name(schema, &composite_foreign_key.referenced_table),
+        composite_foreign_key
+            .referenced_column_names
+            .iter()
+            .map(|col| format!("\"{}\"", col))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn get_add_foreign_key_queries(schema: Option<&str>, table_in: &TableIn) -> Vec<String> {
+    let column_foreign_key_queries = table_in.columns.iter().flat_map(|column| {
+        column.constraints.iter().filter_map(|constraint| {
+            if let Constraint::ForeignKey {
+                table,
+                column: referenced_column,
+                on_delete,
+            } = constraint
+            {
+                let mut query = format!(
+                    "ALTER TABLE {} ADD FOREIGN KEY (\"{}\") REFERENCES {}(\"{}\")",
+                    qualified_table_name(schema, &table_in.table_name),
+                    column.name,
+                    qualified_table_name(schema, table),
+                    referenced_column
+                );
+                if *on_delete != ReferentialAction::NoAction {
+                    query.push_str(&format!(" ON DELETE {}", on_delete));
+                }
+                Some(query)
+            } else {
+                None
+            }
+        })
+    });
+    let composite_foreign_key_queries =
+        table_in
+            .composite_foreign_keys
+            .iter()
+            .map(|composite_foreign_key| {
+                format!(
+                    "ALTER TABLE {} ADD {}",
+                    qualified_table_name(schema, &table_in.table_name),
+                    composite_foreign_key_definition(schema, composite_foreign_key)
+                )
+            });
+    column_foreign_key_queries
+        .chain(composite_foreign_key_queries)
+        .collect()
+}
+
+// semicolons in string literals, quoted identifiers, comments and dollar quoted bodies
+// don't end a statement, statements holding nothing but comments are dropped
+fn split_statements(sql: &str) -> Vec<String> {
+    let mut statements = vec![];
+    let mut statement = String::new();
+    let mut has_code = false;
+    let mut characters = sql.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            ';' => {
+                if has_code {
+                    statements.push(statement.trim().to_string());
+                }
+                statement.clear();
+                has_code = false;
+                continue;
+            }
+            '-' if characters.peek() == Some(&'-') => {
+                statement.push(character);
+                for comment_character in characters.by_ref() {
+                    statement.push(comment_character);
+                    if comment_character == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '/' if characters.peek() == Some(&'*') => {
+                statement.push(character);
+                // the opening star can't also close the comment
+                statement.extend(characters.next());
+                let mut previous_character = ' ';
+                for comment_character in characters.by_ref() {
+                    statement.push(comment_character);
+                    if previous_character == '*' && comment_character == '/' {
+                        break;
+                    }
+                    previous_character = comment_character;
+                }
+                continue;
+            }
+            // a doubled quote closes and reopens the quotes, which keeps it inside
+            '\'' | '"' => {
+                statement.push(character);
+                for quoted_character in characters.by_ref() {
+                    statement.push(quoted_character);
+                    if quoted_character == character {
+                        break;
+                    }
+                }
+            }
+            // $tag$ ... $tag$, a $ followed by a digit is a parameter instead
+            '$' => {
+                let mut tag = String::new();
+                while let Some(tag_character) =
+                    characters.next_if(|c| c.is_alphanumeric() || *c == '_')
+                {
+                    tag.push(tag_character);
+                }
+                statement.push('$');
+                statement.push_str(&tag);
+                if !tag.starts_with(|c: char| c.is_ascii_digit())
+                    && characters.next_if_eq(&'$').is_some()
+                {
+                    statement.push('$');
+                    let delimiter = format!("${}$", tag);
+                    let mut body = String::new();
+                    for body_character in characters.by_ref() {
+                        body.push(body_character);
+                        if body.ends_with(&delimiter) {
+                            break;
+                        }
+                    }
+                    statement.push_str(&body);
+                }
+            }
+            _ => statement.push(character),
+        }
+        has_code |= !character.is_whitespace();
+    }
+    if has_code {
+        statements.push(statement.trim().to_string());
+    }
+    statements
+}
+
+// sqlite keeps the declared type so it's mapped to the postgres names used by DataType
+fn sqlite_data_type(declared_type: &str) -> String {
+    let declared_type = declared_type.to_lowercase();
+    if declared_type.contains("bigint") {
+        String::from("bigint")
+    } else if declared_type.contains("smallint") {
+        String::from("smallint")
+    } else if declared_type.contains("int") || declared_type == "serial" {
+        String::from("integer")
+    } else if ["real", "floa", "doub"]
+        .iter()
+        .any(|float_type| declared_type.contains(float_type))
+    {
+        // the same substrings sqlite checks when it gives a column REAL affinity
+        String::from("double precision")
+    } else if let Some(length) = declared_type
+        .strip_prefix("varchar(")
+        .or_else(|| declared_type.strip_prefix("character varying("))
+        .and_then(|length| length.strip_suffix(')'))
+        .filter(|length| length.parse::<u32>().is_ok())
+    {
+        format!("character varying({})", length)
+    } else if declared_type.contains("bool") {
+        String::from("boolean")
+    } else if declared_type.contains("timestamp") || declared_type.contains("date") {
+        String::from("timestamp without time zone")
+    } else {
+        String::from("text")
+    }
+}
+
+struct SqliteColumn {
+    name: String,
+    declared_type: String,
+    not_null: bool,
+    default_value: Option<String>,
+    primary_key: bool,
+    unique: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct Repository {
-    pool: PgPool,
+    pool: DbPool,
     console: Arc<RepositoryConsole>,
+    keep_alive_interval: Option<Duration>,
+    retry_config: RetryConfig,
+    // postgres schema the tables are listed from and created in
+    schema: Arc<Mutex<String>>,
+    // schema changes are only written to the console instead of being executed
+    dry_run: Arc<Mutex<bool>>,
 }
 
 impl Repository {
-    pub async fn new(existing_pool: Option<PgPool>, console: Arc<RepositoryConsole>) -> Self {
-        if let Some(pool) = existing_pool {
-            Self { pool, console }
-        } else {
-            let pool = create_database_pool().await;
-            Self { pool, console }
+    pub async fn new(pool: DbPool, console: Arc<RepositoryConsole>) -> Self {
+        Self {
+            schema: Arc::new(Mutex::new(String::from(pool.default_schema()))),
+            pool,
+            console,
+            keep_alive_interval: get_keep_alive_interval(),
+            retry_config: RetryConfig::from_env(),
+            dry_run: Arc::new(Mutex::new(false)),
         }
     }
 
+    pub fn keep_alive_interval(&self) -> Option<Duration> {
+        self.keep_alive_interval
+    }
+
+    pub fn schema(&self) -> String {
+        self.schema.lock().unwrap().clone()
+    }
+
+    pub fn set_schema(&self, schema: String) {
+        *self.schema.lock().unwrap() = schema;
+    }
+
+    pub fn dry_run(&self) -> bool {
+        *self.dry_run.lock().unwrap()
+    }
+
+    pub fn set_dry_run(&self, dry_run: bool) {
+        *self.dry_run.lock().unwrap() = dry_run;
+    }
+
+    fn schema_qualifier(&self) -> Option<String> {
+        match self.pool {
+            DbPool::Postgres(_) => Some(self.schema()),
+            DbPool::Sqlite(_) => None,
+        }
+    }
+
+    fn table_reference(&self, table_name: &str) -> String {
+        qualified_table_name(self.schema_qualifier().as_deref(), table_name)
+    }
+
+    // the schemas created by postgres itself are left out
+    pub async fn get_schema_names(&self) -> Result<Vec<String>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                "SELECT schema_name::TEXT FROM information_schema.schemata
+                    WHERE schema_name NOT LIKE 'pg\\_%' AND schema_name <> 'information_schema'
+                    ORDER BY schema_name"
+            }
+            DbPool::Sqlite(_) => return Ok(vec![self.schema()]),
+        };
+        let result = self.pool.fetch_text_rows(query, &[]).await;
+        Ok(self
+            .log_query_error(query, result)
+            .await?
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect())
+    }
+
+    // the settings in effect, whether they came from the environment or the defaults
+    pub async fn log_pool_settings(&self) {
+        let (max_connections, acquire_timeout, idle_timeout) = match &self.pool {
+            DbPool::Postgres(pool) => {
+                let options = pool.options();
+                (
+                    options.get_max_connections(),
+                    options.get_acquire_timeout(),
+                    options.get_idle_timeout(),
+                )
+            }
+            DbPool::Sqlite(pool) => {
+                let options = pool.options();
+                (
+                    options.get_max_connections(),
+                    options.get_acquire_timeout(),
+                    options.get_idle_timeout(),
+                )
+            }
+        };
+        let idle_timeout = idle_timeout.map_or(String::from("none"), |idle_timeout| {
+            format!("{}s", idle_timeout.as_secs())
+        });
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Connection pool: max connections {}, acquire timeout {}s, idle timeout {}",
+                max_connections,
+                acquire_timeout.as_secs(),
+                idle_timeout
+            ),
+        )
+        .await;
+    }
+
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        self.pool.execute("SELECT 1").await
+    }
+
+    // the pool replaces broken connections on acquire, so pinging again with backoff
+    // reconnects as soon as the database is reachable
+    pub async fn reconnect(&self) -> Result<(), sqlx::Error> {
+        self.retry_transient_errors("SELECT 1", || self.ping())
+            .await
+    }
+
+    // sqlite databases are named by their file, an in memory one has none
+    pub async fn get_database_name(&self) -> Result<String, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => "SELECT current_database()::TEXT",
+            DbPool::Sqlite(_) => "SELECT file FROM pragma_database_list WHERE name = 'main'",
+        };
+        let rows = self.pool.fetch_text_rows(query, &[]).await?;
+        Ok(rows
+            .into_iter()
+            .next()
+            .and_then(|row| row.into_iter().next())
+            .filter(|database_name| !database_name.is_empty())
+            .unwrap_or_else(|| String::from("in memory")))
+    }
+
     async fn log_query(&self, query: String) {
+        self.log(LogLevel::Info, query).await;
+    }
+
+    async fn log(&self, level: LogLevel, message: String) {
         let console = self.console.clone();
         task::spawn_blocking(move || {
-            console.write(query);
+            console.write(level, message);
         })
         .await;
     }
 
+    // failed queries are written to the console together with the error
+    async fn log_query_result<T>(
+        &self,
+        query: String,
+        result: Result<T, sqlx::Error>,
+    ) -> Result<T, sqlx::Error> {
+        match &result {
+            Ok(_) => self.log_query(query).await,
+            Err(error) => {
+                self.log(LogLevel::Error, format!("{}\nError: {}", query, error))
+                    .await
+            }
+        }
+        result
+    }
+
+    // read queries are only written to the console when they fail
+    async fn log_query_error<T>(
+        &self,
+        query: &str,
+        result: Result<T, sqlx::Error>,
+    ) -> Result<T, sqlx::Error> {
+        if let Err(error) = &result {
+            self.log(LogLevel::Error, format!("{}\nError: {}", query, error))
+                .await;
+        }
+        result
+    }
+
+    // only for idempotent reads, every attempt runs the query again
+    async fn retry_transient_errors<T, F, Fut>(
+        &self,
+        query: &str,
+        mut run_query: F,
+    ) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut retry = 0;
+        loop {
+            match run_query().await {
+                Err(error)
+                    if retry < self.retry_config.max_retries && is_transient_error(&error) =>
+                {
+                    let backoff = self.retry_config.backoff(retry);
+                    retry += 1;
+                    self.log(
+                        LogLevel::Warning,
+                        format!(
+                            "{}\nError: {}\nRetrying in {}ms (attempt {} of {})",
+                            query,
+                            error,
+                            backoff.as_millis(),
+                            retry,
+                            self.retry_config.max_retries
+                        ),
+                    )
+                    .await;
+                    tokio::time::sleep(backoff).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // the generated statements are logged without touching the schema in a dry run,
+    // either way they are returned so callers can show what was or would have been run
+    async fn execute_ddl(&self, query: String) -> Result<Vec<String>, sqlx::Error> {
+        if self.dry_run() {
+            self.log_dry_run(vec![query.clone()]).await;
+            return Ok(vec![query]);
+        }
+        let result = self.pool.execute(&query).await;
+        self.log_query_result(query.clone(), result).await?;
+        Ok(vec![query])
+    }
+
+    async fn execute_ddl_in_transaction(
+        &self,
+        queries: Vec<String>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        if self.dry_run() {
+            self.log_dry_run(queries.clone()).await;
+            return Ok(queries);
+        }
+        self.execute_queries_in_transaction(queries.clone()).await?;
+        Ok(queries)
+    }
+
+    async fn log_dry_run(&self, queries: Vec<String>) {
+        self.log(
+            LogLevel::Info,
+            format!("Dry run, not executed:\n{}", queries.join(";\n")),
+        )
+        .await;
+    }
+
+    async fn execute_queries_in_transaction(
+        &self,
+        queries: Vec<String>,
+    ) -> Result<(), sqlx::Error> {
+        match &self.pool {
+            DbPool::Postgres(pool) => {
+                let mut transaction = pool.begin().await?;
+                for query in queries {
+                    println!("{}", query);
+                    let result = sqlx::query(&query).execute(&mut *transaction).await;
+                    if let Err(error) = self.log_query_result(query, result).await {
+                        transaction.rollback().await?;
+                        self.log(LogLevel::Error, String::from("Transaction rolled back"))
+                            .await;
+                        return Err(error);
+                    }
+                }
+                transaction.commit().await?;
+                self.log_query(String::from("Transaction committed")).await;
+                Ok(())
+            }
+            DbPool::Sqlite(pool) => {
+                let mut transaction = pool.begin().await?;
+                for query in queries {
+                    println!("{}", query);
+                    let result = sqlx::query(&query).execute(&mut *transaction).await;
+                    if let Err(error) = self.log_query_result(query, result).await {
+                        transaction.rollback().await?;
+                        self.log(LogLevel::Error, String::from("Transaction rolled back"))
+                            .await;
+                        return Err(error);
+                    }
+                }
+                transaction.commit().await?;
+                self.log_query(String::from("Transaction committed")).await;
+                Ok(())
+            }
+        }
+    }
+
     pub async fn get_primary_key_column_names(
         &self,
         table_name: &str,
     ) -> Result<Vec<String>, sqlx::Error> {
-        let query = r#"
-                            SELECT kcu.column_name
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                r#"
+                            SELECT kcu.column_name::TEXT
                             FROM information_schema.table_constraints AS tc
                             JOIN information_schema.key_column_usage AS kcu
                             ON tc.constraint_name = kcu.constraint_name
                             AND tc.table_name = kcu.table_name
+                            AND tc.table_schema = kcu.table_schema
                             WHERE tc.constraint_type = 'PRIMARY KEY'
                             AND tc.table_name = $1
-                         "#;
+                            AND tc.table_schema = $2
+                         "#
+            }
+            DbPool::Sqlite(_) => "SELECT name FROM pragma_table_info(?1) WHERE pk > 0 ORDER BY pk",
+        };
 
-        let primary_key_column_names: Vec<String> = sqlx::query(query)
-            .bind(table_name)
-            .fetch_all(&self.pool)
+        let result = self.fetch_table_introspection_rows(query, table_name).await;
+        let primary_key_column_names: Vec<String> = self
+            .log_query_error(query, result)
             .await?
             .into_iter()
-            .map(|row| row.get("column_name"))
+            .map(|mut row| row.remove(0))
             .collect();
         Ok(primary_key_column_names)
     }
 
+    // the columns of each primary key and unique constraint in their key order, the primary key
+    // first, these are the column sets a foreign key can reference
+    pub async fn get_key_column_names(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<Vec<String>>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                r#"
+                            SELECT tc.constraint_name::TEXT, kcu.column_name::TEXT
+                            FROM information_schema.table_constraints AS tc
+                            JOIN information_schema.key_column_usage AS kcu
+                            ON tc.constraint_name = kcu.constraint_name
+                            AND tc.table_name = kcu.table_name
+                            AND tc.table_schema = kcu.table_schema
+                            WHERE tc.constraint_type IN ('PRIMARY KEY', 'UNIQUE')
+                            AND tc.table_name = $1
+                            AND tc.table_schema = $2
+                            ORDER BY tc.constraint_type, tc.constraint_name, kcu.ordinal_position
+                         "#
+            }
+            DbPool::Sqlite(_) => {
+                // an INTEGER PRIMARY KEY has no index so the primary key comes from the columns
+                "SELECT key_name, column_name FROM (
+                            SELECT '' AS key_name, name AS column_name, pk AS position
+                                FROM pragma_table_info(?1) WHERE pk > 0
+                            UNION ALL
+                            SELECT il.name, ii.name, ii.seqno
+                                FROM pragma_index_list(?1) AS il
+                                JOIN pragma_index_info(il.name) AS ii
+                                WHERE il.\"unique\" = 1 AND il.origin = 'u'
+                        ) ORDER BY key_name, position"
+            }
+        };
+
+        let result = self.fetch_table_introspection_rows(query, table_name).await;
+        let mut keys: Vec<(String, Vec<String>)> = vec![];
+        for row in self.log_query_error(query, result).await? {
+            match keys.last_mut() {
+                Some((key_name, column_names)) if *key_name == row[0] => {
+                    column_names.push(row[1].clone());
+                }
+                _ => keys.push((row[0].clone(), vec![row[1].clone()])),
+            }
+        }
+        Ok(keys
+            .into_iter()
+            .map(|(_, column_names)| column_names)
+            .collect())
+    }
+
+    // postgres queries get the schema as their second parameter
+    async fn fetch_table_introspection_rows(
+        &self,
+        query: &str,
+        table_name: &str,
+    ) -> Result<Vec<Vec<String>>, sqlx::Error> {
+        match self.schema_qualifier() {
+            Some(schema) => {
+                self.pool
+                    .fetch_text_rows(query, &[table_name, &schema])
+                    .await
+            }
+            None => self.pool.fetch_text_rows(query, &[table_name]).await,
+        }
+    }
+
+    pub async fn get_default_column_names(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                // identity and computed columns reject explicit values, even NULL
+                "SELECT column_name::TEXT AS column_name
+                        FROM information_schema.columns
+                        WHERE table_name = $1 AND table_schema = $2
+                            AND (column_default IS NOT NULL
+                                OR is_identity = 'YES'
+                                OR is_generated = 'ALWAYS')"
+            }
+            DbPool::Sqlite(_) => {
+                "SELECT name FROM pragma_table_info(?1) WHERE dflt_value IS NOT NULL"
+            }
+        };
+
+        let default_column_names: Vec<String> = self
+            .fetch_table_introspection_rows(query, table_name)
+            .await?
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect();
+        Ok(default_column_names)
+    }
+
     pub async fn get_general_tables_info(&self) -> Result<Vec<TableGeneralInfo>, sqlx::Error> {
+        let pool = match &self.pool {
+            DbPool::Postgres(pool) => pool,
+            // a local file has no connection to lose
+            DbPool::Sqlite(_) => return self.get_sqlite_general_tables_info().await,
+        };
         let query = "
         SELECT
             t.table_name,
             array_agg(c.column_name::TEXT) AS column_names,
-            array_agg(c.data_type::TEXT) AS data_types,
+            array_agg(
+                CASE
+                    WHEN c.character_maximum_length IS NOT NULL
+                        AND c.data_type = 'character varying'
+                        THEN format('%s(%s)', c.data_type, c.character_maximum_length)
+                    ELSE c.data_type::TEXT
+                END
+            ) AS data_types,
             array_agg(
                 CASE 
                     WHEN u.column_name IS NOT NULL THEN true 
@@ -91,161 +1050,1164 @@ impl Repository {
             ON
                 tc.constraint_name = kcu.constraint_name
                 AND tc.table_name = kcu.table_name
+                AND tc.table_schema = kcu.table_schema
             WHERE
                 tc.constraint_type IN ('UNIQUE', 'PRIMARY KEY')
+                AND tc.table_schema = $1
         ) u
         ON
             c.table_name = u.table_name AND c.column_name = u.column_name
         WHERE
-            t.table_schema = 'public'
+            t.table_schema = $1
             AND t.table_type = 'BASE TABLE'
         GROUP BY
             t.table_name";
-        let res = sqlx::query_as::<_, TableGeneralInfo>(query)
-            .fetch_all(&self.pool)
+        let schema = self.schema();
+        let res = self
+            .retry_transient_errors(query, || {
+                sqlx::query_as::<_, TableGeneralInfo>(query)
+                    .bind(schema.clone())
+                    .fetch_all(pool)
+            })
+            .await;
+        self.log_query_error(query, res).await
+    }
+
+    async fn get_sqlite_table_names(&self) -> Result<Vec<String>, sqlx::Error> {
+        let query = "SELECT name FROM sqlite_master
+                        WHERE type = 'table' AND name NOT LIKE 'sqlite_%'";
+        Ok(self
+            .pool
+            .fetch_text_rows(query, &[])
+            .await?
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect())
+    }
+
+    async fn get_sqlite_columns(&self, table_name: &str) -> Result<Vec<SqliteColumn>, sqlx::Error> {
+        let query = "SELECT
+                            p.name,
+                            p.type,
+                            CAST(p.\"notnull\" AS TEXT),
+                            COALESCE(p.dflt_value, ''),
+                            CAST(p.dflt_value IS NULL AS TEXT),
+                            CAST(p.pk > 0 AS TEXT),
+                            CAST(EXISTS (
+                                SELECT 1 FROM pragma_index_list(?1) AS il
+                                JOIN pragma_index_info(il.name) AS ii
+                                WHERE il.\"unique\" = 1 AND il.origin = 'u' AND ii.name = p.name
+                            ) AS TEXT)
+                        FROM pragma_table_info(?1) AS p
+                        ORDER BY p.cid";
+        Ok(self
+            .pool
+            .fetch_text_rows(query, &[table_name])
+            .await?
+            .into_iter()
+            .map(|row| SqliteColumn {
+                name: row[0].clone(),
+                declared_type: row[1].clone(),
+                not_null: row[2] == "1",
+                default_value: if row[4] == "1" {
+                    None
+                } else {
+                    Some(row[3].clone())
+                },
+                primary_key: row[5] == "1",
+                unique: row[6] == "1",
+            })
+            .collect())
+    }
+
+    async fn get_sqlite_general_tables_info(&self) -> Result<Vec<TableGeneralInfo>, sqlx::Error> {
+        let mut tables_general_info = vec![];
+        for table_name in self.get_sqlite_table_names().await? {
+            let columns = self.get_sqlite_columns(&table_name).await?;
+            tables_general_info.push(TableGeneralInfo {
+                table_name,
+                column_names: columns.iter().map(|column| column.name.clone()).collect(),
+                data_types: columns
+                    .iter()
+                    .map(|column| sqlite_data_type(&column.declared_type))
+                    .collect(),
+                is_unique: columns
+                    .iter()
+                    .map(|column| column.primary_key || column.unique)
+                    .collect(),
+            });
+        }
+        Ok(tables_general_info)
+    }
+
+    pub async fn get_columns_info(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<ColumnsInfo>, sqlx::Error> {
+        let pool = match &self.pool {
+            DbPool::Postgres(pool) => pool,
+            DbPool::Sqlite(_) => return self.get_sqlite_columns_info(table_name).await,
+        };
+        let query = "SELECT
+                            c.column_name,
+                            CASE
+                                WHEN c.character_maximum_length IS NOT NULL
+                                    AND c.data_type = 'character varying'
+                                    THEN format('%s(%s)', c.data_type, c.character_maximum_length)
+                                ELSE c.data_type::TEXT
+                            END AS data_type,
+                            c.is_nullable::TEXT AS is_nullable,
+                            c.column_default::TEXT AS column_default,
+                            ARRAY_AGG(tc.constraint_type::TEXT) AS constraint_types,
+                            ARRAY_AGG(ccu.table_name::TEXT) AS referenced_tables,
+                            ARRAY_AGG(ccu.column_name::TEXT) AS referenced_columns,
+                            ARRAY_AGG(rc.delete_rule::TEXT) AS delete_rules,
+                            col_description(
+                                format('%I.%I', c.table_schema, c.table_name)::regclass,
+                                c.ordinal_position::INT
+                            ) AS column_comment,
+                            c.generation_expression::TEXT AS generation_expression,
+                            (c.is_identity = 'YES' OR c.is_generated = 'ALWAYS') AS is_generated
+                        FROM
+                            information_schema.columns AS c
+                        LEFT JOIN
+                            information_schema.key_column_usage AS kcu
+                            ON c.table_name = kcu.table_name
+                            AND c.table_schema = kcu.table_schema
+                            AND c.column_name = kcu.column_name
+                        LEFT JOIN
+                            information_schema.table_constraints AS tc
+                            ON tc.constraint_name = kcu.constraint_name
+                            AND tc.table_name = c.table_name
+                            AND tc.table_schema = c.table_schema
+                        LEFT JOIN
+                            information_schema.referential_constraints AS rc
+                            ON rc.constraint_name = tc.constraint_name
+                        LEFT JOIN
+                            information_schema.constraint_column_usage AS ccu
+                            ON ccu.constraint_name = rc.unique_constraint_name
+                        WHERE
+                            c.table_name = $1 AND c.table_schema = $2
+                        GROUP BY c.column_name, c.data_type, c.character_maximum_length,
+                            c.is_nullable, c.column_default,
+                            c.is_identity, c.is_generated, c.generation_expression,
+                            c.table_schema, c.table_name, c.ordinal_position";
+        let parameters = (table_name, self.schema());
+
+        let res = sqlx::query_as::<_, ColumnsInfo>(query)
+            .bind(parameters.0)
+            .bind(parameters.1)
+            .fetch_all(pool)
+            .await;
+        self.log_query_error(query, res).await
+    }
+
+    async fn get_sqlite_columns_info(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<ColumnsInfo>, sqlx::Error> {
+        let foreign_keys_query = "SELECT \"from\", \"table\", \"to\", on_delete
+                                    FROM pragma_foreign_key_list(?1)";
+        let foreign_keys = self
+            .pool
+            .fetch_text_rows(foreign_keys_query, &[table_name])
+            .await?;
+        let columns = self.get_sqlite_columns(table_name).await?;
+        Ok(columns
+            .into_iter()
+            .map(|column| {
+                let mut constraint_types = vec![];
+                let mut referenced_tables = vec![];
+                let mut referenced_columns = vec![];
+                let mut delete_rules = vec![];
+                if column.primary_key {
+                    constraint_types.push(Some(String::from("PRIMARY KEY")));
+                    referenced_tables.push(None);
+                    referenced_columns.push(None);
+                    delete_rules.push(None);
+                }
+                if column.unique {
+                    constraint_types.push(Some(String::from("UNIQUE")));
+                    referenced_tables.push(None);
+                    referenced_columns.push(None);
+                    delete_rules.push(None);
+                }
+                for foreign_key in &foreign_keys {
+                    if foreign_key[0] == column.name {
+                        constraint_types.push(Some(String::from("FOREIGN KEY")));
+                        referenced_tables.push(Some(foreign_key[1].clone()));
+                        referenced_columns.push(Some(foreign_key[2].clone()));
+                        delete_rules.push(Some(foreign_key[3].clone()));
+                    }
+                }
+                ColumnsInfo {
+                    column_name: column.name,
+                    data_type: sqlite_data_type(&column.declared_type),
+                    is_nullable: String::from(if column.not_null { "NO" } else { "YES" }),
+                    column_default: column.default_value,
+                    constraint_types,
+                    referenced_tables,
+                    referenced_columns,
+                    delete_rules,
+                    // sqlite has no column comments
+                    column_comment: None,
+                    // pragma_table_info leaves generated columns out
+                    generation_expression: None,
+                    is_generated: false,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn get_indexes(&self, table_name: &str) -> Result<Vec<Index>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                "SELECT indexname::TEXT, indexdef,
+                        EXISTS (SELECT 1 FROM pg_constraint
+                            WHERE conindid = format('%I.%I', schemaname, indexname)::regclass
+                        )::TEXT
+                    FROM pg_indexes
+                    WHERE tablename = $1 AND schemaname = $2 ORDER BY indexname"
+            }
+            // the indexes sqlite creates for constraints have no statement
+            DbPool::Sqlite(_) => {
+                "SELECT name, sql, 'false' FROM sqlite_master
+                    WHERE type = 'index' AND tbl_name = ?1 AND sql IS NOT NULL ORDER BY name"
+            }
+        };
+        let result = self.fetch_table_introspection_rows(query, table_name).await;
+        Ok(self
+            .log_query_error(query, result)
+            .await?
+            .into_iter()
+            .map(|mut row| Index {
+                is_constraint: row.remove(2) == "true",
+                definition: row.remove(1),
+                name: row.remove(0),
+            })
+            .collect())
+    }
+
+    pub async fn create_index(
+        &self,
+        table_name: &str,
+        column_names: &[String],
+        unique: bool,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = format!(
+            "CREATE {}INDEX \"idx_{}_{}\" ON {} ({})",
+            if unique { "UNIQUE " } else { "" },
+            table_name,
+            column_names.join("_"),
+            self.table_reference(table_name),
+            column_names
+                .iter()
+                .map(|column_name| format!("\"{}\"", column_name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        self.execute_ddl(query).await
+    }
+
+    // an index lives in the schema of its table
+    pub async fn drop_index(&self, index_name: &str) -> Result<Vec<String>, sqlx::Error> {
+        let query = format!("DROP INDEX {}", self.table_reference(index_name));
+        self.execute_ddl(query).await
+    }
+
+    pub async fn get_composite_foreign_keys_info(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<CompositeForeignKeyInfo>, sqlx::Error> {
+        let query = "SELECT
+                            array_agg(a.attname::TEXT ORDER BY k.ordinality) AS column_names,
+                            rt.relname::TEXT AS referenced_table,
+                            array_agg(ra.attname::TEXT ORDER BY k.ordinality) AS referenced_column_names
+                        FROM pg_catalog.pg_constraint c
+                        JOIN pg_class t ON t.oid = c.conrelid
+                        JOIN pg_class rt ON rt.oid = c.confrelid
+                        CROSS JOIN LATERAL unnest(c.conkey, c.confkey)
+                            WITH ORDINALITY AS k(attnum, referenced_attnum, ordinality)
+                        JOIN pg_attribute a
+                            ON a.attrelid = c.conrelid AND a.attnum = k.attnum
+                        JOIN pg_attribute ra
+                            ON ra.attrelid = c.confrelid AND ra.attnum = k.referenced_attnum
+                        WHERE t.relname = $1
+                            AND t.relnamespace = $2::regnamespace
+                            AND c.contype = 'f'
+                            AND array_length(c.conkey, 1) > 1
+                        GROUP BY c.conname, rt.relname";
+        match &self.pool {
+            DbPool::Postgres(pool) => {
+                sqlx::query_as::<_, CompositeForeignKeyInfo>(query)
+                    .bind(table_name)
+                    .bind(qualified_table_name(None, &self.schema()))
+                    .fetch_all(pool)
+                    .await
+            }
+            DbPool::Sqlite(_) => {
+                self.get_sqlite_composite_foreign_keys_info(table_name)
+                    .await
+            }
+        }
+    }
+
+    async fn get_sqlite_composite_foreign_keys_info(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<CompositeForeignKeyInfo>, sqlx::Error> {
+        let query = "SELECT CAST(id AS TEXT), \"table\", \"from\", \"to\"
+                        FROM pragma_foreign_key_list(?1)
+                        ORDER BY id, seq";
+        let mut composite_foreign_keys: Vec<(String, CompositeForeignKeyInfo)> = vec![];
+        for row in self.pool.fetch_text_rows(query, &[table_name]).await? {
+            match composite_foreign_keys.last_mut() {
+                Some((id, composite_foreign_key)) if *id == row[0] => {
+                    composite_foreign_key.column_names.push(row[2].clone());
+                    composite_foreign_key
+                        .referenced_column_names
+                        .push(row[3].clone());
+                }
+                _ => composite_foreign_keys.push((
+                    row[0].clone(),
+                    CompositeForeignKeyInfo {
+                        column_names: vec![row[2].clone()],
+                        referenced_table: row[1].clone(),
+                        referenced_column_names: vec![row[3].clone()],
+                    },
+                )),
+            }
+        }
+        Ok(composite_foreign_keys
+            .into_iter()
+            .map(|(_, composite_foreign_key)| composite_foreign_key)
+            .filter(|composite_foreign_key| composite_foreign_key.column_names.len() > 1)
+            .collect())
+    }
+
+    pub async fn get_primary_key_constraint(
+        &self,
+        table_name: &str,
+    ) -> Result<Option<PrimaryKeyConstraint>, sqlx::Error> {
+        let query = "SELECT c.conname
+                FROM pg_catalog.pg_constraint c
+                JOIN pg_class t ON t.oid = c.conrelid
+                WHERE t.relname = $1 AND t.relnamespace = $2::regnamespace AND c.contype ='p'";
+        // sqlite primary keys can't be dropped so there is no constraint name
+        let DbPool::Postgres(pool) = &self.pool else {
+            return Ok(None);
+        };
+        let res = sqlx::query_as::<_, PrimaryKeyConstraint>(query)
+            .bind(table_name)
+            .bind(qualified_table_name(None, &self.schema()))
+            .fetch_optional(pool)
             .await;
         res
     }
 
-    pub async fn get_columns_info(
+    // sqlite has no SERIAL type, AUTOINCREMENT is added to the primary key instead,
+    // and no UUID type so uuids are stored as text
+    fn data_type_definition(&self, data_type: &DataType) -> String {
+        match (&self.pool, data_type) {
+            (DbPool::Sqlite(_), DataType::SERIAL) => DataType::INTEGER.to_string(),
+            (DbPool::Sqlite(_), DataType::UUID) => DataType::TEXT.to_string(),
+            _ => data_type.to_string(),
+        }
+    }
+
+    // primary keys are left out since they are declared at the table level
+    fn column_definition(&self, column: &Column) -> String {
+        let mut column_configuration = vec![format!(
+            "\"{}\" {}",
+            column.name,
+            self.data_type_definition(&column.datatype)
+        )];
+        if let Some(generated) = &column.generated {
+            column_configuration.push(format!("GENERATED ALWAYS AS ({}) STORED", generated));
+        } else if column.default_value.is_some() {
+            // written like a data value of the column, an empty default is NULL unless it holds text
+            column_configuration.push(format!(
+                "DEFAULT {}",
+                column.datatype.to_sql_literal(&column.default_value)
+            ));
+        } else if column.datatype == DataType::UUID
+            && column.constraints.contains(&Constraint::PrimaryKey)
+            && matches!(self.pool, DbPool::Postgres(_))
+        {
+            // uuid primary keys are generated unless a value is given
+            column_configuration.push(String::from("DEFAULT gen_random_uuid()"));
+        }
+        for constraint in &column.constraints {
+            match constraint {
+                Constraint::ForeignKey {
+                    table,
+                    column,
+                    on_delete,
+                } => {
+                    column_configuration.push(format!(
+                        "REFERENCES {}(\"{}\")",
+                        self.table_reference(table),
+                        column
+                    ));
+                    if *on_delete != ReferentialAction::NoAction {
+                        column_configuration.push(format!("ON DELETE {}", on_delete));
+                    }
+                }
+                Constraint::PrimaryKey => {}
+                Constraint::NotNull => {
+                    column_configuration.push(String::from("NOT NULL"));
+                }
+                Constraint::Unique => {
+                    column_configuration.push(String::from("UNIQUE"));
+                }
+                Constraint::Check(expression) => {
+                    column_configuration.push(format!("CHECK ({})", expression));
+                }
+            }
+        }
+        column_configuration.join(" ")
+    }
+
+    pub async fn add_column(
+        &self,
+        table_name: &str,
+        column: &Column,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let mut query = format!(
+            "ALTER TABLE {} ADD COLUMN {}",
+            self.table_reference(table_name),
+            self.column_definition(column)
+        );
+        if column.constraints.contains(&Constraint::PrimaryKey) {
+            query.push_str(" PRIMARY KEY");
+        }
+        self.execute_ddl(query).await
+    }
+
+    // without a USING expression only conversions the database can cast implicitly succeed
+    pub async fn alter_column_type(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+        using_expression: Option<&str>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query =
+            self.get_alter_column_type_query(table_name, column_name, data_type, using_expression);
+        self.execute_ddl(query).await
+    }
+
+    fn get_alter_column_type_query(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+        using_expression: Option<&str>,
+    ) -> String {
+        let mut query = format!(
+            "ALTER TABLE {} ALTER COLUMN \"{}\" TYPE {}",
+            self.table_reference(table_name),
+            column_name,
+            self.data_type_definition(data_type)
+        );
+        if let Some(using_expression) = using_expression {
+            query.push_str(&format!(" USING {}", using_expression));
+        }
+        query
+    }
+
+    // SERIAL is only a shorthand in create table, an existing column becomes an integer
+    // taking its default from a new sequence that starts after the largest value
+    fn get_change_column_data_type_queries(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+    ) -> Vec<String> {
+        if *data_type != DataType::SERIAL || !matches!(self.pool, DbPool::Postgres(_)) {
+            return vec![self.get_alter_column_type_query(
+                table_name,
+                column_name,
+                data_type,
+                Some(&format!("\"{}\"::{}", column_name, data_type)),
+            )];
+        }
+        let sequence_reference =
+            self.table_reference(&format!("{}_{}_seq", table_name, column_name));
+        vec![
+            self.get_alter_column_type_query(
+                table_name,
+                column_name,
+                &DataType::INTEGER,
+                Some(&format!("\"{}\"::{}", column_name, DataType::INTEGER)),
+            ),
+            format!(
+                "CREATE SEQUENCE {} OWNED BY {}.\"{}\"",
+                sequence_reference,
+                self.table_reference(table_name),
+                column_name
+            ),
+            format!(
+                "SELECT setval('{}', COALESCE(MAX(\"{}\"), 0) + 1, false) FROM {}",
+                sequence_reference.replace('\'', "''"),
+                column_name,
+                self.table_reference(table_name)
+            ),
+            format!(
+                "ALTER TABLE {} ALTER COLUMN \"{}\" SET DEFAULT nextval('{}')",
+                self.table_reference(table_name),
+                column_name,
+                sequence_reference.replace('\'', "''")
+            ),
+        ]
+    }
+
+    pub async fn drop_column(
+        &self,
+        table_name: &str,
+        column_name: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = format!(
+            "ALTER TABLE {} DROP COLUMN \"{}\"",
+            self.table_reference(table_name),
+            column_name
+        );
+        self.execute_ddl(query).await
+    }
+
+    pub async fn create_table(&self, table_in: &TableIn) -> Result<Vec<String>, sqlx::Error> {
+        let query = self.get_create_table_query(table_in);
+
+        // Print the query for debugging
+        println!("Generated Query: {}", query);
+
+        let comment_queries = self.get_comment_queries(table_in);
+        if !comment_queries.is_empty() {
+            // the table isn't kept when one of its comments can't be set
+            let mut queries = vec![query];
+            queries.extend(comment_queries);
+            return self.execute_ddl_in_transaction(queries).await;
+        }
+
+        // Execute the query
+        self.execute_ddl(query).await
+    }
+
+    fn get_comment_queries(&self, table_in: &TableIn) -> Vec<String> {
+        if matches!(self.pool, DbPool::Sqlite(_)) {
+            return vec![];
+        }
+        table_in
+            .columns
+            .iter()
+            .filter_map(|column| {
+                column.comment.as_ref().map(|comment| {
+                    format!(
+                        "COMMENT ON COLUMN {}.\"{}\" IS '{}'",
+                        self.table_reference(&table_in.table_name),
+                        column.name.replace('"', "\"\""),
+                        comment.replace('\'', "''")
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn get_create_table_query(&self, table_in: &TableIn) -> String {
+        let mut primary_key_columns = vec![];
+        let primary_key_count = table_in
+            .columns
+            .iter()
+            .filter(|column| column.constraints.contains(&Constraint::PrimaryKey))
+            .count();
+
+        let columns_query_list: Vec<String> = table_in
+            .columns
+            .iter()
+            .map(|column| {
+                let is_primary_key = column.constraints.contains(&Constraint::PrimaryKey);
+                if matches!(self.pool, DbPool::Sqlite(_))
+                    && is_primary_key
+                    && primary_key_count == 1
+                    && column.datatype == DataType::SERIAL
+                {
+                    // AUTOINCREMENT only works on an inline single column primary key
+                    return format!(
+                        "{} PRIMARY KEY AUTOINCREMENT",
+                        self.column_definition(column)
+                    );
+                }
+                if is_primary_key {
+                    primary_key_columns.push(column.name.clone());
+                }
+                self.column_definition(column)
+            })
+            .collect();
+
+        // If there are primary keys, append the PRIMARY KEY constraint
+        let mut full_query_list = columns_query_list.clone();
+        if !primary_key_columns.is_empty() {
+            full_query_list.push(format!(
+                "PRIMARY KEY ({})",
+                primary_key_columns
+                    .iter()
+                    .map(|col| format!("\"{}\"", col))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        for composite_foreign_key in &table_in.composite_foreign_keys {
+            full_query_list.push(composite_foreign_key_definition(
+                self.schema_qualifier().as_deref(),
+                composite_foreign_key,
+            ));
+        }
+
+        let columns_query_joined = format!("({})", full_query_list.join(", "));
+
+        // Construct the full SQL query
+        format!(
+            "CREATE TABLE {} {}",
+            self.table_reference(&table_in.table_name),
+            columns_query_joined
+        )
+    }
+
+    // the table as it would have to be passed to create_table to recreate it
+    async fn get_table_in(&self, table_name: &str) -> Result<TableIn, sqlx::Error> {
+        let composite_foreign_keys: Vec<CompositeForeignKey> = self
+            .get_composite_foreign_keys_info(table_name)
+            .await?
+            .into_iter()
+            .map(CompositeForeignKey::to_composite_foreign_key)
+            .collect();
+        let columns = self
+            .get_columns_info(table_name)
+            .await?
+            .into_iter()
+            .map(Column::to_column)
+            .map(|mut column| {
+                // composite foreign keys are emitted at table level
+                column.constraints.retain(|constraint| {
+                    if let Constraint::ForeignKey { table, .. } = constraint {
+                        !composite_foreign_keys.iter().any(|composite_foreign_key| {
+                            composite_foreign_key.referenced_table == *table
+                                && composite_foreign_key.column_names.contains(&column.name)
+                        })
+                    } else {
+                        true
+                    }
+                });
+                // a sequence default means the column was created as SERIAL
+                if column.datatype == DataType::INTEGER && column.is_serial() {
+                    column.datatype = DataType::SERIAL;
+                    column.default_value = None;
+                }
+                // create_table adds the generated default to uuid primary keys
+                if column.datatype == DataType::UUID
+                    && column.constraints.contains(&Constraint::PrimaryKey)
+                    && column.default_value.as_deref() == Some("gen_random_uuid()")
+                {
+                    column.default_value = None;
+                }
+                column
+            })
+            .collect();
+        Ok(TableIn {
+            table_name: table_name.to_string(),
+            columns,
+            composite_foreign_keys,
+        })
+    }
+
+    pub async fn get_table_metadata(&self, table_name: &str) -> Result<TableMetadata, sqlx::Error> {
+        let table_in = self.get_table_in(table_name).await?;
+        let primary_key_column_names = self.get_primary_key_column_names(table_name).await?;
+        let single_column_foreign_keys = table_in.columns.iter().flat_map(|column| {
+            column
+                .constraints
+                .iter()
+                .filter_map(|constraint| match constraint {
+                    Constraint::ForeignKey {
+                        table,
+                        column: referenced_column,
+                        ..
+                    } => Some(CompositeForeignKey {
+                        column_names: vec![column.name.clone()],
+                        referenced_table: table.clone(),
+                        referenced_column_names: vec![referenced_column.clone()],
+                    }),
+                    _ => None,
+                })
+        });
+        let foreign_keys = single_column_foreign_keys
+            .chain(table_in.composite_foreign_keys.iter().cloned())
+            .collect();
+        Ok(TableMetadata {
+            column_count: table_in.columns.len(),
+            primary_key_column_names,
+            foreign_keys,
+            total_size_bytes: self.get_total_table_size(table_name).await?,
+        })
+    }
+
+    // scans the whole table, so it is only run for the columns the user asks about
+    pub async fn get_column_stats(
+        &self,
+        table_name: &str,
+        column_name: &str,
+    ) -> Result<ColumnStats, sqlx::Error> {
+        let query = format!(
+            "SELECT CAST(COUNT(*) FILTER (WHERE \"{}\" IS NULL) AS TEXT), CAST(COUNT(DISTINCT \"{}\") AS TEXT) FROM {}",
+            column_name,
+            column_name,
+            self.table_reference(table_name)
+        );
+        let result = self.pool.fetch_text_rows(&query, &[]).await;
+        let rows = self.log_query_error(&query, result).await?;
+        let get_count = |index: usize| {
+            rows.first()
+                .and_then(|row| row[index].parse::<i64>().ok())
+                .unwrap_or_default()
+        };
+        Ok(ColumnStats {
+            null_count: get_count(0),
+            distinct_count: get_count(1),
+        })
+    }
+
+    async fn get_total_table_size(&self, table_name: &str) -> Result<Option<i64>, sqlx::Error> {
+        let DbPool::Postgres(_) = self.pool else {
+            return Ok(None);
+        };
+        let query = "SELECT pg_total_relation_size(format('%I.%I', $2, $1)::regclass)::TEXT";
+        let result = self.fetch_table_introspection_rows(query, table_name).await;
+        Ok(self
+            .log_query_error(query, result)
+            .await?
+            .first()
+            .and_then(|row| row[0].parse().ok()))
+    }
+
+    // referenced tables are created before the tables that reference them, foreign keys of
+    // tables in a cycle are added with ALTER TABLE once every table exists
+    pub async fn export_schema(&self) -> Result<String, sqlx::Error> {
+        let mut table_names: Vec<String> = self
+            .get_general_tables_info()
+            .await?
+            .into_iter()
+            .map(|table_general_info| table_general_info.table_name)
+            .collect();
+        table_names.sort();
+        let mut tables_in = vec![];
+        for table_name in table_names {
+            tables_in.push(self.get_table_in(&table_name).await?);
+        }
+        let (ordered_tables_in, cyclic_tables_in) = order_tables_by_dependency(tables_in);
+        let mut queries: Vec<String> = ordered_tables_in
+            .iter()
+            .map(|table_in| self.get_create_table_query(table_in))
+            .collect();
+        queries.extend(
+            cyclic_tables_in
+                .iter()
+                .map(|table_in| self.get_create_table_query(&without_foreign_keys(table_in))),
+        );
+        let schema = self.schema_qualifier();
+        queries.extend(
+            cyclic_tables_in
+                .iter()
+                .flat_map(|table_in| get_add_foreign_key_queries(schema.as_deref(), table_in)),
+        );
+        queries.extend(
+            ordered_tables_in
+                .iter()
+                .chain(&cyclic_tables_in)
+                .flat_map(|table_in| self.get_comment_queries(table_in)),
+        );
+        Ok(join_schema_queries(&queries))
+    }
+
+    // the same statements export_schema emits for the table, its foreign keys stay inline
+    // since the referenced tables are expected to exist wherever it is recreated
+    pub async fn export_table_schema(&self, table_name: &str) -> Result<String, sqlx::Error> {
+        let table_in = self.get_table_in(table_name).await?;
+        let mut queries = vec![self.get_create_table_query(&table_in)];
+        queries.extend(self.get_comment_queries(&table_in));
+        Ok(join_schema_queries(&queries))
+    }
+
+    // constraints and indexes are not copied by CREATE TABLE AS
+
+    // without copy_constraints only the columns and rows are copied, with it the defaults,
+    // constraints and indexes are kept too, postgres' LIKE leaves out the foreign keys so they are
+    // added from the introspected definition, sqlite has no LIKE so the whole definition is
+    // created under the new name, nothing is kept when a step fails
+    pub async fn clone_table(
+        &self,
+        source_table_name: &str,
+        target_table_name: &str,
+        copy_constraints: bool,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        if !copy_constraints {
+            let query = format!(
+                "CREATE TABLE {} AS SELECT * FROM {}",
+                self.table_reference(target_table_name),
+                self.table_reference(source_table_name)
+            );
+            return self.execute_ddl(query).await;
+        }
+        let mut table_in = self.get_table_in(source_table_name).await?;
+        table_in.table_name = target_table_name.to_string();
+        let mut queries = match self.pool {
+            DbPool::Postgres(_) => {
+                let mut queries = vec![format!(
+                    "CREATE TABLE {} (LIKE {} INCLUDING ALL)",
+                    self.table_reference(target_table_name),
+                    self.table_reference(source_table_name)
+                )];
+                queries.extend(get_add_foreign_key_queries(
+                    self.schema_qualifier().as_deref(),
+                    &table_in,
+                ));
+                queries
+            }
+            DbPool::Sqlite(_) => vec![self.get_create_table_query(&table_in)],
+        };
+        queries.push(format!(
+            "INSERT INTO {} SELECT * FROM {}",
+            self.table_reference(target_table_name),
+            self.table_reference(source_table_name)
+        ));
+        self.execute_ddl_in_transaction(queries).await
+    }
+
+    // cascading also drops the foreign keys of the tables referencing this one,
+    // sqlite has no CASCADE and doesn't stop the drop in the first place
+    // unlike clone_table the defaults, constraints and indexes are kept but no rows are copied,
+    // sqlite has no LIKE so the introspected definition is created under the new name
+    pub async fn clone_table_structure(
         &self,
-        table_name: &str,
-    ) -> Result<Vec<ColumnsInfo>, sqlx::Error> {
-        let query = "SELECT
-                            c.column_name,
-                            c.data_type,
-                            ARRAY_AGG(tc.constraint_type::TEXT) AS constraint_types,
-                            ARRAY_AGG(ccu.table_name::TEXT) AS referenced_tables,
-                            ARRAY_AGG(ccu.column_name::TEXT) AS referenced_columns
-                        FROM
-                            information_schema.columns AS c
-                        LEFT JOIN
-                            information_schema.key_column_usage AS kcu
-                            ON c.table_name = kcu.table_name
-                            AND c.column_name = kcu.column_name
-                        LEFT JOIN
-                            information_schema.table_constraints AS tc
-                            ON tc.constraint_name = kcu.constraint_name
-                            AND tc.table_name = c.table_name
-                        LEFT JOIN
-                            information_schema.referential_constraints AS rc
-                            ON rc.constraint_name = tc.constraint_name
-                        LEFT JOIN
-                            information_schema.constraint_column_usage AS ccu
-                            ON ccu.constraint_name = rc.unique_constraint_name
-                        WHERE
-                            c.table_name = $1 
-                        GROUP BY c.column_name, c.data_type";
-        let parameters = (table_name,);
+        source_table_name: &str,
+        target_table_name: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        match self.pool {
+            DbPool::Postgres(_) => {
+                let query = format!(
+                    "CREATE TABLE {} (LIKE {} INCLUDING ALL)",
+                    self.table_reference(target_table_name),
+                    self.table_reference(source_table_name)
+                );
+                self.execute_ddl(query).await
+            }
+            DbPool::Sqlite(_) => {
+                let mut table_in = self.get_table_in(source_table_name).await?;
+                table_in.table_name = target_table_name.to_string();
+                self.create_table(&table_in).await
+            }
+        }
+    }
 
-        let res = sqlx::query_as::<_, ColumnsInfo>(query)
-            .bind(parameters.0)
-            .fetch_all(&self.pool)
-            .await;
-        res
+    // the target is expected to have the columns of the source in the same order
+    pub async fn copy_table_rows(
+        &self,
+        source_table_name: &str,
+        target_table_name: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = format!(
+            "INSERT INTO {} SELECT * FROM {}",
+            self.table_reference(target_table_name),
+            self.table_reference(source_table_name)
+        );
+        self.execute_ddl(query).await
     }
 
-    pub async fn get_primary_key_constraint(
+    pub async fn delete_table(
         &self,
         table_name: &str,
-    ) -> Result<Option<PrimaryKeyConstraint>, sqlx::Error> {
-        let query = "SELECT c.conname
-                FROM pg_catalog.pg_constraint c
-                JOIN pg_class t ON t.oid = c.conrelid
-                WHERE t.relname = $1 AND c.contype ='p'";
-        let res = sqlx::query_as::<_, PrimaryKeyConstraint>(query)
-            .bind(table_name)
-            .fetch_optional(&self.pool)
-            .await;
-        res
+        cascade: bool,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let cascade = match self.pool {
+            DbPool::Postgres(_) if cascade => " CASCADE",
+            _ => "",
+        };
+        let query = format!("DROP TABLE {}{}", self.table_reference(table_name), cascade);
+        self.execute_ddl(query).await
     }
 
-    pub async fn create_table(&self, table_in: &TableIn) {
-        let mut primary_key_columns = vec![];
+    // cascading also empties the tables referencing this one,
+    // sqlite has no TRUNCATE but optimizes a DELETE without a WHERE the same way
+    pub async fn truncate_table(
+        &self,
+        table_name: &str,
+        cascade: bool,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) if cascade => {
+                format!(
+                    "TRUNCATE TABLE {} CASCADE",
+                    self.table_reference(table_name)
+                )
+            }
+            DbPool::Postgres(_) => format!("TRUNCATE TABLE {}", self.table_reference(table_name)),
+            DbPool::Sqlite(_) => format!("DELETE FROM {}", self.table_reference(table_name)),
+        };
+        self.execute_ddl(query).await
+    }
 
-        let columns_query_list: Vec<String> = table_in
-            .columns
-            .iter()
-            .map(|column| {
-                let mut column_configuration =
-                    vec![format!("\"{}\" {}", column.name, column.datatype)];
-                for constraint in &column.constraints {
-                    match constraint {
-                        Constraint::ForeignKey(referenced_table, referenced_column) => {
-                            column_configuration.push(format!(
-                                "REFERENCES \"{}\"(\"{}\")",
-                                referenced_table, referenced_column
-                            ));
+    pub async fn rename_table(
+        &self,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = format!(
+            "ALTER TABLE {} RENAME TO \"{}\"",
+            self.table_reference(old_name),
+            new_name
+        );
+        self.execute_ddl(query).await
+    }
+
+    // every value is returned as TEXT so the result can be shown like table data
+    pub async fn run_query(
+        &self,
+        query: &str,
+        allow_writes: bool,
+    ) -> Result<TableInsertedData, sqlx::Error> {
+        let result = if allow_writes {
+            self.pool.fetch_query_rows(query).await
+        } else {
+            self.pool.fetch_read_only_query_rows(query).await
+        };
+        let (column_names, rows) = self.log_query_result(query.to_string(), result).await?;
+        Ok(query_result(column_names, rows))
+    }
+
+    // the statements run in order in one transaction, the first one that fails rolls back
+    // those before it and is named in the console
+    pub async fn run_script(&self, sql: &str) -> Result<Vec<StatementResult>, sqlx::Error> {
+        let statements = split_statements(sql);
+        let statement_count = statements.len();
+        let mut statement_results = vec![];
+        match &self.pool {
+            DbPool::Postgres(pool) => {
+                let mut transaction = pool.begin().await?;
+                for (index, statement) in statements.into_iter().enumerate() {
+                    let result;
+                    (transaction, result) = run_pg_statement(transaction, statement.clone()).await;
+                    match self.log_query_result(statement, result).await {
+                        Ok(statement_result) => statement_results.push(statement_result),
+                        Err(error) => {
+                            transaction.rollback().await?;
+                            self.log_failed_statement(index, statement_count).await;
+                            return Err(error);
                         }
-                        Constraint::PrimaryKey => {
-                            primary_key_columns.push(column.name.clone());
+                    }
+                }
+                transaction.commit().await?;
+            }
+            DbPool::Sqlite(pool) => {
+                let mut transaction = pool.begin().await?;
+                for (index, statement) in statements.into_iter().enumerate() {
+                    let result;
+                    (transaction, result) =
+                        run_sqlite_statement(transaction, statement.clone()).await;
+                    match self.log_query_result(statement, result).await {
+                        Ok(statement_result) => statement_results.push(statement_result),
+                        Err(error) => {
+                            transaction.rollback().await?;
+                            self.log_failed_statement(index, statement_count).await;
+                            return Err(error);
                         }
                     }
                 }
-                column_configuration.join(" ")
-            })
-            .collect();
-
-        // If there are primary keys, append the PRIMARY KEY constraint
-        let mut full_query_list = columns_query_list.clone();
-        if !primary_key_columns.is_empty() {
-            full_query_list.push(format!(
-                "PRIMARY KEY ({})",
-                primary_key_columns
-                    .iter()
-                    .map(|col| format!("\"{}\"", col))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ));
+                transaction.commit().await?;
+            }
         }
+        self.log_query(String::from("Transaction committed")).await;
+        Ok(statement_results)
+    }
 
-        let columns_query_joined = format!("({})", full_query_list.join(", "));
+    async fn log_failed_statement(&self, index: usize, statement_count: usize) {
+        self.log(
+            LogLevel::Error,
+            format!(
+                "Statement {} of {} failed, transaction rolled back",
+                index + 1,
+                statement_count
+            ),
+        )
+        .await;
+    }
 
-        // Construct the full SQL query
-        let query = format!(
-            "CREATE TABLE \"{}\" {}",
-            table_in.table_name, columns_query_joined
-        );
+    pub async fn get_row_count(&self, table_name: &str) -> Result<i64, sqlx::Error> {
+        self.get_filtered_row_count(table_name, &vec![]).await
+    }
 
-        // Print the query for debugging
-        println!("Generated Query: {}", query);
+    // one COUNT(*) per table, exact unlike the planner statistics
+    pub async fn get_row_counts(&self) -> Result<HashMap<String, i64>, sqlx::Error> {
+        let mut row_counts = HashMap::new();
+        for table_general_info in self.get_general_tables_info().await? {
+            let row_count = self.get_row_count(&table_general_info.table_name).await?;
+            row_counts.insert(table_general_info.table_name, row_count);
+        }
+        Ok(row_counts)
+    }
 
-        // Execute the query
-        sqlx::query(&query).execute(&self.pool).await.unwrap();
-        self.log_query(query).await;
+    pub async fn get_referencing_table_names(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                r#"
+                            SELECT DISTINCT tc.table_name::TEXT AS table_name
+                            FROM information_schema.table_constraints AS tc
+                            JOIN information_schema.constraint_column_usage AS ccu
+                            ON ccu.constraint_name = tc.constraint_name
+                            WHERE tc.constraint_type = 'FOREIGN KEY'
+                            AND ccu.table_name = $1
+                            AND ccu.table_schema = $2
+                         "#
+            }
+            DbPool::Sqlite(_) => {
+                r#"
+                            SELECT DISTINCT m.name
+                            FROM sqlite_master AS m
+                            JOIN pragma_foreign_key_list(m.name) AS f
+                            WHERE m.type = 'table' AND f."table" = ?1
+                         "#
+            }
+        };
+
+        let referencing_table_names: Vec<String> = self
+            .fetch_table_introspection_rows(query, table_name)
+            .await?
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect();
+        Ok(referencing_table_names)
     }
 
-    pub async fn delete_table(&self, table_name: &str) {
-        let query = format!("DROP TABLE \"{}\"", table_name);
-        sqlx::query(&query).execute(&self.pool).await.unwrap();
-        self.log_query(query).await;
+    // (table, column) for every column of the schema's tables whose name contains the pattern,
+    // ignoring case
+    pub async fn search_columns(
+        &self,
+        pattern: &str,
+    ) -> Result<Vec<(String, String)>, sqlx::Error> {
+        let query = match self.pool {
+            DbPool::Postgres(_) => {
+                r#"
+                            SELECT c.table_name::TEXT, c.column_name::TEXT
+                            FROM information_schema.columns AS c
+                            JOIN information_schema.tables AS t
+                            ON t.table_name = c.table_name AND t.table_schema = c.table_schema
+                            WHERE t.table_type = 'BASE TABLE'
+                            AND strpos(lower(c.column_name), lower($1)) > 0
+                            AND c.table_schema = $2
+                            ORDER BY c.table_name, c.ordinal_position
+                         "#
+            }
+            DbPool::Sqlite(_) => {
+                r#"
+                            SELECT m.name, p.name
+                            FROM sqlite_master AS m
+                            JOIN pragma_table_info(m.name) AS p
+                            WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'
+                            AND instr(lower(p.name), lower(?1)) > 0
+                            ORDER BY m.name, p.cid
+                         "#
+            }
+        };
+        let result = self.fetch_table_introspection_rows(query, pattern).await;
+        Ok(self
+            .log_query_error(query, result)
+            .await?
+            .into_iter()
+            .map(|mut row| {
+                let column_name = row.remove(1);
+                (row.remove(0), column_name)
+            })
+            .collect())
     }
 
     fn get_filter_condition(&self, conditions: &Vec<Condition>) -> String {
         conditions
             .iter()
             .map(|condition| {
-                let value = if condition.data_type == DataType::TEXT {
-                    format!("'{}'", condition.value)
+                let value = if condition.data_type.is_quoted() {
+                    format!("'{}'", condition.value.replace('\'', "''"))
                 } else {
                     condition.value.clone()
                 };
-                format!("{} = {}", condition.column_name, value)
+                format!("{} {} {}", condition.column_name, condition.operator, value)
             })
             .collect::<Vec<String>>()
             .join(" AND ")
     }
 
-    fn get_updates(&self, updated_column_values: &HashMap<String, (DataType, String)>) -> String {
+    // filter values are bound as text parameters and cast to the column type,
+    // LIKE compares the text representation of the column
+    fn get_filter_clause(&self, filters: &Vec<Condition>) -> String {
+        if filters.is_empty() {
+            return String::new();
+        }
+        let filter_conditions: Vec<String> = filters
+            .iter()
+            .enumerate()
+            .map(|(index, filter)| {
+                let parameter = self.pool.parameter(index + 1);
+                if filter.operator == Operator::Like {
+                    format!(
+                        "CAST(\"{}\" AS TEXT) LIKE {}",
+                        filter.column_name, parameter
+                    )
+                } else {
+                    // casting to a varchar would truncate the value before comparing it
+                    let cast_type = match filter.data_type {
+                        DataType::SERIAL => DataType::INTEGER.to_string(),
+                        DataType::VARCHAR(_) => DataType::TEXT.to_string(),
+                        _ => filter.data_type.to_string(),
+                    };
+                    format!(
+                        "\"{}\" {} CAST({} AS {})",
+                        filter.column_name, filter.operator, parameter, cast_type
+                    )
+                }
+            })
+            .collect();
+        format!(" WHERE {}", filter_conditions.join(" AND "))
+    }
+
+    pub async fn get_filtered_row_count(
+        &self,
+        table_name: &str,
+        filters: &Vec<Condition>,
+    ) -> Result<i64, sqlx::Error> {
+        let query = format!(
+            "SELECT CAST(COUNT(*) AS TEXT) FROM {}{}",
+            self.table_reference(table_name),
+            self.get_filter_clause(filters)
+        );
+        let parameters: Vec<&str> = filters.iter().map(|filter| filter.value.as_str()).collect();
+        let result = self.pool.fetch_text_rows(&query, &parameters).await;
+        let rows = self.log_query_error(&query, result).await?;
+        Ok(rows[0][0].parse().unwrap_or_default())
+    }
+
+    fn get_updates(
+        &self,
+        updated_column_values: &HashMap<String, (DataType, Option<String>)>,
+    ) -> String {
         updated_column_values
             .iter()
             .map(|(column_name, (data_type, new_value))| {
-                let value = if *data_type == DataType::TEXT {
-                    format!("'{}'", new_value)
-                } else {
-                    new_value.clone()
-                };
-                format!("\"{}\" = {}", column_name, value)
+                format!(
+                    "\"{}\" = {}",
+                    column_name,
+                    data_type.to_sql_literal(new_value)
+                )
             })
             .collect::<Vec<String>>()
             .join(", ")
@@ -256,132 +2218,334 @@ impl Repository {
         table_name: &str,
         table_data_change_events: &Vec<TableDataChangeEvents>,
     ) -> Result<(), sqlx::Error> {
-        // Start a transaction
-        let mut transaction = self.pool.begin().await?;
-        let primary_key_column_names = self.get_primary_key_column_names(table_name).await.unwrap();
+        let queries = self
+            .get_table_data_queries(table_name, table_data_change_events)
+            .await?;
+        // constraint violations are reported to the console instead of panicking
+        self.execute_queries_in_transaction(queries).await
+    }
 
+    // shared by update_table_data and the sql preview so they can't diverge
+    pub async fn get_table_data_queries(
+        &self,
+        table_name: &str,
+        table_data_change_events: &Vec<TableDataChangeEvents>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let primary_key_column_names = self.get_primary_key_column_names(table_name).await?;
+        let default_column_names = self.get_default_column_names(table_name).await?;
+        let mut queries = vec![];
+        // consecutive inserts into the same columns are sent as one multi-row INSERT
+        let mut insert_batch: Option<(Vec<String>, Vec<String>)> = None;
         for event in table_data_change_events {
-            match event {
-                TableDataChangeEvents::ModifyRowColumnValue(row_column_value) => {
-                    let filter_condition = self.get_filter_condition(&row_column_value.conditions);
-                    let updates = self.get_updates(&row_column_value.column_values);
-                    let query = format!(
-                        "UPDATE \"{}\" SET {} WHERE {}",
-                        table_name, // Table for the update
-                        updates,
-                        filter_condition
+            let batch_row = match event {
+                // a generated integer key reads MAX once per statement, so its row can't share one
+                TableDataChangeEvents::InsertRow(row_insert_data)
+                    if !self.generates_integer_primary_key(
+                        row_insert_data,
+                        &primary_key_column_names,
+                    ) =>
+                {
+                    let (column_names, values) = self.get_insert_column_values(
+                        table_name,
+                        row_insert_data,
+                        &primary_key_column_names,
+                        &default_column_names,
                     );
-
-                    // Execute the query with parameters
-                    println!("{}", query);
-                    sqlx::query(&query)
-                        .execute(&mut *transaction)
-                        .await
-                        .unwrap();
-                    self.log_query(query).await;
+                    // DEFAULT VALUES inserts have no column list to share
+                    (!column_names.is_empty())
+                        .then(|| (column_names, format!("({})", values.join(", "))))
                 }
-
-                TableDataChangeEvents::DeleteRow(conditions) => {
-                    let filter_condition = self.get_filter_condition(&conditions);
-                    let query =
-                        format!("DELETE FROM \"{}\" WHERE {}", table_name, filter_condition);
-                    println!("{}", query);
-                    sqlx::query(&query)
-                        .execute(&mut *transaction)
-                        .await
-                        .unwrap();
-                    self.log_query(query).await;
+                _ => None,
+            };
+            match (batch_row, insert_batch.as_mut()) {
+                (Some((column_names, row)), Some((batch_column_names, rows)))
+                    if *batch_column_names == column_names
+                        && rows.len() < MAX_INSERT_BATCH_ROWS =>
+                {
+                    rows.push(row);
                 }
-
-                TableDataChangeEvents::InsertRow(row_insert_data) => {
-                    let (column_names, values): (Vec<String>, Vec<String>) = row_insert_data
-                        .column_names
-                        .iter()
-                        .zip(
-                            row_insert_data
-                                .values
-                                .iter()
-                                .zip(row_insert_data.data_types.iter()),
-                        )
-                        .map(|(column_name, (value, data_type))| {
-                            // Map the filtered columns to (column_name, value) pairs
-                            if value.is_empty() && primary_key_column_names.contains(column_name) {
-                                // Generate values for primary key columns
-                                let generated_value = if *data_type == DataType::INTEGER {
-                                    format!(
-                                        "(SELECT COALESCE(MAX(\"{}\"), 0) + 1 FROM \"{}\")",
-                                        column_name, table_name
-                                    )
-                                } else if *data_type == DataType::TEXT {
-                                    "gen_random_uuid()::TEXT".to_string()
-                                } else {
-                                    "NULL".to_string() // Fallback for unsupported types
-                                };
-
-                                (column_name.to_string(), generated_value)
-                            } else {
-                                (
-                                    column_name.to_string(),
-                                    if value.is_empty() {
-                                        "NULL".to_string()
-                                    } else if *data_type == DataType::TEXT {
-                                        format!("'{}'", value)
-                                    } else {
-                                        value.to_string()
-                                    },
-                                )
-                            }
-                        })
-                        .unzip();
-                    let query = format!(
-                        "INSERT INTO \"{}\" ({}) VALUES {}",
+                (Some((column_names, row)), _) => {
+                    queries.extend(insert_batch.take().map(|(column_names, rows)| {
+                        self.get_insert_query(table_name, &column_names, &rows)
+                    }));
+                    insert_batch = Some((column_names, vec![row]));
+                }
+                (None, _) => {
+                    queries.extend(insert_batch.take().map(|(column_names, rows)| {
+                        self.get_insert_query(table_name, &column_names, &rows)
+                    }));
+                    queries.push(self.get_table_data_query(
                         table_name,
-                        column_names.join(", "),
-                        format!("({})", values.join(", "))
-                    );
-
-                    println!("{}", query);
-                    sqlx::query(&query)
-                        .execute(&mut *transaction)
-                        .await
-                        .unwrap();
-                    self.log_query(query).await;
+                        event,
+                        &primary_key_column_names,
+                        &default_column_names,
+                    ));
                 }
             }
         }
+        queries.extend(
+            insert_batch.take().map(|(column_names, rows)| {
+                self.get_insert_query(table_name, &column_names, &rows)
+            }),
+        );
+        Ok(queries)
+    }
 
-        // Commit the transaction
-        transaction.commit().await.unwrap();
-        Ok(())
+    fn get_table_data_query(
+        &self,
+        table_name: &str,
+        event: &TableDataChangeEvents,
+        primary_key_column_names: &Vec<String>,
+        default_column_names: &Vec<String>,
+    ) -> String {
+        match event {
+            TableDataChangeEvents::ModifyRowColumnValue(row_column_value) => {
+                let filter_condition = self.get_filter_condition(&row_column_value.conditions);
+                let updates = self.get_updates(&row_column_value.column_values);
+                format!(
+                    "UPDATE {} SET {} WHERE {}",
+                    self.table_reference(table_name), // Table for the update
+                    updates,
+                    filter_condition
+                )
+            }
+
+            TableDataChangeEvents::DeleteRow(conditions) => {
+                let filter_condition = self.get_filter_condition(&conditions);
+                format!(
+                    "DELETE FROM {} WHERE {}",
+                    self.table_reference(table_name),
+                    filter_condition
+                )
+            }
+
+            TableDataChangeEvents::InsertRow(row_insert_data) => {
+                let (column_names, values) = self.get_insert_column_values(
+                    table_name,
+                    row_insert_data,
+                    primary_key_column_names,
+                    default_column_names,
+                );
+                self.get_insert_query(
+                    table_name,
+                    &column_names,
+                    &[format!("({})", values.join(", "))],
+                )
+            }
+        }
+    }
+
+    fn generates_integer_primary_key(
+        &self,
+        row_insert_data: &RowInsertData,
+        primary_key_column_names: &Vec<String>,
+    ) -> bool {
+        zip(
+            &row_insert_data.column_names,
+            zip(&row_insert_data.values, &row_insert_data.data_types),
+        )
+        .any(|(column_name, (value, data_type))| {
+            value.as_ref().map_or(true, |value| value.is_empty())
+                && data_type.is_integer()
+                && primary_key_column_names.contains(column_name)
+        })
+    }
+
+    fn get_insert_column_values(
+        &self,
+        table_name: &str,
+        row_insert_data: &RowInsertData,
+        primary_key_column_names: &Vec<String>,
+        default_column_names: &Vec<String>,
+    ) -> (Vec<String>, Vec<String>) {
+        row_insert_data
+            .column_names
+            .iter()
+            .zip(
+                row_insert_data
+                    .values
+                    .iter()
+                    .zip(row_insert_data.data_types.iter()),
+            )
+            // NULL values are left out so the database applies the column default
+            .filter(|(column_name, (value, data_type))| {
+                !(data_type.is_null(value) && default_column_names.contains(*column_name))
+            })
+            .map(|(column_name, (value, data_type))| {
+                // Map the filtered columns to (column_name, value) pairs
+                let is_empty = value.as_ref().map_or(true, |value| value.is_empty());
+                if is_empty && primary_key_column_names.contains(column_name) {
+                    // Generate values for primary key columns
+                    let generated_value = if data_type.is_integer() {
+                        format!(
+                            "(SELECT COALESCE(MAX(\"{}\"), 0) + 1 FROM {})",
+                            column_name,
+                            self.table_reference(table_name)
+                        )
+                    } else if *data_type == DataType::UUID {
+                        // only the column default generates uuids, the filter above
+                        // already left the column out when it has one
+                        "NULL".to_string()
+                    } else if matches!(data_type, DataType::TEXT | DataType::VARCHAR(_)) {
+                        match self.pool {
+                            DbPool::Postgres(_) => "gen_random_uuid()::TEXT",
+                            DbPool::Sqlite(_) => "lower(hex(randomblob(16)))",
+                        }
+                        .to_string()
+                    } else {
+                        "NULL".to_string() // Fallback for unsupported types
+                    };
+
+                    (column_name.to_string(), generated_value)
+                } else {
+                    (column_name.to_string(), data_type.to_sql_literal(value))
+                }
+            })
+            .unzip()
+    }
+
+    fn get_insert_query(
+        &self,
+        table_name: &str,
+        column_names: &[String],
+        rows: &[String],
+    ) -> String {
+        if column_names.is_empty() {
+            format!(
+                "INSERT INTO {} DEFAULT VALUES",
+                self.table_reference(table_name)
+            )
+        } else {
+            format!(
+                "INSERT INTO {} ({}) VALUES {}",
+                self.table_reference(table_name),
+                column_names.join(", "),
+                rows.join(", ")
+            )
+        }
     }
 
     pub async fn get_table_data_rows(
         &self,
         table_name: &str,
         column_names: &Vec<String>,
-        order_by_column_names: &Vec<String>,
-    ) -> Result<Vec<PgRow>, sqlx::Error> {
+        filters: &Vec<Condition>,
+        order_by: &Option<(String, SortDirection)>,
+        primary_key_column_names: &Vec<String>,
+        page: &Page,
+    ) -> Result<Vec<Vec<Option<String>>>, sqlx::Error> {
+        let select_column_names: Vec<String> = column_names
+            .into_iter()
+            .map(|column_name| format!("CAST(\"{}\" AS TEXT) AS \"{}\"", column_name, column_name))
+            .collect();
+        // primary keys break ties so rows with equal sort values keep a stable order,
+        // without one every column does, rows equal in all of them can't be told apart anyway
+        let tie_breaker_column_names = if primary_key_column_names.is_empty() {
+            column_names
+        } else {
+            primary_key_column_names
+        };
+        let order_by_columns: Vec<String> = order_by
+            .iter()
+            .map(|(column_name, direction)| format!("\"{}\" {}", column_name, direction))
+            .chain(
+                tie_breaker_column_names
+                    .iter()
+                    .map(|column_name| format!("\"{}\"", column_name)),
+            )
+            .collect();
+        // pages are only stable when the rows have an order
+        let order_by = if order_by_columns.is_empty() {
+            String::new()
+        } else {
+            format!(" ORDER BY {}", order_by_columns.join(", "))
+        };
+        let query = format!(
+            "SELECT {} FROM {}{}{} LIMIT {} OFFSET {}",
+            select_column_names.join(", "),
+            self.table_reference(table_name),
+            self.get_filter_clause(filters),
+            order_by,
+            page.size,
+            page.offset()
+        );
+        let parameters: Vec<&str> = filters.iter().map(|filter| filter.value.as_str()).collect();
+        let table_data_rows = self
+            .retry_transient_errors(&query, || {
+                self.pool.fetch_optional_text_rows(&query, &parameters)
+            })
+            .await;
+        self.log_query_error(&query, table_data_rows).await
+    }
+
+    // values shared by more than one row and how many rows share them, NULLs are grouped
+    // together like GROUP BY does
+    pub async fn find_duplicates(
+        &self,
+        table_name: &str,
+        column_names: &[String],
+    ) -> Result<Vec<(Vec<Option<String>>, i64)>, sqlx::Error> {
         let select_column_names: Vec<String> = column_names
+            .iter()
+            .map(|column_name| format!("CAST(\"{}\" AS TEXT) AS \"{}\"", column_name, column_name))
+            .collect();
+        let group_column_names: Vec<String> = column_names
+            .iter()
+            .map(|column_name| format!("\"{}\"", column_name))
+            .collect();
+        let query = format!(
+            "SELECT {}, CAST(COUNT(*) AS TEXT) FROM {} GROUP BY {} HAVING COUNT(*) > 1 ORDER BY COUNT(*) DESC, {} LIMIT {}",
+            select_column_names.join(", "),
+            self.table_reference(table_name),
+            group_column_names.join(", "),
+            group_column_names.join(", "),
+            MAX_DUPLICATE_GROUPS
+        );
+        let rows = self.pool.fetch_optional_text_rows(&query, &[]).await;
+        Ok(self
+            .log_query_error(&query, rows)
+            .await?
             .into_iter()
+            .map(|mut row| {
+                let count = row
+                    .pop()
+                    .flatten()
+                    .and_then(|count| count.parse::<i64>().ok())
+                    .unwrap_or_default();
+                (row, count)
+            })
+            .collect())
+    }
+
+    pub async fn get_referenced_row(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        value: &str,
+        column_names: &Vec<String>,
+    ) -> Result<Option<Vec<String>>, sqlx::Error> {
+        let select_column_names: Vec<String> = column_names
+            .iter()
             .map(|column_name| {
                 format!(
-                    "COALESCE(\"{}\"::TEXT, '') AS \"{}\"",
+                    "COALESCE(CAST(\"{}\" AS TEXT), '') AS \"{}\"",
                     column_name, column_name
                 )
             })
             .collect();
-        let order_by_columns: Vec<String> = order_by_column_names
-            .iter()
-            .map(|column_name| format!("\"{}\"", column_name))
-            .collect();
         let query = format!(
-            "SELECT {} FROM \"{}\" ORDER BY {}",
+            "SELECT {} FROM {} WHERE CAST(\"{}\" AS TEXT) = {} LIMIT 1",
             select_column_names.join(", "),
-            table_name,
-            order_by_columns.join(", ")
+            self.table_reference(table_name),
+            column_name,
+            self.pool.parameter(1)
         );
-        let table_data_rows = sqlx::query(&query).fetch_all(&self.pool).await;
-        table_data_rows
+        Ok(self
+            .pool
+            .fetch_text_rows(&query, &[value])
+            .await?
+            .into_iter()
+            .next())
     }
 
     pub async fn alter_table(
@@ -389,9 +2553,7 @@ impl Repository {
         table_name: &str,
         table_change_events: &Vec<TableChangeEvents>,
         initial_primary_key_column_names: &Vec<String>,
-    ) -> Result<(), sqlx::Error> {
-        // Begin a transaction
-        let mut transaction: Transaction<'_, Postgres> = self.pool.begin().await?;
+    ) -> Result<Vec<String>, sqlx::Error> {
         let mut current_table_name = table_name.to_string();
 
         let mut primary_key_columns = initial_primary_key_column_names.clone();
@@ -402,27 +2564,33 @@ impl Repository {
             match event {
                 TableChangeEvents::ChangeTableName(new_name) => {
                     queries.push(format!(
-                        "ALTER TABLE \"{}\" RENAME TO \"{}\"",
-                        current_table_name, new_name
+                        "ALTER TABLE {} RENAME TO \"{}\"",
+                        self.table_reference(&current_table_name),
+                        new_name
                     ));
                     current_table_name = new_name.clone();
                 }
                 TableChangeEvents::ChangeColumnDataType(column_name, new_data_type) => {
-                    queries.push(format!(
-                        "ALTER TABLE \"{}\" ALTER COLUMN \"{}\" TYPE {} USING \"{}\"::{}",
-                        current_table_name, column_name, new_data_type, column_name, new_data_type
+                    queries.extend(self.get_change_column_data_type_queries(
+                        &current_table_name,
+                        column_name,
+                        new_data_type,
                     ));
                 }
                 TableChangeEvents::ChangeColumnName(old_name, new_name) => {
                     queries.push(format!(
-                        "ALTER TABLE \"{}\" RENAME COLUMN \"{}\" TO \"{}\"",
-                        current_table_name, old_name, new_name
+                        "ALTER TABLE {} RENAME COLUMN \"{}\" TO \"{}\"",
+                        self.table_reference(&current_table_name),
+                        old_name,
+                        new_name
                     ));
                 }
                 TableChangeEvents::AddColumn(column_name, data_type) => {
                     queries.push(format!(
-                        "ALTER TABLE \"{}\" ADD COLUMN \"{}\" {}",
-                        current_table_name, column_name, data_type
+                        "ALTER TABLE {} ADD COLUMN \"{}\" {}",
+                        self.table_reference(&current_table_name),
+                        column_name,
+                        self.data_type_definition(data_type)
                     ));
                 }
                 TableChangeEvents::RemoveColumn(column_name) => {
@@ -435,22 +2603,30 @@ impl Repository {
                         primary_key_columns.remove(existing_index);
                     }
                     queries.push(format!(
-                        "ALTER TABLE \"{}\" DROP COLUMN \"{}\"",
-                        current_table_name, column_name
+                        "ALTER TABLE {} DROP COLUMN \"{}\"",
+                        self.table_reference(&current_table_name),
+                        column_name
                     ));
                 }
                 TableChangeEvents::AddForeignKey(column_foreign_key) => {
-                    queries.push(format!(
-                    "ALTER TABLE \"{}\" ADD CONSTRAINT fk_{}_{} FOREIGN KEY (\"{}\") REFERENCES \"{}\" (\"{}\")",
-                    current_table_name, current_table_name, column_foreign_key.column_name,
-                    column_foreign_key.column_name, column_foreign_key.referenced_table,
-                    column_foreign_key.referenced_column
-                ));
+                    let mut query = format!(
+                        "ALTER TABLE {} ADD CONSTRAINT fk_{}_{} FOREIGN KEY (\"{}\") REFERENCES {} (\"{}\")",
+                        self.table_reference(&current_table_name), current_table_name,
+                        column_foreign_key.column_name, column_foreign_key.column_name,
+                        self.table_reference(&column_foreign_key.referenced_table),
+                        column_foreign_key.referenced_column
+                    );
+                    if column_foreign_key.on_delete != ReferentialAction::NoAction {
+                        query.push_str(&format!(" ON DELETE {}", column_foreign_key.on_delete));
+                    }
+                    queries.push(query);
                 }
                 TableChangeEvents::RemoveForeignKey(column_name) => {
                     queries.push(format!(
-                        "ALTER TABLE \"{}\" DROP CONSTRAINT IF EXISTS fk_{}_{}",
-                        current_table_name, current_table_name, column_name,
+                        "ALTER TABLE {} DROP CONSTRAINT IF EXISTS fk_{}_{}",
+                        self.table_reference(&current_table_name),
+                        current_table_name,
+                        column_name,
                     ));
                 }
                 TableChangeEvents::AddPrimaryKey(column_name) => {
@@ -471,19 +2647,20 @@ impl Repository {
         if *initial_primary_key_column_names != primary_key_columns {
             if run_drop_primary_constraint_query {
                 if let Some(primary_key_constraint) =
-                    self.get_primary_key_constraint(&table_name).await.unwrap()
+                    self.get_primary_key_constraint(&table_name).await?
                 {
                     let drop_query = format!(
-                        "ALTER TABLE \"{}\" DROP CONSTRAINT \"{}\"",
-                        current_table_name, primary_key_constraint.conname
+                        "ALTER TABLE {} DROP CONSTRAINT \"{}\"",
+                        self.table_reference(&current_table_name),
+                        primary_key_constraint.conname
                     );
                     queries.push(drop_query);
                 }
             }
             if !primary_key_columns.is_empty() {
                 let add_query = format!(
-                    "ALTER TABLE \"{}\" ADD CONSTRAINT pk_{} PRIMARY KEY ({})",
-                    current_table_name,
+                    "ALTER TABLE {} ADD CONSTRAINT pk_{} PRIMARY KEY ({})",
+                    self.table_reference(&current_table_name),
                     current_table_name,
                     primary_key_columns.join(", ")
                 );
@@ -492,15 +2669,6 @@ impl Repository {
         }
 
         // Execute each query in the transaction
-        for query in queries {
-            println!("{}", query);
-            sqlx::query(&query).execute(&mut *transaction).await?;
-            self.log_query(query).await;
-        }
-
-        // Commit the transaction
-        transaction.commit().await?;
-
-        Ok(())
+        self.execute_ddl_in_transaction(queries).await
     }
 }
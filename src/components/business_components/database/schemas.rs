@@ -1,4 +1,6 @@
-use crate::components::business_components::database::models::{ColumnsInfo, TableGeneralInfo};
+use crate::components::business_components::database::models::{
+    ColumnsInfo, CompositeForeignKeyInfo, TableGeneralInfo,
+};
 use std::collections::HashMap;
 use std::fmt;
 use std::iter::zip;
@@ -6,10 +8,16 @@ use std::iter::zip;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DataType {
     TEXT,
+    // the maximum number of characters
+    VARCHAR(u32),
+    SMALLINT,
     INTEGER,
+    BIGINT,
+    DOUBLE,
     TIMESTAMP,
     SERIAL,
     BOOLEAN,
+    UUID,
 }
 
 impl Default for DataType {
@@ -22,40 +30,154 @@ impl fmt::Display for DataType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             DataType::TEXT => write!(f, "TEXT"),
+            DataType::VARCHAR(length) => write!(f, "VARCHAR({})", length),
+            DataType::SMALLINT => write!(f, "SMALLINT"),
             DataType::INTEGER => write!(f, "INTEGER"),
+            DataType::BIGINT => write!(f, "BIGINT"),
+            DataType::DOUBLE => write!(f, "DOUBLE PRECISION"),
             DataType::TIMESTAMP => write!(f, "TIMESTAMP"),
             DataType::SERIAL => write!(f, "SERIAL"),
             DataType::BOOLEAN => write!(f, "BOOLEAN"),
+            DataType::UUID => write!(f, "UUID"),
         }
     }
 }
 
 impl DataType {
+    pub const DEFAULT_VARCHAR_LENGTH: u32 = 255;
+
+    // the length of a varchar is introspected as part of its type, e.g. character varying(20)
     pub fn to_datatype(value: String) -> Self {
         match value.as_str() {
             "text" => Self::TEXT,
+            "smallint" => Self::SMALLINT,
             "integer" => Self::INTEGER,
+            "bigint" => Self::BIGINT,
+            "double precision" => Self::DOUBLE,
             "timestamp without time zone" => Self::TIMESTAMP,
             "serial" => Self::SERIAL,
             "boolean" => Self::BOOLEAN,
-            _ => panic!("Invalid datatype"),
+            "uuid" => Self::UUID,
+            value => value
+                .strip_prefix("character varying(")
+                .and_then(|length| length.strip_suffix(')'))
+                .and_then(|length| length.parse().ok())
+                .map(Self::VARCHAR)
+                .expect("Invalid datatype"),
+        }
+    }
+
+    // SERIAL is left out as it is only a shorthand for a sequence backed INTEGER
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DataType::SMALLINT | DataType::INTEGER | DataType::BIGINT
+        )
+    }
+
+    // numbers and booleans are written as they are, the values of every other type are
+    // quoted literals the database converts to the column type, e.g. '2024-01-31 10:00:00'
+    pub fn is_quoted(&self) -> bool {
+        !matches!(
+            self,
+            DataType::SMALLINT
+                | DataType::INTEGER
+                | DataType::BIGINT
+                | DataType::DOUBLE
+                | DataType::SERIAL
+                | DataType::BOOLEAN
+        )
+    }
+
+    // only TEXT and VARCHAR can hold an empty string, any other empty value is NULL
+    pub fn is_null(&self, value: &Option<String>) -> bool {
+        value.as_ref().is_none_or(|value| {
+            value.is_empty() && !matches!(self, DataType::TEXT | DataType::VARCHAR(_))
+        })
+    }
+
+    pub fn to_sql_literal(&self, value: &Option<String>) -> String {
+        match value {
+            _ if self.is_null(value) => String::from("NULL"),
+            Some(value) if self.is_quoted() => format!("'{}'", value.replace('\'', "''")),
+            Some(value) => value.clone(),
+            None => String::from("NULL"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ReferentialAction {
+    #[default]
+    NoAction,
+    Cascade,
+    SetNull,
+    Restrict,
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReferentialAction::NoAction => write!(f, "NO ACTION"),
+            ReferentialAction::Cascade => write!(f, "CASCADE"),
+            ReferentialAction::SetNull => write!(f, "SET NULL"),
+            ReferentialAction::Restrict => write!(f, "RESTRICT"),
+        }
+    }
+}
+
+impl ReferentialAction {
+    pub const ALL: [ReferentialAction; 4] = [
+        ReferentialAction::NoAction,
+        ReferentialAction::Cascade,
+        ReferentialAction::SetNull,
+        ReferentialAction::Restrict,
+    ];
+
+    // SET DEFAULT isn't supported so it's shown as the default action
+    pub fn to_referential_action(value: &str) -> Self {
+        match value {
+            "CASCADE" => Self::Cascade,
+            "SET NULL" => Self::SetNull,
+            "RESTRICT" => Self::Restrict,
+            _ => Self::NoAction,
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Constraint {
-    ForeignKey(String, String),
+    ForeignKey {
+        table: String,
+        column: String,
+        on_delete: ReferentialAction,
+    },
     PrimaryKey,
+    NotNull,
+    Unique,
+    // boolean sql expression every row has to satisfy
+    Check(String),
 }
 
 impl fmt::Display for Constraint {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Constraint::ForeignKey(referenced_table, referenced_column) => {
-                write!(f, "REFERENCES {}({})", referenced_table, referenced_column)
+            Constraint::ForeignKey {
+                table,
+                column,
+                on_delete,
+            } => {
+                write!(f, "REFERENCES {}({})", table, column)?;
+                // NO ACTION is what postgres does without an ON DELETE clause
+                if *on_delete != ReferentialAction::NoAction {
+                    write!(f, " ON DELETE {}", on_delete)?;
+                }
+                Ok(())
             }
             Constraint::PrimaryKey => write!(f, "PRIMARY KEY"),
+            Constraint::NotNull => write!(f, "NOT NULL"),
+            Constraint::Unique => write!(f, "UNIQUE"),
+            Constraint::Check(expression) => write!(f, "CHECK ({})", expression),
         }
     }
 }
@@ -65,10 +187,16 @@ impl Constraint {
         constraint_type: String,
         referenced_table: String,
         referenced_column: String,
+        delete_rule: String,
     ) -> Self {
         match constraint_type.as_str() {
             "PRIMARY KEY" => Self::PrimaryKey,
-            "FOREIGN KEY" => Self::ForeignKey(referenced_table, referenced_column),
+            "FOREIGN KEY" => Self::ForeignKey {
+                table: referenced_table,
+                column: referenced_column,
+                on_delete: ReferentialAction::to_referential_action(&delete_rule),
+            },
+            "UNIQUE" => Self::Unique,
             _ => panic!("Invalid Constraint"),
         }
     }
@@ -79,6 +207,10 @@ pub struct Column {
     pub name: String,
     pub datatype: DataType,
     pub constraints: Vec<Constraint>,
+    pub default_value: Option<String>,
+    pub comment: Option<String>,
+    // expression of a GENERATED ALWAYS AS (...) STORED column
+    pub generated: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -105,27 +237,75 @@ impl TableGeneral {
 }
 
 impl Column {
+    // values of serial and computed columns are assigned by the database
+    pub fn is_generated(&self) -> bool {
+        self.generated.is_some() || self.is_serial()
+    }
+
+    // serial columns are introspected as an integer type with a sequence default
+    pub fn is_serial(&self) -> bool {
+        self.datatype == DataType::SERIAL
+            || (self.datatype.is_integer()
+                && self
+                    .default_value
+                    .as_ref()
+                    .is_some_and(|default_value| default_value.starts_with("nextval(")))
+    }
+
+    // literal defaults are introspected as 'value'::type, they are kept as the value itself
+    // the way the create table form takes them, other expressions are kept as they are
+    fn unquote_default(column_default: String) -> String {
+        let Some(quoted) = column_default.strip_prefix('\'') else {
+            return column_default;
+        };
+        let mut value = String::new();
+        let mut characters = quoted.char_indices().peekable();
+        while let Some((index, character)) = characters.next() {
+            if character != '\'' {
+                value.push(character);
+            } else if characters.next_if(|(_, next)| *next == '\'').is_some() {
+                value.push('\'');
+            } else {
+                let rest = &quoted[index + 1..];
+                if rest.is_empty() || rest.starts_with("::") {
+                    return value;
+                }
+                break;
+            }
+        }
+        column_default
+    }
+
     pub fn to_column(column_info: ColumnsInfo) -> Self {
         // initial query couldve returned null constraint types so they
         // need to be filtered
-        Self {
-            name: column_info.column_name,
-            datatype: DataType::to_datatype(column_info.data_type),
-            constraints: zip(
-                zip(column_info.constraint_types, column_info.referenced_tables),
-                column_info.referenced_columns,
-            )
-            .filter(|((constraint_type, referenced_table), referenced_column)| {
-                !constraint_type.is_none()
-            })
-            .map(|((constraint_type, referenced_table), referenced_column)| {
+        let mut constraints: Vec<Constraint> = zip(
+            zip(column_info.constraint_types, column_info.referenced_tables),
+            zip(column_info.referenced_columns, column_info.delete_rules),
+        )
+        .filter(|((constraint_type, _), _)| !constraint_type.is_none())
+        .map(
+            |((constraint_type, referenced_table), (referenced_column, delete_rule))| {
                 Constraint::to_constraint(
                     constraint_type.unwrap(),
                     referenced_table.unwrap_or_default(),
                     referenced_column.unwrap_or_default(),
+                    delete_rule.unwrap_or_default(),
                 )
-            })
-            .collect(),
+            },
+        )
+        .collect();
+        // primary keys are implicitly not null
+        if column_info.is_nullable == "NO" && !constraints.contains(&Constraint::PrimaryKey) {
+            constraints.push(Constraint::NotNull);
+        }
+        Self {
+            name: column_info.column_name,
+            datatype: DataType::to_datatype(column_info.data_type),
+            constraints,
+            default_value: column_info.column_default.map(Self::unquote_default),
+            comment: column_info.column_comment,
+            generated: column_info.generation_expression,
         }
     }
 }
@@ -134,6 +314,96 @@ impl Column {
 pub struct TableIn {
     pub table_name: String,
     pub columns: Vec<Column>,
+    pub composite_foreign_keys: Vec<CompositeForeignKey>,
+}
+
+// table level foreign key spanning multiple columns
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CompositeForeignKey {
+    pub column_names: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_column_names: Vec<String>,
+}
+
+impl CompositeForeignKey {
+    pub fn to_composite_foreign_key(composite_foreign_key_info: CompositeForeignKeyInfo) -> Self {
+        Self {
+            column_names: composite_foreign_key_info.column_names,
+            referenced_table: composite_foreign_key_info.referenced_table,
+            referenced_column_names: composite_foreign_key_info.referenced_column_names,
+        }
+    }
+}
+
+// secondary index of a table, the definition is the statement that created it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Index {
+    pub name: String,
+    pub definition: String,
+    // primary key and unique constraints own their index, it goes away with the constraint
+    pub is_constraint: bool,
+}
+
+// structural overview of a table, single column foreign keys are listed with one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableMetadata {
+    pub column_count: usize,
+    pub primary_key_column_names: Vec<String>,
+    pub foreign_keys: Vec<CompositeForeignKey>,
+    // including indexes and toast data, sqlite doesn't report it
+    pub total_size_bytes: Option<i64>,
+}
+
+// how many of each kind of change event are waiting to be committed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSummary {
+    pub inserts: usize,
+    pub updates: usize,
+    pub deletes: usize,
+}
+
+impl ChangeSummary {
+    pub fn is_empty(&self) -> bool {
+        self.inserts == 0 && self.updates == 0 && self.deletes == 0
+    }
+}
+
+impl fmt::Display for ChangeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} insert{}, {} update{}, {} delete{}",
+            self.inserts,
+            plural(self.inserts),
+            self.updates,
+            plural(self.updates),
+            self.deletes,
+            plural(self.deletes)
+        )
+    }
+}
+
+// a queued change event next to the committed row it changes
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowDiff {
+    // (column, value) of the row to insert
+    Inserted(Vec<(String, Option<String>)>),
+    Modified {
+        // (column, value) of the primary key identifying the row
+        primary_key: Vec<(String, String)>,
+        // (column, committed value, new value) in column order
+        changed_cells: Vec<(String, Option<String>, Option<String>)>,
+    },
+    // (column, committed value) of the row to delete
+    Deleted(Vec<(String, Option<String>)>),
+}
+
+// how complete a column's data is, NULLs are not counted as a distinct value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub null_count: i64,
+    pub distinct_count: i64,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -141,6 +411,57 @@ pub struct ColumnForeignKey {
     pub column_name: String,
     pub referenced_column: String,
     pub referenced_table: String,
+    pub on_delete: ReferentialAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SortDirection::Ascending => write!(f, "ASC"),
+            SortDirection::Descending => write!(f, "DESC"),
+        }
+    }
+}
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+// page numbers start at 0
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub size: usize,
+    pub number: usize,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_PAGE_SIZE,
+            number: 0,
+        }
+    }
+}
+
+impl Page {
+    pub fn offset(&self) -> usize {
+        self.size * self.number
+    }
+
+    pub fn page_count(&self, total_row_count: i64) -> usize {
+        (total_row_count.max(0) as usize).div_ceil(self.size).max(1)
+    }
+}
+
+// what one statement of a script did, result sets are kept for the statements that return rows
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementResult {
+    RowsAffected(u64),
+    Rows(TableInsertedData),
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -148,7 +469,12 @@ pub struct TableInsertedData {
     pub table_name: String,
     pub column_names: Vec<String>,
     pub data_types: Vec<DataType>,
-    pub rows: Vec<Vec<String>>,
+    // one per column, true for the serial, identity and computed columns the database fills in
+    pub read_only_columns: Vec<bool>,
+    // None is SQL NULL, which is distinct from an empty string
+    pub rows: Vec<Vec<Option<String>>>,
+    pub page: Page,
+    pub total_row_count: i64,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -164,24 +490,57 @@ pub enum TableChangeEvents {
     RemovePrimaryKey(String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Operator {
+    #[default]
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    Like,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operator::Equal => write!(f, "="),
+            Operator::NotEqual => write!(f, "<>"),
+            Operator::GreaterThan => write!(f, ">"),
+            Operator::LessThan => write!(f, "<"),
+            Operator::Like => write!(f, "LIKE"),
+        }
+    }
+}
+
+impl Operator {
+    pub const ALL: [Operator; 5] = [
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::GreaterThan,
+        Operator::LessThan,
+        Operator::Like,
+    ];
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Condition {
     pub column_name: String,
     pub data_type: DataType,
+    pub operator: Operator,
     pub value: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct RowColumnValue {
     pub conditions: Vec<Condition>,
-    pub column_values: HashMap<String, (DataType, String)>,
+    pub column_values: HashMap<String, (DataType, Option<String>)>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct RowInsertData {
     pub column_names: Vec<String>,
     pub data_types: Vec<DataType>,
-    pub values: Vec<String>,
+    pub values: Vec<Option<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1,14 +1,19 @@
 use crate::components::business_components::component::{
-    repository_module::BRepository, BColumn, BDataType, BTableIn, BTableInfo, BusinessComponent,
+    repository_module::BRepository, BColumn, BDataType, BLogLevel, BTableIn, BTableInfo,
+    BusinessComponent,
 };
 use crate::components::business_components::components::BusinessConsole;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::Mutex as AsyncMutex;
+use tokio::task;
 
 #[derive(Debug, Clone)]
 pub struct Home {
     repository: Arc<BRepository>,
     pub title: Arc<AsyncMutex<Option<String>>>,
+    // shown in the status bar
+    pub database_name: Arc<AsyncMutex<Option<String>>>,
     console: Arc<BusinessConsole>,
 }
 
@@ -16,8 +21,21 @@ impl BusinessComponent for Home {
     async fn initialize_component(&self) {
         let mut locked_title = self.title.lock().await;
         *locked_title = Some(String::from("Home Component"));
+        match self.repository.get_database_name().await {
+            Ok(database_name) => *self.database_name.lock().await = Some(database_name),
+            Err(error) => {
+                let console = self.console.clone();
+                task::spawn_blocking(move || {
+                    console.write(
+                        BLogLevel::Error,
+                        format!("Could not get the database name: {}", error),
+                    );
+                })
+                .await;
+            }
+        }
         self.console
-            .write(String::from("Home Component Initialized"));
+            .write_info(String::from("Home Component Initialized"));
     }
 }
 
@@ -26,7 +44,45 @@ impl Home {
         Self {
             repository,
             title: Arc::new(AsyncMutex::new(None)),
+            database_name: Arc::new(AsyncMutex::new(None)),
             console,
         }
     }
+
+    pub fn keep_alive_interval(&self) -> Option<Duration> {
+        self.repository.keep_alive_interval()
+    }
+
+    pub async fn keep_alive(&self) -> bool {
+        let ping_result = self.repository.ping().await;
+        let is_alive = ping_result.is_ok();
+        if let Err(error) = ping_result {
+            let console = self.console.clone();
+            task::spawn_blocking(move || {
+                console.write(
+                    BLogLevel::Error,
+                    format!("Keep-alive ping failed: {}", error),
+                );
+            })
+            .await;
+        }
+        is_alive
+    }
+
+    pub async fn reconnect(&self) -> bool {
+        let reconnect_result = self.repository.reconnect().await;
+        let (level, message) = match &reconnect_result {
+            Ok(()) => (BLogLevel::Info, String::from("Reconnected to the database")),
+            Err(error) => (
+                BLogLevel::Error,
+                format!("Could not reconnect to the database: {}", error),
+            ),
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+        reconnect_result.is_ok()
+    }
 }
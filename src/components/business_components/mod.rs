@@ -1,6 +1,11 @@
+mod appearance;
 pub mod component;
 pub mod components;
+mod config_file;
+mod connection;
 mod console;
 mod database;
 mod home;
+mod query_runner;
+mod session;
 mod tables;
@@ -0,0 +1,258 @@
+use crate::components::business_components::component::{
+    repository_module::BRepository, BLogLevel, BStatementResult, BTableInsertedData,
+};
+use crate::components::business_components::components::BusinessConsole;
+use crate::components::business_components::tables::table_data::csv::to_csv;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::task;
+
+#[derive(Debug, Clone)]
+pub struct QueryRunner {
+    repository: Arc<BRepository>,
+    console: Arc<BusinessConsole>,
+}
+
+impl QueryRunner {
+    pub fn new(repository: Arc<BRepository>, console: Arc<BusinessConsole>) -> Self {
+        Self {
+            repository,
+            console,
+        }
+    }
+
+    // failed queries are written to the database console by the repository,
+    // with writes allowed the query is a script run in one transaction showing its last result set
+    pub async fn run_query(&self, query: String, allow_writes: bool) -> Option<BTableInsertedData> {
+        if !allow_writes {
+            return self.repository.run_query(&query, false).await.ok();
+        }
+        let statement_results = self.repository.run_script(&query).await.ok()?;
+        let summary: Vec<String> = statement_results
+            .iter()
+            .enumerate()
+            .map(|(index, statement_result)| match statement_result {
+                BStatementResult::RowsAffected(rows_affected) => {
+                    format!("Statement {}: {} rows affected", index + 1, rows_affected)
+                }
+                BStatementResult::Rows(rows) => {
+                    format!("Statement {}: {} rows returned", index + 1, rows.rows.len())
+                }
+            })
+            .collect();
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write_info(summary.join("\n"));
+        })
+        .await;
+        // a script that returns no rows shows an empty result
+        Some(
+            statement_results
+                .into_iter()
+                .rev()
+                .find_map(|statement_result| match statement_result {
+                    BStatementResult::Rows(rows) => Some(rows),
+                    BStatementResult::RowsAffected(_) => None,
+                })
+                .unwrap_or_default(),
+        )
+    }
+
+    pub async fn export_csv(&self, query_result: &BTableInsertedData, path: PathBuf) {
+        let (level, message) = match tokio::fs::write(&path, to_csv(query_result)).await {
+            Ok(()) => (
+                BLogLevel::Info,
+                format!(
+                    "Exported {} rows to {}",
+                    query_result.rows.len(),
+                    path.display()
+                ),
+            ),
+            Err(error) => (
+                BLogLevel::Error,
+                format!(
+                    "Could not export query results to {}: {}",
+                    path.display(),
+                    error
+                ),
+            ),
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::business_components::tables::test_utils::{
+        create_repository_table_and_console, default_table_in,
+    };
+    use sqlx::PgPool;
+
+    async fn create_query_runner(pool: PgPool) -> QueryRunner {
+        let (repository, console) =
+            create_repository_table_and_console(pool, &default_table_in()).await;
+        repository
+            .run_query(
+                "INSERT INTO users (id, name) VALUES (1, 'Alice'), (2, NULL)",
+                true,
+            )
+            .await
+            .unwrap();
+        QueryRunner::new(repository, console)
+    }
+
+    #[sqlx::test]
+    async fn test_run_select_query(pool: PgPool) {
+        let query_runner = create_query_runner(pool).await;
+
+        let result = query_runner
+            .run_query(
+                String::from("SELECT id, name, id * 2 AS doubled FROM users ORDER BY id;"),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.column_names, vec!["id", "name", "doubled"]);
+        assert_eq!(
+            result.rows,
+            vec![
+                vec![
+                    Some("1".to_string()),
+                    Some("Alice".to_string()),
+                    Some("2".to_string())
+                ],
+                vec![Some("2".to_string()), None, Some("4".to_string())],
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_export_csv(pool: PgPool) {
+        let query_runner = create_query_runner(pool).await;
+        let path = std::env::temp_dir().join(format!("crm_query_{}.csv", std::process::id()));
+
+        let result = query_runner
+            .run_query(
+                String::from("SELECT id, name FROM users ORDER BY id"),
+                false,
+            )
+            .await
+            .unwrap();
+        query_runner.export_csv(&result, path.clone()).await;
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "id,name\n1,Alice\n2,\n"
+        );
+
+        // an empty result still names its columns
+        let result = query_runner
+            .run_query(
+                String::from("SELECT id, name FROM users WHERE id > 2"),
+                false,
+            )
+            .await
+            .unwrap();
+        query_runner.export_csv(&result, path.clone()).await;
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "id,name\n");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_run_script(pool: PgPool) {
+        let query_runner = create_query_runner(pool).await;
+
+        let statement_results = query_runner
+            .repository
+            .run_script(
+                "INSERT INTO users (id, name) VALUES (3, 'a;b'); -- the name holds a ;
+                 UPDATE users SET name = name || '\"c;\"' WHERE id >= 2;
+                 /* ; */ SELECT name FROM users WHERE id = 3;;",
+            )
+            .await
+            .unwrap();
+        assert_eq!(statement_results.len(), 3);
+        assert_eq!(statement_results[0], BStatementResult::RowsAffected(1));
+        assert_eq!(statement_results[1], BStatementResult::RowsAffected(2));
+        let BStatementResult::Rows(rows) = &statement_results[2] else {
+            panic!("the SELECT should return rows");
+        };
+        assert_eq!(rows.column_names, vec!["name"]);
+        assert_eq!(rows.rows, vec![vec![Some("a;b\"c;\"".to_string())]]);
+
+        let result = query_runner
+            .run_query(
+                String::from("DELETE FROM users WHERE id = 3; SELECT COUNT(*) FROM users"),
+                true,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.rows, vec![vec![Some("2".to_string())]]);
+    }
+
+    #[sqlx::test]
+    async fn test_run_script_rolls_back_on_error(pool: PgPool) {
+        let query_runner = create_query_runner(pool).await;
+
+        assert!(query_runner
+            .run_query(
+                String::from(
+                    "INSERT INTO users (id, name) VALUES (3, 'Carol'); \
+                     INSERT INTO users (id, name) VALUES (1, 'Duplicate')"
+                ),
+                true,
+            )
+            .await
+            .is_none());
+
+        let result = query_runner
+            .run_query(String::from("SELECT COUNT(*) AS count FROM users"), false)
+            .await
+            .unwrap();
+        assert_eq!(result.rows, vec![vec![Some("2".to_string())]]);
+    }
+
+    #[sqlx::test]
+    async fn test_run_write_query_without_permission(pool: PgPool) {
+        let query_runner = create_query_runner(pool).await;
+
+        assert!(query_runner
+            .run_query(String::from("DELETE FROM users"), false)
+            .await
+            .is_none());
+        assert!(query_runner
+            .run_query(String::from("SELECT 1; DELETE FROM users"), false)
+            .await
+            .is_none());
+
+        assert!(query_runner
+            .run_query(
+                String::from(
+                    "WITH deleted AS (DELETE FROM users RETURNING *) SELECT * FROM deleted"
+                ),
+                false
+            )
+            .await
+            .is_none());
+        assert!(query_runner
+            .run_query(String::from("SELECT * INTO users_copy FROM users"), false)
+            .await
+            .is_none());
+
+        let result = query_runner
+            .run_query(String::from("SELECT COUNT(*) AS count FROM users"), false)
+            .await
+            .unwrap();
+        assert_eq!(result.rows, vec![vec![Some("2".to_string())]]);
+
+        let result = query_runner
+            .run_query(String::from("SELECT ';' AS separator"), false)
+            .await
+            .unwrap();
+        assert_eq!(result.rows, vec![vec![Some(";".to_string())]]);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::components::business_components::config_file::ConfigFile;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const SESSION_CONFIG_PATH: &str = "crm_session.json";
+const MAX_RECENT_TABLES: usize = 8;
+
+// what was shown when the app was last closed
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    // (width, height) in logical pixels
+    pub window_size: Option<(f32, f32)>,
+    pub open_table: Option<String>,
+    // most recently opened first
+    pub recent_tables: Vec<String>,
+}
+
+impl SessionState {
+    // reopening a table moves it to the front instead of listing it twice
+    pub fn push_recent_table(recent_tables: &mut Vec<String>, table_name: String) {
+        recent_tables.retain(|recent_table| *recent_table != table_name);
+        recent_tables.insert(0, table_name);
+        recent_tables.truncate(MAX_RECENT_TABLES);
+    }
+
+    fn to_config_file_content(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    // a file that is not valid json keeps the defaults, a hand edited one is cleaned up:
+    // the window size is only restored when both sides are positive
+    fn from_config_file_content(content: &str) -> Self {
+        let session_state = serde_json::from_str::<Self>(content).unwrap_or_default();
+        let window_size = session_state
+            .window_size
+            .filter(|(width, height)| *width > 0.0 && *height > 0.0);
+        let open_table = session_state
+            .open_table
+            .filter(|open_table| !open_table.is_empty());
+        // deduplicated and capped like a list built while browsing
+        let mut recent_tables = vec![];
+        for recent_table in session_state.recent_tables.into_iter().rev() {
+            if !recent_table.is_empty() {
+                Self::push_recent_table(&mut recent_tables, recent_table);
+            }
+        }
+        Self {
+            window_size,
+            open_table,
+            recent_tables,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    config_file: ConfigFile,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new(PathBuf::from(SESSION_CONFIG_PATH))
+    }
+}
+
+impl Session {
+    pub fn new(config_path: PathBuf) -> Self {
+        Self {
+            config_file: ConfigFile::new(config_path),
+        }
+    }
+
+    pub fn last_session_state(&self) -> SessionState {
+        SessionState::from_config_file_content(&self.config_file.read())
+    }
+
+    pub fn save(&self, session_state: &SessionState) -> Result<(), String> {
+        self.config_file
+            .write(&session_state.to_config_file_content())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_round_trip() {
+        let session_state = SessionState {
+            window_size: Some((1280.0, 720.5)),
+            open_table: Some(String::from("users")),
+            recent_tables: vec![String::from("users"), String::from("orders")],
+        };
+        assert_eq!(
+            SessionState::from_config_file_content(&session_state.to_config_file_content()),
+            session_state
+        );
+        assert_eq!(
+            SessionState::from_config_file_content(
+                &SessionState::default().to_config_file_content()
+            ),
+            SessionState::default()
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_file_is_ignored() {
+        assert_eq!(
+            SessionState::from_config_file_content("window_width=800\nwindow_height=600\n"),
+            SessionState::default()
+        );
+        assert_eq!(
+            SessionState::from_config_file_content(
+                r#"{"window_size": [800.0, -1.0], "open_table": "", "recent_tables": ["users", "users", ""]}"#
+            ),
+            SessionState {
+                recent_tables: vec![String::from("users")],
+                ..SessionState::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_push_recent_table() {
+        let mut recent_tables = vec![];
+        for table_number in 0..10 {
+            SessionState::push_recent_table(&mut recent_tables, format!("table_{}", table_number));
+        }
+        SessionState::push_recent_table(&mut recent_tables, String::from("table_5"));
+        assert_eq!(recent_tables.len(), MAX_RECENT_TABLES);
+        assert_eq!(recent_tables[0], "table_5");
+        assert_eq!(recent_tables[1], "table_9");
+        assert_eq!(
+            recent_tables
+                .iter()
+                .filter(|recent_table| *recent_table == "table_5")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_missing_config_file() {
+        let session = Session::new(PathBuf::from("/nonexistent/crm_session.json"));
+        assert_eq!(session.last_session_state(), SessionState::default());
+    }
+}
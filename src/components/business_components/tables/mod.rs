@@ -1,5 +1,6 @@
 pub mod table_data;
 pub mod table_info;
 pub mod tables;
-mod test_utils;
+pub(super) mod test_utils;
 mod utils;
+mod validation;
@@ -0,0 +1,154 @@
+use crate::components::business_components::component::BTableInsertedData;
+
+// minimal RFC 4180 reader: quoted fields may contain commas, newlines and "" escapes
+pub fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut characters = content.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        if in_quotes {
+            match character {
+                '"' if characters.peek() == Some(&'"') => {
+                    characters.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(character),
+            }
+            continue;
+        }
+        match character {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if characters.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(character),
+        }
+    }
+    if in_quotes {
+        return Err(String::from("Unterminated quoted field"));
+    }
+    // the last line may not end with a newline
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // blank lines carry no values
+    records.retain(|record| !(record.len() == 1 && record[0].is_empty()));
+    Ok(records)
+}
+
+// the header is always written so a result without rows still names its columns,
+// NULL is written as an empty field which parse_csv reads back as NULL
+pub fn to_csv(table_inserted_data: &BTableInsertedData) -> String {
+    let records = std::iter::once(
+        table_inserted_data
+            .column_names
+            .iter()
+            .map(|column_name| csv_field(column_name))
+            .collect::<Vec<String>>(),
+    )
+    .chain(table_inserted_data.rows.iter().map(|row| {
+        row.iter()
+            .map(|value| csv_field(value.as_deref().unwrap_or_default()))
+            .collect()
+    }));
+    records.fold(String::new(), |mut content, record| {
+        content.push_str(&record.join(","));
+        content.push('\n');
+        content
+    })
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::business_components::component::{BDataType, BPage};
+
+    fn query_result(rows: Vec<Vec<Option<String>>>) -> BTableInsertedData {
+        BTableInsertedData {
+            table_name: String::new(),
+            column_names: vec![String::from("id"), String::from("name")],
+            data_types: vec![BDataType::INTEGER, BDataType::TEXT],
+            read_only_columns: vec![false, false],
+            total_row_count: rows.len() as i64,
+            rows,
+            page: BPage::default(),
+        }
+    }
+
+    #[test]
+    fn test_to_csv_round_trip() {
+        let content = to_csv(&query_result(vec![
+            vec![Some("1".to_string()), Some("Smith, \"Al\"\nJr".to_string())],
+            vec![Some("2".to_string()), None],
+        ]));
+        assert_eq!(content, "id,name\n1,\"Smith, \"\"Al\"\"\nJr\"\n2,\n");
+        assert_eq!(
+            parse_csv(&content).unwrap(),
+            vec![
+                vec!["id".to_string(), "name".to_string()],
+                vec!["1".to_string(), "Smith, \"Al\"\nJr".to_string()],
+                vec!["2".to_string(), "".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_csv_without_rows() {
+        assert_eq!(to_csv(&query_result(vec![])), "id,name\n");
+    }
+
+    #[test]
+    fn test_parse_simple_csv() {
+        assert_eq!(
+            parse_csv("id,name\n1,Alice\n2,Bob").unwrap(),
+            vec![
+                vec!["id".to_string(), "name".to_string()],
+                vec!["1".to_string(), "Alice".to_string()],
+                vec!["2".to_string(), "Bob".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        assert_eq!(
+            parse_csv("id,name\r\n1,\"Smith, \"\"Al\"\"\nJr\"\r\n").unwrap(),
+            vec![
+                vec!["id".to_string(), "name".to_string()],
+                vec!["1".to_string(), "Smith, \"Al\"\nJr".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_fields_and_blank_lines() {
+        assert_eq!(
+            parse_csv("id,name\n\n3,\n").unwrap(),
+            vec![
+                vec!["id".to_string(), "name".to_string()],
+                vec!["3".to_string(), "".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unterminated_quote() {
+        assert!(parse_csv("id,name\n1,\"Alice").is_err());
+    }
+}
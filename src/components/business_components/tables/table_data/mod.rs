@@ -1 +1,2 @@
+pub(crate) mod csv;
 pub mod table_data;
@@ -1,16 +1,28 @@
 use crate::components::business_components::component::{
-    repository_module::BRepository, BColumn, BColumnForeignKey, BCondition, BConstraint, BDataType,
-    BRowColumnValue, BRowInsertData, BTableChangeEvents, BTableDataChangeEvents, BTableGeneral,
-    BTableIn, BTableInfo, BTableInsertedData, BusinessComponent,
+    repository_module::BRepository, BChangeSummary, BColumn, BColumnForeignKey, BCondition,
+    BConstraint, BDataType, BLogLevel, BOperator, BPage, BRowColumnValue, BRowDiff, BRowInsertData,
+    BSortDirection, BTableChangeEvents, BTableDataChangeEvents, BTableGeneral, BTableIn,
+    BTableInfo, BTableInsertedData, BusinessComponent,
 };
 use crate::components::business_components::components::BusinessConsole;
-use sqlx::Row;
+use crate::components::business_components::tables::{
+    table_data::csv::parse_csv, validation::validate_value,
+};
 use std::collections::HashMap;
+use std::fs;
 use std::iter::zip;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::task;
 
+// state restored by undo and redo
+#[derive(Debug, Clone)]
+struct TableDataSnapshot {
+    table_data_change_events: Vec<BTableDataChangeEvents>,
+    current_to_initial_row_indexes: HashMap<usize, usize>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TableData {
     repository: Arc<BRepository>,
@@ -20,7 +32,35 @@ pub struct TableData {
     table_data_change_events: Arc<AsyncMutex<Vec<BTableDataChangeEvents>>>,
     primary_key_column_names: Arc<AsyncMutex<Vec<String>>>,
     current_to_initial_row_indexes: Arc<AsyncMutex<HashMap<usize, usize>>>,
+    pub foreign_keys: Arc<AsyncMutex<Vec<BColumnForeignKey>>>,
+    not_null_column_names: Arc<AsyncMutex<Vec<String>>>,
+    // column name to comment, only commented columns are present
+    pub column_comments: Arc<AsyncMutex<HashMap<String, String>>>,
+    pub order_by: Arc<AsyncMutex<Option<(String, BSortDirection)>>>,
+    pub filters: Arc<AsyncMutex<Vec<BCondition>>>,
+    undo_stack: Arc<AsyncMutex<Vec<TableDataSnapshot>>>,
+    redo_stack: Arc<AsyncMutex<Vec<TableDataSnapshot>>>,
+    // every edit is saved as soon as it is made instead of waiting for a commit
+    auto_commit: Arc<AsyncMutex<bool>>,
 }
+
+impl BusinessComponent for TableData {
+    // the loaded rows and any pending changes are dropped
+    async fn teardown_component(&self) {
+        *self.table_inserted_data.lock().await = None;
+        *self.table_data_change_events.lock().await = vec![];
+        *self.primary_key_column_names.lock().await = vec![];
+        *self.current_to_initial_row_indexes.lock().await = HashMap::new();
+        *self.foreign_keys.lock().await = vec![];
+        *self.not_null_column_names.lock().await = vec![];
+        *self.column_comments.lock().await = HashMap::new();
+        *self.order_by.lock().await = None;
+        *self.filters.lock().await = vec![];
+        *self.undo_stack.lock().await = vec![];
+        *self.redo_stack.lock().await = vec![];
+    }
+}
+
 impl TableData {
     pub fn new(
         repository: Arc<BRepository>,
@@ -35,19 +75,322 @@ impl TableData {
             table_data_change_events: Arc::new(AsyncMutex::new(vec![])),
             primary_key_column_names: Arc::new(AsyncMutex::new(vec![])),
             current_to_initial_row_indexes: Arc::new(AsyncMutex::new(HashMap::new())),
+            foreign_keys: Arc::new(AsyncMutex::new(vec![])),
+            not_null_column_names: Arc::new(AsyncMutex::new(vec![])),
+            column_comments: Arc::new(AsyncMutex::new(HashMap::new())),
+            order_by: Arc::new(AsyncMutex::new(None)),
+            filters: Arc::new(AsyncMutex::new(vec![])),
+            undo_stack: Arc::new(AsyncMutex::new(vec![])),
+            redo_stack: Arc::new(AsyncMutex::new(vec![])),
+            auto_commit: Arc::new(AsyncMutex::new(false)),
         }
     }
 
-    pub fn reset_table_data(&self) {
-        let mut locked_table_inserted_data = self.table_inserted_data.blocking_lock();
-        *locked_table_inserted_data = None;
-        let mut locked_table_data_change_events = self.table_data_change_events.blocking_lock();
-        *locked_table_data_change_events = vec![];
-        let mut locked_primary_key_column_names = self.primary_key_column_names.blocking_lock();
-        *locked_primary_key_column_names = vec![];
-        let mut locked_current_to_initial_row_indexes =
-            self.current_to_initial_row_indexes.blocking_lock();
-        *locked_current_to_initial_row_indexes = HashMap::new();
+    pub fn has_pending_changes(&self) -> bool {
+        !self.table_data_change_events.blocking_lock().is_empty()
+    }
+
+    pub fn pending_change_count(&self) -> usize {
+        self.table_data_change_events.blocking_lock().len()
+    }
+
+    pub fn change_summary(&self) -> BChangeSummary {
+        self.table_data_change_events.blocking_lock().iter().fold(
+            BChangeSummary::default(),
+            |mut change_summary, table_data_change_event| {
+                match table_data_change_event {
+                    BTableDataChangeEvents::InsertRow(_) => change_summary.inserts += 1,
+                    BTableDataChangeEvents::ModifyRowColumnValue(_) => change_summary.updates += 1,
+                    BTableDataChangeEvents::DeleteRow(_) => change_summary.deletes += 1,
+                }
+                change_summary
+            },
+        )
+    }
+
+    // one entry per queued change event, in the order they were made
+    pub fn pending_diff(&self) -> Vec<BRowDiff> {
+        let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
+        let Some(table_inserted_data) = locked_table_inserted_data.as_ref() else {
+            return vec![];
+        };
+        let primary_key_column_names = self.primary_key_column_names.blocking_lock();
+        let column_names = &table_inserted_data.column_names;
+        // the events identify the committed rows by their primary key values
+        let find_initial_row = |conditions: &Vec<BCondition>| {
+            (0..table_inserted_data.rows.len())
+                .find(|initial_row_index| {
+                    Self::get_initial_row_conditions(
+                        &primary_key_column_names,
+                        *initial_row_index,
+                        table_inserted_data,
+                    ) == *conditions
+                })
+                .map(|initial_row_index| &table_inserted_data.rows[initial_row_index])
+        };
+        self.table_data_change_events
+            .blocking_lock()
+            .iter()
+            .map(|table_data_change_event| match table_data_change_event {
+                BTableDataChangeEvents::InsertRow(row_insert_data) => BRowDiff::Inserted(
+                    zip(
+                        row_insert_data.column_names.clone(),
+                        row_insert_data.values.clone(),
+                    )
+                    .collect(),
+                ),
+                BTableDataChangeEvents::ModifyRowColumnValue(row_column_value) => {
+                    let initial_row = find_initial_row(&row_column_value.conditions);
+                    let mut changed_cells: Vec<(String, Option<String>, Option<String>)> =
+                        row_column_value
+                            .column_values
+                            .iter()
+                            .map(|(column_name, (_, new_value))| {
+                                let old_value = initial_row.and_then(|row| {
+                                    column_names
+                                        .iter()
+                                        .position(|name| name == column_name)
+                                        .and_then(|column_index| row[column_index].clone())
+                                });
+                                (column_name.clone(), old_value, new_value.clone())
+                            })
+                            .collect();
+                    changed_cells.sort_by_key(|(column_name, _, _)| {
+                        column_names.iter().position(|name| name == column_name)
+                    });
+                    BRowDiff::Modified {
+                        primary_key: row_column_value
+                            .conditions
+                            .iter()
+                            .map(|condition| {
+                                (condition.column_name.clone(), condition.value.clone())
+                            })
+                            .collect(),
+                        changed_cells,
+                    }
+                }
+                BTableDataChangeEvents::DeleteRow(conditions) => {
+                    BRowDiff::Deleted(match find_initial_row(conditions) {
+                        Some(row) => zip(column_names.clone(), row.clone()).collect(),
+                        None => conditions
+                            .iter()
+                            .map(|condition| {
+                                (condition.column_name.clone(), Some(condition.value.clone()))
+                            })
+                            .collect(),
+                    })
+                }
+            })
+            .collect()
+    }
+
+    // the loaded rows are shown again as they are in the database
+    pub fn discard_pending_changes(&self) {
+        if !self.has_pending_changes() {
+            return;
+        }
+        let row_count = self
+            .table_inserted_data
+            .blocking_lock()
+            .as_ref()
+            .map_or(0, |table_inserted_data| table_inserted_data.rows.len());
+        *self.current_to_initial_row_indexes.blocking_lock() =
+            (0..row_count).map(|index| (index, index)).collect();
+        *self.table_data_change_events.blocking_lock() = vec![];
+        *self.undo_stack.blocking_lock() = vec![];
+        *self.redo_stack.blocking_lock() = vec![];
+    }
+
+    // unlike resetting the table data the rows are not loaded again
+    pub fn discard_changes(&self) {
+        let discarded_count = self.table_data_change_events.blocking_lock().len();
+        self.discard_pending_changes();
+        self.console
+            .write_info(format!("Discarded {} pending change(s)", discarded_count));
+    }
+
+    pub async fn set_order_by(&self, order_by: Option<(String, BSortDirection)>) {
+        *self.order_by.lock().await = order_by;
+    }
+
+    pub async fn set_filters(&self, filters: Vec<BCondition>) {
+        *self.filters.lock().await = filters;
+    }
+
+    pub fn get_primary_key_column_names(&self) -> Vec<String> {
+        self.primary_key_column_names.blocking_lock().clone()
+    }
+
+    // replaces the filters with one equal condition per primary key column,
+    // the values are given in the order of get_primary_key_column_names
+    pub async fn look_up_primary_key(&self, primary_key_values: Vec<String>) {
+        let (table_name, page, filters) = {
+            let locked_table_inserted_data = self.table_inserted_data.lock().await;
+            let Some(table_inserted_data) = locked_table_inserted_data.as_ref() else {
+                return;
+            };
+            let primary_key_column_names = self.primary_key_column_names.lock().await;
+            let filters: Vec<BCondition> = zip(primary_key_column_names.iter(), primary_key_values)
+                .filter_map(|(primary_key_column_name, value)| {
+                    let column_index = table_inserted_data
+                        .column_names
+                        .iter()
+                        .position(|column_name| column_name == primary_key_column_name)?;
+                    Some(BCondition {
+                        column_name: primary_key_column_name.clone(),
+                        data_type: table_inserted_data.data_types[column_index].clone(),
+                        operator: BOperator::Equal,
+                        value,
+                    })
+                })
+                .collect();
+            let page = BPage {
+                size: table_inserted_data.page.size,
+                number: 0,
+            };
+            (table_inserted_data.table_name.clone(), page, filters)
+        };
+        self.set_filters(filters).await;
+        self.set_table_data(table_name, page).await;
+    }
+
+    pub fn is_auto_commit(&self) -> bool {
+        *self.auto_commit.blocking_lock()
+    }
+
+    pub fn set_auto_commit(&self, auto_commit: bool) {
+        *self.auto_commit.blocking_lock() = auto_commit;
+    }
+
+    // saves the edit that was just queued, one the database rejects is dropped
+    // by reloading the page so it isn't saved again with the next edit
+    pub async fn auto_commit_table_data(&self) {
+        if !*self.auto_commit.lock().await {
+            return;
+        }
+        self.update_table_data().await;
+        if self.table_data_change_events.lock().await.is_empty() {
+            return;
+        }
+        let Some((table_name, page)) =
+            self.table_inserted_data
+                .lock()
+                .await
+                .as_ref()
+                .map(|table_inserted_data| {
+                    (
+                        table_inserted_data.table_name.clone(),
+                        table_inserted_data.page.clone(),
+                    )
+                })
+        else {
+            return;
+        };
+        self.set_table_data(table_name, page).await;
+    }
+
+    fn snapshot(&self) -> TableDataSnapshot {
+        TableDataSnapshot {
+            table_data_change_events: self.table_data_change_events.blocking_lock().clone(),
+            current_to_initial_row_indexes: self
+                .current_to_initial_row_indexes
+                .blocking_lock()
+                .clone(),
+        }
+    }
+
+    fn restore_snapshot(&self, snapshot: TableDataSnapshot) {
+        *self.table_data_change_events.blocking_lock() = snapshot.table_data_change_events;
+        *self.current_to_initial_row_indexes.blocking_lock() =
+            snapshot.current_to_initial_row_indexes;
+    }
+
+    // a new change makes the undone changes unreachable
+    fn push_undo_snapshot(&self, snapshot: TableDataSnapshot) {
+        self.undo_stack.blocking_lock().push(snapshot);
+        *self.redo_stack.blocking_lock() = vec![];
+    }
+
+    pub fn undo_last_event(&self) -> bool {
+        let Some(snapshot) = self.undo_stack.blocking_lock().pop() else {
+            self.console
+                .write(BLogLevel::Warning, String::from("Nothing to undo"));
+            return false;
+        };
+        self.redo_stack.blocking_lock().push(self.snapshot());
+        self.restore_snapshot(snapshot);
+        self.console.write_info(format!(
+            "Undid last change: {:?}",
+            *self.table_data_change_events.blocking_lock()
+        ));
+        true
+    }
+
+    pub fn redo_last_event(&self) -> bool {
+        let Some(snapshot) = self.redo_stack.blocking_lock().pop() else {
+            self.console
+                .write(BLogLevel::Warning, String::from("Nothing to redo"));
+            return false;
+        };
+        self.undo_stack.blocking_lock().push(self.snapshot());
+        self.restore_snapshot(snapshot);
+        self.console.write_info(format!(
+            "Redid last change: {:?}",
+            *self.table_data_change_events.blocking_lock()
+        ));
+        true
+    }
+
+    // the loaded rows with the queued change events applied, in display order
+    pub fn get_displayed_rows(&self) -> Vec<Vec<Option<String>>> {
+        let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
+        let Some(table_inserted_data) = locked_table_inserted_data.as_ref() else {
+            return vec![];
+        };
+        let table_data_change_events = self.table_data_change_events.blocking_lock();
+        let current_to_initial_row_indexes = self.current_to_initial_row_indexes.blocking_lock();
+        let primary_key_column_names = self.primary_key_column_names.blocking_lock();
+        let delete_row_event_count = table_data_change_events
+            .iter()
+            .filter(|event| matches!(event, BTableDataChangeEvents::DeleteRow(_)))
+            .count();
+
+        let mut displayed_rows = vec![];
+        for row_index in 0..table_inserted_data
+            .rows
+            .len()
+            .saturating_sub(delete_row_event_count)
+        {
+            let initial_row_index = current_to_initial_row_indexes[&row_index];
+            let mut row = table_inserted_data.rows[initial_row_index].clone();
+            let conditions = Self::get_initial_row_conditions(
+                &primary_key_column_names,
+                initial_row_index,
+                table_inserted_data,
+            );
+            for event in table_data_change_events.iter() {
+                if let BTableDataChangeEvents::ModifyRowColumnValue(row_column_value) = event {
+                    if row_column_value.conditions == conditions {
+                        for (column_name, (_, value)) in &row_column_value.column_values {
+                            if let Some(column_index) = table_inserted_data
+                                .column_names
+                                .iter()
+                                .position(|name| name == column_name)
+                            {
+                                row[column_index] = value.clone();
+                            }
+                        }
+                    }
+                }
+            }
+            displayed_rows.push(row);
+        }
+        for event in table_data_change_events.iter() {
+            if let BTableDataChangeEvents::InsertRow(row_insert_data) = event {
+                displayed_rows.push(row_insert_data.values.clone());
+            }
+        }
+        displayed_rows
     }
 
     fn get_primary_key_conditions(
@@ -62,16 +405,29 @@ impl TableData {
             .get(&row_index)
             .unwrap()
             .clone();
+        Self::get_initial_row_conditions(
+            &primary_key_column_names,
+            adjusted_row_index,
+            table_inserted_data,
+        )
+    }
+
+    fn get_initial_row_conditions(
+        primary_key_column_names: &Vec<String>,
+        initial_row_index: usize,
+        table_inserted_data: &BTableInsertedData,
+    ) -> Vec<BCondition> {
         table_inserted_data
             .column_names
             .iter()
             .zip(&table_inserted_data.data_types)
-            .zip(&table_inserted_data.rows[adjusted_row_index])
+            .zip(&table_inserted_data.rows[initial_row_index])
             .filter(|((column_name, _), _)| primary_key_column_names.contains(column_name))
             .map(|((column_name, data_type), value)| BCondition {
                 column_name: column_name.clone(),
                 data_type: data_type.clone(),
-                value: value.clone(),
+                operator: BOperator::Equal,
+                value: value.clone().unwrap_or_default(),
             })
             .collect()
     }
@@ -89,8 +445,12 @@ impl TableData {
             .count();
         // checks if row was inserted through a insert row event
         // by row_index >= len(rows) - deleted row events
-        let index_of_events_filtered_by_insert_row =
-            row_index.checked_sub(table_inserted_data.rows.len() - delete_row_event_count)?;
+        let index_of_events_filtered_by_insert_row = row_index.checked_sub(
+            table_inserted_data
+                .rows
+                .len()
+                .saturating_sub(delete_row_event_count),
+        )?;
 
         // Find the nth InsertRow event index
         table_data_change_events
@@ -123,7 +483,7 @@ impl TableData {
         row_index: usize,
         event_index: usize,
         column_name: String,
-        new_value: String,
+        new_value: Option<String>,
         data_type: BDataType,
     ) {
         if let Some(event) = table_data_change_events.get_mut(event_index) {
@@ -159,25 +519,281 @@ impl TableData {
         }
     }
 
-    pub fn add_insert_row_event(&self, values: Vec<String>) {
+    // generated columns are left NULL so the insert omits them,
+    // returns false if a value doesn't match its column type
+    pub fn add_insert_row_event(&self, mut values: Vec<Option<String>>) -> bool {
+        let snapshot = self.snapshot();
         let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
-        let mut locked_table_data_change_events = self.table_data_change_events.blocking_lock();
         let table_inserted_data = locked_table_inserted_data.as_ref().unwrap();
+        let invalid_values: Vec<String> = zip(
+            &table_inserted_data.column_names,
+            zip(&table_inserted_data.data_types, &values),
+        )
+        // empty values of the other types are inserted as NULL
+        .filter(|(_, (data_type, value))| !data_type.is_null(value))
+        .filter_map(|(column_name, (data_type, value))| {
+            validate_value(data_type, value.as_deref()?)
+                .err()
+                .map(|error| format!("{} ({})", error, column_name))
+        })
+        .collect();
+        if !invalid_values.is_empty() {
+            self.console.write(
+                BLogLevel::Warning,
+                format!("Rejected row: {}", invalid_values.join(", ")),
+            );
+            return false;
+        }
+        let mut locked_table_data_change_events = self.table_data_change_events.blocking_lock();
+        for (value, is_read_only) in values
+            .iter_mut()
+            .zip(&table_inserted_data.read_only_columns)
+        {
+            if *is_read_only {
+                *value = None;
+            }
+        }
         locked_table_data_change_events.push(BTableDataChangeEvents::InsertRow(BRowInsertData {
             column_names: table_inserted_data.column_names.clone(),
             values,
             data_types: table_inserted_data.data_types.clone(),
         }));
+        self.push_undo_snapshot(snapshot);
         self.console
-            .write(format!("{:?}", locked_table_data_change_events));
+            .write_info(format!("{:?}", locked_table_data_change_events));
+        true
+    }
+
+    // the columns that aren't given are NULL, which leaves those with a default out of the INSERT
+    pub fn add_partial_insert_row_event(&self, values: HashMap<String, String>) -> bool {
+        let values = {
+            let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
+            let table_inserted_data = locked_table_inserted_data.as_ref().unwrap();
+            let unknown_column_names: Vec<&str> = values
+                .keys()
+                .filter(|column_name| !table_inserted_data.column_names.contains(column_name))
+                .map(String::as_str)
+                .collect();
+            if !unknown_column_names.is_empty() {
+                self.console.write(
+                    BLogLevel::Warning,
+                    format!(
+                        "Rejected row: unknown columns {}",
+                        unknown_column_names.join(", ")
+                    ),
+                );
+                return false;
+            }
+            table_inserted_data
+                .column_names
+                .iter()
+                .map(|column_name| values.get(column_name).cloned())
+                .collect()
+        };
+        self.add_insert_row_event(values)
+    }
+
+    // primary key values are set to NULL so the insert generates new ones
+    pub fn duplicate_row(&self, row_index: usize) -> bool {
+        let Some(mut values) = self.get_displayed_rows().into_iter().nth(row_index) else {
+            return false;
+        };
+        {
+            let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
+            let primary_key_column_names = self.primary_key_column_names.blocking_lock();
+            let table_inserted_data = locked_table_inserted_data.as_ref().unwrap();
+            for (value, column_name) in values.iter_mut().zip(&table_inserted_data.column_names) {
+                if primary_key_column_names.contains(column_name) {
+                    *value = None;
+                }
+            }
+        }
+        self.add_insert_row_event(values)
+    }
+
+    // queues one insert row event per valid record and returns how many were queued
+    pub fn import_csv(&self, path: &Path) -> usize {
+        let (column_names, data_types) = {
+            let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
+            let Some(table_inserted_data) = locked_table_inserted_data.as_ref() else {
+                return 0;
+            };
+            (
+                table_inserted_data.column_names.clone(),
+                table_inserted_data.data_types.clone(),
+            )
+        };
+        let records = match fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|content| parse_csv(&content))
+        {
+            Ok(records) => records,
+            Err(error) => {
+                self.console.write(
+                    BLogLevel::Error,
+                    format!("Could not import {}: {}", path.display(), error),
+                );
+                return 0;
+            }
+        };
+        let Some((header, rows)) = records.split_first() else {
+            self.console.write(
+                BLogLevel::Error,
+                format!("Could not import {}: file is empty", path.display()),
+            );
+            return 0;
+        };
+        if *header != column_names {
+            self.console.write(
+                BLogLevel::Error,
+                format!(
+                    "Could not import {}: header {} does not match columns {}",
+                    path.display(),
+                    header.join(", "),
+                    column_names.join(", ")
+                ),
+            );
+            return 0;
+        }
+
+        let mut imported_row_count = 0;
+        // the header is line 1
+        for (record_index, row) in rows.iter().enumerate() {
+            let line_number = record_index + 2;
+            if row.len() != column_names.len() {
+                self.console.write(
+                    BLogLevel::Warning,
+                    format!(
+                        "Skipped line {}: expected {} values but found {}",
+                        line_number,
+                        column_names.len(),
+                        row.len()
+                    ),
+                );
+                continue;
+            }
+            // empty fields are imported as NULL so they aren't values of the column type
+            let invalid_values: Vec<String> = zip(&column_names, zip(&data_types, row))
+                .filter(|(_, (_, value))| !value.is_empty())
+                .filter_map(|(column_name, (data_type, value))| {
+                    validate_value(data_type, value)
+                        .err()
+                        .map(|error| format!("{} ({})", error, column_name))
+                })
+                .collect();
+            if !invalid_values.is_empty() {
+                self.console.write(
+                    BLogLevel::Warning,
+                    format!(
+                        "Skipped line {}: {}",
+                        line_number,
+                        invalid_values.join(", ")
+                    ),
+                );
+                continue;
+            }
+            // csv has no NULL, an empty field is imported as NULL
+            let values = row
+                .iter()
+                .map(|value| (!value.is_empty()).then(|| value.clone()))
+                .collect();
+            self.add_insert_row_event(values);
+            imported_row_count += 1;
+        }
+        self.console.write_info(format!(
+            "Queued {} rows from {}",
+            imported_row_count,
+            path.display()
+        ));
+        imported_row_count
+    }
+
+    // the type check an edit has to pass to be queued, without warning about the value
+    pub fn validate_cell_value(&self, col_index: usize, value: &str) -> Result<(), String> {
+        match self
+            .table_inserted_data
+            .blocking_lock()
+            .as_ref()
+            .and_then(|table_inserted_data| table_inserted_data.data_types.get(col_index))
+        {
+            Some(data_type) => validate_value(data_type, value),
+            None => Ok(()),
+        }
     }
 
+    // returns false if the value was rejected
     pub fn add_modify_row_column_value_event(
         &self,
         row_index: usize,
         column_name: String,
-        new_value: String,
-    ) {
+        new_value: Option<String>,
+    ) -> bool {
+        let is_read_only = self
+            .table_inserted_data
+            .blocking_lock()
+            .as_ref()
+            .is_some_and(|table_inserted_data| {
+                zip(
+                    &table_inserted_data.column_names,
+                    &table_inserted_data.read_only_columns,
+                )
+                .any(|(name, is_read_only)| *name == column_name && *is_read_only)
+            });
+        if is_read_only {
+            self.console.write(
+                BLogLevel::Warning,
+                format!(
+                    "Column {} is generated by the database and cannot be edited",
+                    column_name
+                ),
+            );
+            return false;
+        }
+        let data_type =
+            self.table_inserted_data
+                .blocking_lock()
+                .as_ref()
+                .and_then(|table_inserted_data| {
+                    zip(
+                        &table_inserted_data.column_names,
+                        &table_inserted_data.data_types,
+                    )
+                    .find(|(name, _)| **name == column_name)
+                    .map(|(_, data_type)| data_type.clone())
+                });
+        let is_null = data_type.as_ref().map_or(new_value.is_none(), |data_type| {
+            data_type.is_null(&new_value)
+        });
+        if is_null
+            && self
+                .not_null_column_names
+                .blocking_lock()
+                .contains(&column_name)
+        {
+            self.console.write(
+                BLogLevel::Warning,
+                format!(
+                    "Column {} cannot be NULL because it is NOT NULL",
+                    column_name
+                ),
+            );
+            return false;
+        }
+        if let Some(Err(error)) = data_type
+            .as_ref()
+            .filter(|_| !is_null)
+            .and_then(|data_type| {
+                new_value
+                    .as_deref()
+                    .map(|new_value| validate_value(data_type, new_value))
+            })
+        {
+            self.console
+                .write(BLogLevel::Warning, format!("{} ({})", error, column_name));
+            return false;
+        }
+        let snapshot = self.snapshot();
+
         // Step 1: Acquire the table data lock first, process what can be done without holding all locks
         let table_inserted_data = {
             let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
@@ -201,15 +817,16 @@ impl TableData {
                     &new_value,
                     &table_inserted_data,
                 );
+                self.push_undo_snapshot(snapshot);
                 self.console
-                    .write(format!("{:?}", locked_table_data_change_events));
+                    .write_info(format!("{:?}", locked_table_data_change_events));
             }
-            return;
+            return true;
         }
 
         // Step 2: Check if the row index is in the database
         if row_index >= table_inserted_data.rows.len() {
-            return; // Invalid row index, no further processing needed
+            return true; // Invalid row index, no further processing needed
         }
 
         let column_datatype_index = table_inserted_data
@@ -249,15 +866,17 @@ impl TableData {
                 row_column_value,
             ));
         }
+        self.push_undo_snapshot(snapshot);
         self.console
-            .write(format!("{:?}", locked_table_data_change_events));
+            .write_info(format!("{:?}", locked_table_data_change_events));
+        true
     }
 
     fn update_existing_insert_row_event(
         &self,
         event: &mut BTableDataChangeEvents,
         column_name: &str,
-        new_value: &str,
+        new_value: &Option<String>,
         table_inserted_data: &BTableInsertedData,
     ) {
         match event {
@@ -266,9 +885,9 @@ impl TableData {
                     zip(&table_inserted_data.column_names, &row_insert_data.values)
                         .map(|(col_name, value)| {
                             if col_name == column_name {
-                                new_value.to_string() // Update the value for the matching column
+                                new_value.clone() // Update the value for the matching column
                             } else {
-                                value.to_string() // Keep the existing value
+                                value.clone() // Keep the existing value
                             }
                         })
                         .collect();
@@ -278,6 +897,16 @@ impl TableData {
     }
 
     pub fn add_delete_row_event(&self, row_index: usize) {
+        self.add_delete_rows_event(&[row_index]);
+    }
+
+    // deleting from the highest index down keeps the lower indexes pointing at the same rows,
+    // all deletes are undone together
+    pub fn add_delete_rows_event(&self, row_indexes: &[usize]) {
+        let snapshot = self.snapshot();
+        let mut row_indexes = row_indexes.to_vec();
+        row_indexes.sort_by(|a, b| b.cmp(a));
+        row_indexes.dedup();
         // Acquire locks for necessary data
         let locked_table_inserted_data = self.table_inserted_data.blocking_lock();
 
@@ -286,27 +915,50 @@ impl TableData {
         // Safely unwrap the locked data
         let table_inserted_data = locked_table_inserted_data.as_ref().unwrap();
 
+        let mut deleted_row_count = 0;
+        for row_index in row_indexes {
+            if self.queue_delete_row_event(
+                &mut locked_table_data_change_events,
+                table_inserted_data,
+                row_index,
+            ) {
+                deleted_row_count += 1;
+            }
+        }
+        if deleted_row_count == 0 {
+            return;
+        }
+        self.push_undo_snapshot(snapshot);
+        // Log the current state of table data change events to the console
+        self.console
+            .write_info(format!("{:?}", *locked_table_data_change_events));
+    }
+
+    // returns false if the row index is out of bounds
+    fn queue_delete_row_event(
+        &self,
+        table_data_change_events: &mut Vec<BTableDataChangeEvents>,
+        table_inserted_data: &BTableInsertedData,
+        row_index: usize,
+    ) -> bool {
         if let Some(existing_event_index) = self.find_existing_row_insert_event(
-            &locked_table_data_change_events,
-            &table_inserted_data,
+            table_data_change_events,
+            table_inserted_data,
             row_index,
         ) {
-            locked_table_data_change_events.remove(existing_event_index);
-            self.console
-                .write(format!("{:?}", locked_table_data_change_events));
-
-            return;
+            table_data_change_events.remove(existing_event_index);
+            return true;
         }
         // Ensure the row index is valid
         else if row_index >= table_inserted_data.rows.len() {
-            return; // Exit if the row index is out of bounds
+            return false; // Exit if the row index is out of bounds
         }
 
         // Extract conditions based on primary key column names
-        let conditions = self.get_primary_key_conditions(row_index, &table_inserted_data);
+        let conditions = self.get_primary_key_conditions(row_index, table_inserted_data);
 
         // Add the delete row event
-        locked_table_data_change_events.push(BTableDataChangeEvents::DeleteRow(conditions));
+        table_data_change_events.push(BTableDataChangeEvents::DeleteRow(conditions));
         let mut locked_current_to_initial_row_indexes =
             self.current_to_initial_row_indexes.blocking_lock();
 
@@ -316,7 +968,8 @@ impl TableData {
             .filter(|current_row_index| *current_row_index > row_index)
             .collect();
 
-        keys_to_update.sort_by(|a, b| b.cmp(a));
+        // shifted from the lowest index up so each row is read before it is overwritten
+        keys_to_update.sort();
 
         for (iter_index, current_row_index) in keys_to_update.iter().enumerate() {
             let initial_row_index = locked_current_to_initial_row_indexes
@@ -326,58 +979,175 @@ impl TableData {
             let new_current_row_index = current_row_index - 1;
             locked_current_to_initial_row_indexes
                 .insert(new_current_row_index, initial_row_index.clone());
-            if iter_index == 0 {
+            if iter_index == keys_to_update.len() - 1 {
                 locked_current_to_initial_row_indexes.remove(current_row_index);
             }
         }
-        // Log the current state of table data change events to the console
-        self.console
-            .write(format!("{:?}", *locked_table_data_change_events));
+        true
+    }
+
+    // renders the queued events with the same queries update_table_data executes
+    pub async fn preview_sql(&self) -> Vec<String> {
+        let Some(table_name) = self
+            .table_inserted_data
+            .lock()
+            .await
+            .as_ref()
+            .map(|table_inserted_data| table_inserted_data.table_name.clone())
+        else {
+            return vec![];
+        };
+        let table_data_change_events = self.table_data_change_events.lock().await.clone();
+        match self
+            .repository
+            .get_table_data_queries(&table_name, &table_data_change_events)
+            .await
+        {
+            Ok(queries) => queries,
+            Err(error) => {
+                let console = self.console.clone();
+                task::spawn_blocking(move || {
+                    console.write(
+                        BLogLevel::Error,
+                        format!("Could not preview changes: {}", error),
+                    );
+                })
+                .await;
+                vec![]
+            }
+        }
     }
 
     pub async fn update_table_data(&self) {
         // Extract and drop the lock on `table_inserted_data`
-        let (table_name, table_data_change_events) = {
+        let (table_name, page, table_data_change_events) = {
             let table_inserted_data_guard = self.table_inserted_data.lock().await;
             if let Some(ref table_inserted_data) = *table_inserted_data_guard {
                 let table_name = table_inserted_data.table_name.clone();
+                let page = table_inserted_data.page.clone();
                 let table_data_change_events_guard = self.table_data_change_events.lock().await;
                 let table_data_change_events = table_data_change_events_guard.clone();
-                (table_name, table_data_change_events)
+                (table_name, page, table_data_change_events)
             } else {
                 return; // If there's no table_inserted_data, exit the function
             }
         };
         {
             // Use the extracted values without holding the locks
-            self.repository
+            if let Err(error) = self
+                .repository
                 .update_table_data(&table_name, &table_data_change_events)
+                .await
+            {
+                // the transaction was rolled back so the events stay queued for a retry
+                let console = self.console.clone();
+                task::spawn_blocking(move || {
+                    console.write(
+                        BLogLevel::Error,
+                        format!("Could not save table data: {}", error),
+                    );
+                })
                 .await;
+                return;
+            }
         }
-        self.set_table_data(table_name.to_string()).await;
+        self.set_table_data(table_name.to_string(), page).await;
     }
-    pub async fn set_table_data(&self, table_name: String) {
+
+    // row indexes of change events are relative to the loaded page,
+    // so loading a page discards the queued events
+    pub async fn set_table_data(&self, table_name: String, page: BPage) {
         // Lock the general info table
         let tables_general_info = self.tables_general_info.lock().await;
         if let Some(table_general_info) = tables_general_info
             .iter()
             .find(|info| info.table_name == table_name)
         {
-            let primary_key_column_names = self
-                .repository
-                .get_primary_key_column_names(&table_name)
-                .await
-                .unwrap();
+            let order_by = {
+                let mut locked_order_by = self.order_by.lock().await;
+                // the sorted column may have been renamed or dropped
+                if let Some((column_name, _)) = locked_order_by.as_ref() {
+                    if !table_general_info.column_names.contains(column_name) {
+                        *locked_order_by = None;
+                    }
+                }
+                locked_order_by.clone()
+            };
+            let filters = {
+                let mut locked_filters = self.filters.lock().await;
+                locked_filters.retain(|filter| {
+                    table_general_info
+                        .column_names
+                        .contains(&filter.column_name)
+                });
+                locked_filters.clone()
+            };
             // Fetch rows for the table
-            let table_inserted_data_rows = self
+            let rows_result = async {
+                let total_row_count = self
+                    .repository
+                    .get_filtered_row_count(&table_name, &filters)
+                    .await?;
+                // a page left past the end by deleted or filtered out rows shows the last one
+                let page = BPage {
+                    number: page.number.min(page.page_count(total_row_count) - 1),
+                    ..page
+                };
+                let primary_key_column_names = self
+                    .repository
+                    .get_primary_key_column_names(&table_name)
+                    .await?;
+                let rows = self
+                    .repository
+                    .get_table_data_rows(
+                        &table_name,
+                        &table_general_info.column_names,
+                        &filters,
+                        &order_by,
+                        &primary_key_column_names,
+                        &page,
+                    )
+                    .await?;
+                Ok::<_, sqlx::Error>((total_row_count, page, primary_key_column_names, rows))
+            }
+            .await;
+            // the previously loaded data is kept so the ui stays usable
+            let (total_row_count, page, primary_key_column_names, table_inserted_data_rows) =
+                match rows_result {
+                    Ok(rows) => rows,
+                    Err(error) => {
+                        let console = self.console.clone();
+                        task::spawn_blocking(move || {
+                            console.write(
+                                BLogLevel::Error,
+                                format!("Could not load table data: {}", error),
+                            );
+                        })
+                        .await;
+                        return;
+                    }
+                };
+            let column_infos = self
                 .repository
-                .get_table_data_rows(
-                    &table_name,
-                    &table_general_info.column_names,
-                    &primary_key_column_names,
-                )
+                .get_columns_info(&table_name)
                 .await
-                .unwrap();
+                .unwrap_or_default();
+            // identity and computed columns are only told apart by the introspected info
+            let mut read_only_column_names: Vec<String> = column_infos
+                .iter()
+                .filter(|column_info| column_info.is_generated)
+                .map(|column_info| column_info.column_name.clone())
+                .collect();
+            let columns: Vec<BColumn> = column_infos
+                .into_iter()
+                .map(|column_info| BColumn::to_column(column_info))
+                .collect();
+            read_only_column_names.extend(
+                columns
+                    .iter()
+                    .filter(|column| column.is_generated())
+                    .map(|column| column.name.clone()),
+            );
             let mut locked_current_to_initial_row_indexes =
                 self.current_to_initial_row_indexes.lock().await;
             // Construct the inserted data
@@ -385,25 +1155,121 @@ impl TableData {
                 table_name: table_name.clone(),
                 column_names: table_general_info.column_names.clone(),
                 data_types: table_general_info.data_types.clone(),
-                rows: table_inserted_data_rows
+                read_only_columns: table_general_info
+                    .column_names
                     .iter()
-                    .map(|row| {
-                        table_general_info
-                            .column_names
-                            .iter()
-                            .map(|column_name| row.get::<String, _>(column_name.as_str()))
-                            .collect::<Vec<String>>()
-                    })
-                    .collect::<Vec<Vec<String>>>(),
+                    .map(|column_name| read_only_column_names.contains(column_name))
+                    .collect(),
+                rows: table_inserted_data_rows,
+                page,
+                total_row_count,
             };
+            // rebuilt from the filtered and ordered rows so new events map to the displayed rows
             *locked_current_to_initial_row_indexes = HashMap::new();
             for (index, _) in table_inserted_data.rows.iter().enumerate() {
                 locked_current_to_initial_row_indexes.insert(index, index);
             } // Update the shared table inserted data
             *self.table_inserted_data.lock().await = Some(table_inserted_data);
             *self.table_data_change_events.lock().await = vec![];
+            *self.undo_stack.lock().await = vec![];
+            *self.redo_stack.lock().await = vec![];
             *self.primary_key_column_names.lock().await = primary_key_column_names;
+            *self.foreign_keys.lock().await = Self::get_foreign_keys(&columns);
+            *self.not_null_column_names.lock().await = columns
+                .iter()
+                .filter(|column| column.constraints.contains(&BConstraint::NotNull))
+                .map(|column| column.name.clone())
+                .collect();
+            *self.column_comments.lock().await = columns
+                .iter()
+                .filter_map(|column| {
+                    column
+                        .comment
+                        .clone()
+                        .map(|comment| (column.name.clone(), comment))
+                })
+                .collect();
+        }
+    }
+
+    fn get_foreign_keys(columns: &Vec<BColumn>) -> Vec<BColumnForeignKey> {
+        let mut foreign_keys = vec![];
+        for column in columns {
+            for constraint in &column.constraints {
+                if let BConstraint::ForeignKey {
+                    table,
+                    column: referenced_column,
+                    on_delete,
+                } = constraint
+                {
+                    foreign_keys.push(BColumnForeignKey {
+                        column_name: column.name.clone(),
+                        referenced_table: table.clone(),
+                        referenced_column: referenced_column.clone(),
+                        on_delete: on_delete.clone(),
+                    });
+                }
+            }
+        }
+        foreign_keys
+    }
+
+    // duplicates of the loaded table, grouped by the given columns
+    pub async fn find_duplicates(
+        &self,
+        column_names: Vec<String>,
+    ) -> Vec<(Vec<Option<String>>, i64)> {
+        let Some(table_name) = self
+            .table_inserted_data
+            .lock()
+            .await
+            .as_ref()
+            .map(|table_inserted_data| table_inserted_data.table_name.clone())
+        else {
+            return vec![];
+        };
+        if column_names.is_empty() {
+            return vec![];
+        }
+        // the repository logs a failed query
+        self.repository
+            .find_duplicates(&table_name, &column_names)
+            .await
+            .unwrap_or_default()
+    }
+
+    pub async fn get_foreign_key_preview(
+        &self,
+        column_name: String,
+        value: String,
+    ) -> Option<Vec<(String, String)>> {
+        let foreign_key = {
+            let locked_foreign_keys = self.foreign_keys.lock().await;
+            locked_foreign_keys
+                .iter()
+                .find(|foreign_key| foreign_key.column_name == column_name)
+                .cloned()
+        }?;
+        // shows the key columns of the referenced row
+        let mut preview_column_names = self
+            .repository
+            .get_primary_key_column_names(&foreign_key.referenced_table)
+            .await
+            .ok()?;
+        if !preview_column_names.contains(&foreign_key.referenced_column) {
+            preview_column_names.push(foreign_key.referenced_column.clone());
         }
+        let referenced_row = self
+            .repository
+            .get_referenced_row(
+                &foreign_key.referenced_table,
+                &foreign_key.referenced_column,
+                &value,
+                &preview_column_names,
+            )
+            .await
+            .ok()??;
+        Some(zip(preview_column_names, referenced_row).collect())
     }
 }
 
@@ -434,7 +1300,9 @@ mod tests {
         let tables_general_info = Arc::new(AsyncMutex::new(Vec::<BTableGeneral>::new()));
         set_tables_general_info(repository_result.clone(), tables_general_info.clone()).await;
         let table_data = TableData::new(repository_result, console_result, tables_general_info);
-        table_data.set_table_data(table_in.table_name.clone()).await;
+        table_data
+            .set_table_data(table_in.table_name.clone(), BPage::default())
+            .await;
         table_data
     }
 
@@ -454,47 +1322,56 @@ mod tests {
             BTableDataChangeEvents::InsertRow(BRowInsertData {
                 column_names: column_names.clone(),
                 data_types: data_types.clone(),
-                values: vec!["1".to_string(), "Alice".to_string()],
+                values: vec![Some("1".to_string()), Some("Alice".to_string())],
             }),
             BTableDataChangeEvents::InsertRow(BRowInsertData {
                 column_names: column_names.clone(),
                 data_types: data_types.clone(),
-                values: vec!["2".to_string(), "Bob".to_string()],
+                values: vec![Some("2".to_string()), Some("Bob".to_string())],
             }),
             BTableDataChangeEvents::InsertRow(BRowInsertData {
                 column_names: column_names.clone(),
                 data_types: data_types.clone(),
-                values: vec!["3".to_string(), "Charlie".to_string()],
+                values: vec![Some("3".to_string()), Some("Charlie".to_string())],
             }),
             BTableDataChangeEvents::InsertRow(BRowInsertData {
                 column_names: column_names.clone(),
                 data_types: data_types.clone(),
-                values: vec!["4".to_string(), "Jacob".to_string()],
+                values: vec![Some("4".to_string()), Some("Jacob".to_string())],
             }),
         ];
         let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
         let copied_table_data = table_data.clone();
         task::spawn_blocking(move || {
-            copied_table_data.add_modify_row_column_value_event(0, id.clone(), "5".to_string());
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                id.clone(),
+                Some("5".to_string()),
+            );
             copied_table_data.add_modify_row_column_value_event(
                 3,
                 name.clone(),
-                "Liam".to_string(),
+                Some("Liam".to_string()),
             );
 
-            copied_table_data.add_modify_row_column_value_event(3, id.clone(), "8".to_string());
+            copied_table_data.add_modify_row_column_value_event(
+                3,
+                id.clone(),
+                Some("8".to_string()),
+            );
             copied_table_data.add_delete_row_event(1);
-            copied_table_data.add_insert_row_event(vec!["6".to_string(), "".to_string()]);
+            copied_table_data
+                .add_insert_row_event(vec![Some("6".to_string()), Some("".to_string())]);
             // row index is out of range
             copied_table_data.add_modify_row_column_value_event(
                 4,
                 name.clone(),
-                "John".to_string(),
+                Some("John".to_string()),
             );
             copied_table_data.add_modify_row_column_value_event(
                 2,
                 name.clone(),
-                "Daniel".to_string(),
+                Some("Daniel".to_string()),
             );
 
             // since there was a delete row event index 3 -> 2
@@ -506,12 +1383,15 @@ mod tests {
             table_name: table_in.table_name.clone(),
             column_names: column_names.clone(),
             data_types: data_types.clone(),
+            read_only_columns: vec![false, false],
             rows: vec![
-                vec!["3".to_string(), "Charlie".to_string()],
-                vec!["5".to_string(), "Alice".to_string()],
-                vec!["6".to_string(), "".to_string()],
-                vec!["8".to_string(), "Daniel".to_string()],
+                vec![Some("3".to_string()), Some("Charlie".to_string())],
+                vec![Some("5".to_string()), Some("Alice".to_string())],
+                vec![Some("6".to_string()), Some("".to_string())],
+                vec![Some("8".to_string()), Some("Daniel".to_string())],
             ],
+            page: BPage::default(),
+            total_row_count: 4,
         };
         let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
         assert_eq!(
@@ -519,4 +1399,1327 @@ mod tests {
             Some(expected_table_inserted_data)
         );
     }
+
+    #[sqlx::test]
+    async fn test_null_and_empty_text_are_distinct(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let name = column_names[1].clone();
+        let insert_row_events = vec![
+            BTableDataChangeEvents::InsertRow(BRowInsertData {
+                column_names: column_names.clone(),
+                data_types: data_types.clone(),
+                values: vec![Some("1".to_string()), None],
+            }),
+            BTableDataChangeEvents::InsertRow(BRowInsertData {
+                column_names: column_names.clone(),
+                data_types: data_types.clone(),
+                values: vec![Some("2".to_string()), Some("".to_string())],
+            }),
+        ];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        assert_eq!(
+            table_data
+                .table_inserted_data
+                .lock()
+                .await
+                .as_ref()
+                .unwrap()
+                .rows,
+            vec![
+                vec![Some("1".to_string()), None],
+                vec![Some("2".to_string()), Some("".to_string())],
+            ]
+        );
+
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                name.clone(),
+                Some("".to_string()),
+            );
+            copied_table_data.add_modify_row_column_value_event(1, name, None);
+        })
+        .await;
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![
+                vec![Some("1".to_string()), Some("".to_string())],
+                vec![Some("2".to_string()), None],
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_quotes_in_values_are_escaped(pool: PgPool) {
+        let table_in = BTableIn {
+            table_name: String::from("customers"),
+            columns: vec![
+                BColumn {
+                    name: String::from("code"),
+                    datatype: BDataType::TEXT,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("name"),
+                    datatype: BDataType::TEXT,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let name = column_names[1].clone();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("o'brien".to_string()), Some("O'Brien".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+
+        // the primary key with a quote is used in the WHERE clause of the update
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                name,
+                Some("Pat O'Brien".to_string()),
+            );
+        })
+        .await;
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![vec![
+                Some("o'brien".to_string()),
+                Some("Pat O'Brien".to_string())
+            ]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_table_data_page_without_primary_key(pool: PgPool) {
+        let (repository_result, console_result) =
+            create_repository_table_and_console(pool.clone(), &default_table_in()).await;
+        sqlx::raw_sql(
+            "CREATE TABLE notes (body TEXT);
+            INSERT INTO notes VALUES ('c'), ('a'), ('b');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::<BTableGeneral>::new()));
+        set_tables_general_info(repository_result.clone(), tables_general_info.clone()).await;
+        let table_data = TableData::new(repository_result, console_result, tables_general_info);
+
+        // the rows are ordered by every column so the pages neither repeat nor skip rows
+        let mut rows = vec![];
+        for number in 0..2 {
+            table_data
+                .set_table_data(String::from("notes"), BPage { size: 2, number })
+                .await;
+            rows.extend(
+                table_data
+                    .table_inserted_data
+                    .lock()
+                    .await
+                    .as_ref()
+                    .unwrap()
+                    .rows
+                    .clone(),
+            );
+        }
+        assert_eq!(
+            rows,
+            vec![
+                vec![Some("a".to_string())],
+                vec![Some("b".to_string())],
+                vec![Some("c".to_string())]
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_timestamp_values(pool: PgPool) {
+        let table_in = BTableIn {
+            table_name: String::from("events"),
+            columns: vec![
+                BColumn {
+                    name: String::from("happened_at"),
+                    datatype: BDataType::TIMESTAMP,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("ends_at"),
+                    datatype: BDataType::TIMESTAMP,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            assert!(copied_table_data.add_insert_row_event(vec![
+                Some("2024-01-31 10:00:00".to_string()),
+                Some("2024-01-31".to_string()),
+            ]));
+        })
+        .await
+        .unwrap();
+        table_data.update_table_data().await;
+
+        // the timestamp primary key is used in the WHERE clause of the update
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                String::from("ends_at"),
+                Some("2024-02-29T18:30:00".to_string()),
+            );
+        })
+        .await
+        .unwrap();
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![vec![
+                Some("2024-01-31 10:00:00".to_string()),
+                Some("2024-02-29 18:30:00".to_string())
+            ]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_table_data_page(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let name = column_names[1].clone();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob", "Charlie"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let second_page = BPage { size: 2, number: 1 };
+        table_data
+            .set_table_data(table_in.table_name.clone(), second_page.clone())
+            .await;
+
+        // the first row of the second page is the third row of the table
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                name,
+                Some("Daniel".to_string()),
+            );
+        })
+        .await;
+        table_data.update_table_data().await;
+
+        let expected_table_inserted_data = BTableInsertedData {
+            table_name: table_in.table_name.clone(),
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            read_only_columns: vec![false, false],
+            rows: vec![vec![Some("3".to_string()), Some("Daniel".to_string())]],
+            page: second_page,
+            total_row_count: 3,
+        };
+        assert_eq!(
+            *table_data.table_inserted_data.lock().await,
+            Some(expected_table_inserted_data)
+        );
+
+        // a page past the end is loaded as the last page
+        table_data
+            .set_table_data(table_in.table_name.clone(), BPage { size: 2, number: 5 })
+            .await;
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        let table_inserted_data = locked_table_inserted_data.as_ref().unwrap();
+        assert_eq!(table_inserted_data.page, BPage { size: 2, number: 1 });
+        assert_eq!(table_inserted_data.rows.len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_table_data_order_by(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let name = column_names[1].clone();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Bob", "Alice", "Charlie"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        table_data
+            .set_order_by(Some((name.clone(), BSortDirection::Descending)))
+            .await;
+        table_data
+            .set_table_data(table_in.table_name.clone(), BPage::default())
+            .await;
+
+        // the first displayed row is now Charlie so the modify event must target id 3
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                name,
+                Some("Daniel".to_string()),
+            );
+        })
+        .await;
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![
+                vec![Some("3".to_string()), Some("Daniel".to_string())],
+                vec![Some("1".to_string()), Some("Bob".to_string())],
+                vec![Some("2".to_string()), Some("Alice".to_string())],
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_table_data_filters(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob", "Anna"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        table_data
+            .set_filters(vec![
+                BCondition {
+                    column_name: column_names[0].clone(),
+                    data_type: data_types[0].clone(),
+                    operator: BOperator::GreaterThan,
+                    value: "1".to_string(),
+                },
+                BCondition {
+                    column_name: column_names[1].clone(),
+                    data_type: data_types[1].clone(),
+                    operator: BOperator::Like,
+                    value: "A%".to_string(),
+                },
+            ])
+            .await;
+        table_data
+            .set_table_data(table_in.table_name.clone(), BPage::default())
+            .await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        let table_inserted_data = locked_table_inserted_data.as_ref().unwrap();
+        assert_eq!(
+            table_inserted_data.rows,
+            vec![vec![Some("3".to_string()), Some("Anna".to_string())]]
+        );
+        assert_eq!(table_inserted_data.total_row_count, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_look_up_primary_key(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob", "Anna"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let primary_key_column_names =
+            task::spawn_blocking(move || copied_table_data.get_primary_key_column_names())
+                .await
+                .unwrap();
+        assert_eq!(primary_key_column_names, vec![column_names[0].clone()]);
+
+        table_data.look_up_primary_key(vec!["2".to_string()]).await;
+        assert_eq!(
+            table_data
+                .table_inserted_data
+                .lock()
+                .await
+                .as_ref()
+                .unwrap()
+                .rows,
+            vec![vec![Some("2".to_string()), Some("Bob".to_string())]]
+        );
+        assert_eq!(table_data.filters.lock().await.len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_undo_redo_events(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let name = column_names[1].clone();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let (undone_rows, redone_rows) = task::spawn_blocking(move || {
+            copied_table_data.add_delete_row_event(0);
+            // Bob moved to index 0 after the delete
+            copied_table_data.add_modify_row_column_value_event(0, name, Some("Liam".to_string()));
+            copied_table_data.undo_last_event();
+            copied_table_data.undo_last_event();
+            let undone_rows = copied_table_data.get_displayed_rows();
+            copied_table_data.redo_last_event();
+            let redone_rows = copied_table_data.get_displayed_rows();
+            (undone_rows, redone_rows)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            undone_rows,
+            vec![
+                vec![Some("1".to_string()), Some("Alice".to_string())],
+                vec![Some("2".to_string()), Some("Bob".to_string())],
+            ]
+        );
+        assert_eq!(
+            redone_rows,
+            vec![vec![Some("2".to_string()), Some("Bob".to_string())]]
+        );
+
+        // the redone delete still targets Alice
+        table_data.update_table_data().await;
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![vec![Some("2".to_string()), Some("Bob".to_string())]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_delete_rows(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob", "Charlie", "Jacob"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let (undone_rows, displayed_rows) = task::spawn_blocking(move || {
+            copied_table_data
+                .add_insert_row_event(vec![Some("5".to_string()), Some("Liam".to_string())]);
+            // the queued insert is row 4
+            copied_table_data.add_delete_rows_event(&[0, 4, 2]);
+            copied_table_data.undo_last_event();
+            let undone_rows = copied_table_data.get_displayed_rows();
+            copied_table_data.redo_last_event();
+            (undone_rows, copied_table_data.get_displayed_rows())
+        })
+        .await
+        .unwrap();
+
+        // the deletes are undone as one change
+        assert_eq!(undone_rows.len(), 5);
+        assert_eq!(
+            displayed_rows,
+            vec![
+                vec![Some("2".to_string()), Some("Bob".to_string())],
+                vec![Some("4".to_string()), Some("Jacob".to_string())],
+            ]
+        );
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![
+                vec![Some("2".to_string()), Some("Bob".to_string())],
+                vec![Some("4".to_string()), Some("Jacob".to_string())],
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_preview_sql(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("1".to_string()), Some("Alice".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_delete_row_event(0);
+            copied_table_data
+                .add_insert_row_event(vec![Some("2".to_string()), Some("Bob".to_string())]);
+        })
+        .await;
+
+        assert_eq!(
+            table_data.preview_sql().await,
+            vec![
+                String::from("DELETE FROM \"public\".\"users\" WHERE id = 1"),
+                String::from("INSERT INTO \"public\".\"users\" (id, name) VALUES (2, 'Bob')"),
+            ]
+        );
+        // previewing doesn't consume the queued events
+        assert_eq!(table_data.table_data_change_events.lock().await.len(), 2);
+    }
+
+    #[sqlx::test]
+    async fn test_change_summary(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let change_summary = task::spawn_blocking(move || {
+            assert!(copied_table_data.change_summary().is_empty());
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                String::from("name"),
+                Some("Carol".to_string()),
+            );
+            copied_table_data.add_delete_row_event(1);
+            copied_table_data
+                .add_insert_row_event(vec![Some("3".to_string()), Some("Dave".to_string())]);
+            copied_table_data
+                .add_insert_row_event(vec![Some("4".to_string()), Some("Erin".to_string())]);
+            copied_table_data.change_summary()
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            change_summary,
+            BChangeSummary {
+                inserts: 2,
+                updates: 1,
+                deletes: 1,
+            }
+        );
+        assert_eq!(change_summary.to_string(), "2 inserts, 1 update, 1 delete");
+    }
+
+    #[sqlx::test]
+    async fn test_pending_diff(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events: Vec<BTableDataChangeEvents> = ["Alice", "Bob"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some((index + 1).to_string()), Some(name.to_string())],
+                })
+            })
+            .collect();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let pending_diff = task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                String::from("name"),
+                Some("Carol".to_string()),
+            );
+            copied_table_data.add_delete_row_event(1);
+            copied_table_data
+                .add_insert_row_event(vec![Some("3".to_string()), Some("Dave".to_string())]);
+            copied_table_data.pending_diff()
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            pending_diff,
+            vec![
+                BRowDiff::Modified {
+                    primary_key: vec![(String::from("id"), String::from("1"))],
+                    changed_cells: vec![(
+                        String::from("name"),
+                        Some("Alice".to_string()),
+                        Some("Carol".to_string())
+                    )],
+                },
+                BRowDiff::Deleted(vec![
+                    (String::from("id"), Some("2".to_string())),
+                    (String::from("name"), Some("Bob".to_string())),
+                ]),
+                BRowDiff::Inserted(vec![
+                    (String::from("id"), Some("3".to_string())),
+                    (String::from("name"), Some("Dave".to_string())),
+                ]),
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_find_duplicates(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events: Vec<BTableDataChangeEvents> = [
+            (1, Some("Alice")),
+            (2, Some("Bob")),
+            (3, Some("Alice")),
+            (4, None),
+            (5, None),
+            (6, Some("Alice")),
+        ]
+        .into_iter()
+        .map(|(id, name)| {
+            BTableDataChangeEvents::InsertRow(BRowInsertData {
+                column_names: column_names.clone(),
+                data_types: data_types.clone(),
+                values: vec![Some(id.to_string()), name.map(String::from)],
+            })
+        })
+        .collect();
+        let table_data = create_table_data(pool, &table_in, &insert_row_events).await;
+
+        assert_eq!(
+            table_data.find_duplicates(vec![String::from("name")]).await,
+            vec![(vec![Some(String::from("Alice"))], 3), (vec![None], 2),]
+        );
+        // the primary key never repeats
+        assert!(table_data
+            .find_duplicates(vec![String::from("id"), String::from("name")])
+            .await
+            .is_empty());
+        assert!(table_data.find_duplicates(vec![]).await.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_insert_rows_are_batched(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("1".to_string()), Some("Alice".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        table_data
+            .table_data_change_events
+            .lock()
+            .await
+            .extend((2..=1001).map(|id| {
+                BTableDataChangeEvents::InsertRow(BRowInsertData {
+                    column_names: column_names.clone(),
+                    data_types: data_types.clone(),
+                    values: vec![Some(id.to_string()), Some(format!("user {}", id))],
+                })
+            }));
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_delete_row_event(0);
+            copied_table_data
+                .add_insert_row_event(vec![Some("1002".to_string()), Some("Bob".to_string())]);
+        })
+        .await;
+
+        let queries = table_data.preview_sql().await;
+        // two batches of inserts, the delete, then the insert queued after it
+        assert_eq!(queries.len(), 4);
+        assert_eq!(
+            queries[2],
+            String::from("DELETE FROM \"public\".\"users\" WHERE id = 1")
+        );
+        assert_eq!(
+            queries[3],
+            String::from("INSERT INTO \"public\".\"users\" (id, name) VALUES (1002, 'Bob')")
+        );
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().total_row_count,
+            1001
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_duplicate_row(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("1".to_string()), Some("Alice".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let (duplicated, missing_row_duplicated) = task::spawn_blocking(move || {
+            (
+                copied_table_data.duplicate_row(0),
+                copied_table_data.duplicate_row(5),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(duplicated);
+        assert!(!missing_row_duplicated);
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![
+                vec![Some("1".to_string()), Some("Alice".to_string())],
+                vec![Some("2".to_string()), Some("Alice".to_string())],
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_add_partial_insert_row_event(pool: PgPool) {
+        let mut table_in = default_table_in();
+        table_in.columns.push(BColumn {
+            name: String::from("status"),
+            datatype: BDataType::TEXT,
+            constraints: vec![],
+            default_value: Some(String::from("active")),
+            comment: None,
+            generated: None,
+        });
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+        let copied_table_data = table_data.clone();
+        let (inserted, unknown_column_inserted) = task::spawn_blocking(move || {
+            (
+                copied_table_data.add_partial_insert_row_event(HashMap::from([
+                    (String::from("id"), String::from("1")),
+                    (String::from("name"), String::from("Alice")),
+                ])),
+                copied_table_data.add_partial_insert_row_event(HashMap::from([(
+                    String::from("email"),
+                    String::from("alice@example.com"),
+                )])),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(inserted);
+        assert!(!unknown_column_inserted);
+        table_data.update_table_data().await;
+
+        // the column that wasn't given takes its default
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![vec![
+                Some("1".to_string()),
+                Some("Alice".to_string()),
+                Some("active".to_string()),
+            ]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_cell_value(pool: PgPool) {
+        let table_data = Arc::new(create_table_data(pool, &default_table_in(), &vec![]).await);
+        let copied_table_data = table_data.clone();
+        let (valid_id, invalid_id, name) = task::spawn_blocking(move || {
+            (
+                copied_table_data.validate_cell_value(0, "42"),
+                copied_table_data.validate_cell_value(0, "forty"),
+                copied_table_data.validate_cell_value(1, "forty"),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(valid_id.is_ok());
+        assert!(invalid_id.is_err());
+        assert!(name.is_ok());
+        // nothing is queued by checking a value
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_insert_row_generates_uuid_primary_key(pool: PgPool) {
+        let table_in = BTableIn {
+            table_name: String::from("sessions"),
+            columns: vec![
+                BColumn {
+                    name: String::from("id"),
+                    datatype: BDataType::UUID,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("name"),
+                    datatype: BDataType::TEXT,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        // the NULL id is left out so the database generates it
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: vec!["id".to_string(), "name".to_string()],
+            data_types: vec![BDataType::UUID, BDataType::TEXT],
+            values: vec![None, Some("Alice".to_string())],
+        })];
+        let table_data = create_table_data(pool, &table_in, &insert_row_events).await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        let rows = &locked_table_inserted_data.as_ref().unwrap().rows;
+        assert_eq!(rows.len(), 1);
+        let id = rows[0][0].as_deref().unwrap();
+        assert_eq!(id.len(), 36);
+        assert!(validate_value(&BDataType::UUID, id).is_ok());
+        assert_eq!(rows[0][1], Some("Alice".to_string()));
+    }
+
+    #[sqlx::test]
+    async fn test_generated_columns_are_not_inserted_or_edited(pool: PgPool) {
+        let table_in = BTableIn {
+            table_name: String::from("orders"),
+            columns: vec![
+                BColumn {
+                    name: String::from("id"),
+                    datatype: BDataType::SERIAL,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("name"),
+                    datatype: BDataType::TEXT,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+        let copied_table_data = table_data.clone();
+        let id_edited = task::spawn_blocking(move || {
+            copied_table_data
+                .add_insert_row_event(vec![Some("99".to_string()), Some("Alice".to_string())]);
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                "id".to_string(),
+                Some("5".to_string()),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(!id_edited);
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![vec![Some("1".to_string()), Some("Alice".to_string())]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_identity_and_computed_columns_are_read_only(pool: PgPool) {
+        let (repository_result, console_result) =
+            create_repository_table_and_console(pool.clone(), &default_table_in()).await;
+        sqlx::raw_sql(
+            "CREATE TABLE invoices (
+                id INTEGER GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+                amount INTEGER,
+                doubled_amount INTEGER GENERATED ALWAYS AS (amount * 2) STORED
+            );",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::<BTableGeneral>::new()));
+        set_tables_general_info(repository_result.clone(), tables_general_info.clone()).await;
+        let table_data = Arc::new(TableData::new(
+            repository_result,
+            console_result,
+            tables_general_info,
+        ));
+        table_data
+            .set_table_data(String::from("invoices"), BPage::default())
+            .await;
+        assert_eq!(
+            table_data
+                .table_inserted_data
+                .lock()
+                .await
+                .as_ref()
+                .unwrap()
+                .read_only_columns,
+            vec![true, false, true]
+        );
+
+        let copied_table_data = table_data.clone();
+        let doubled_amount_edited = task::spawn_blocking(move || {
+            copied_table_data.add_insert_row_event(vec![
+                Some("7".to_string()),
+                Some("10".to_string()),
+                Some("99".to_string()),
+            ]);
+            copied_table_data.add_modify_row_column_value_event(
+                0,
+                "doubled_amount".to_string(),
+                Some("5".to_string()),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(!doubled_amount_edited);
+        table_data.update_table_data().await;
+
+        let locked_table_inserted_data = table_data.table_inserted_data.lock().await;
+        assert_eq!(
+            locked_table_inserted_data.as_ref().unwrap().rows,
+            vec![vec![
+                Some("1".to_string()),
+                Some("10".to_string()),
+                Some("20".to_string())
+            ]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_auto_commit(pool: PgPool) {
+        let table_in = default_table_in();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+
+        // batch mode keeps the edit queued
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data
+                .add_insert_row_event(vec![Some("1".to_string()), Some("Alice".to_string())]);
+        })
+        .await
+        .unwrap();
+        table_data.auto_commit_table_data().await;
+        assert_eq!(table_data.table_data_change_events.lock().await.len(), 1);
+
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.discard_changes();
+            copied_table_data.set_auto_commit(true);
+            copied_table_data
+                .add_insert_row_event(vec![Some("1".to_string()), Some("Alice".to_string())]);
+        })
+        .await
+        .unwrap();
+        table_data.auto_commit_table_data().await;
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+        assert_eq!(
+            table_data
+                .table_inserted_data
+                .lock()
+                .await
+                .as_ref()
+                .unwrap()
+                .rows,
+            vec![vec![Some("1".to_string()), Some("Alice".to_string())]]
+        );
+
+        // a duplicate primary key is rejected and not kept for the next edit
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data
+                .add_insert_row_event(vec![Some("1".to_string()), Some("Bob".to_string())]);
+        })
+        .await
+        .unwrap();
+        table_data.auto_commit_table_data().await;
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+        assert_eq!(
+            table_data
+                .table_inserted_data
+                .lock()
+                .await
+                .as_ref()
+                .unwrap()
+                .rows
+                .len(),
+            1
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_teardown_component(pool: PgPool) {
+        let table_in = default_table_in();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data
+                .add_insert_row_event(vec![Some("1".to_string()), Some("Alice".to_string())]);
+        })
+        .await
+        .unwrap();
+
+        table_data.teardown_component().await;
+        assert!(table_data.table_inserted_data.lock().await.is_none());
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+        assert!(table_data.undo_stack.lock().await.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_invalid_values_are_rejected(pool: PgPool) {
+        let table_in = default_table_in();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+        let (row_inserted, value_modified, has_pending_changes) = task::spawn_blocking(move || {
+            let row_inserted = table_data
+                .add_insert_row_event(vec![Some("one".to_string()), Some("Alice".to_string())]);
+            table_data.add_insert_row_event(vec![Some("1".to_string()), Some("Alice".to_string())]);
+            let value_modified = table_data.add_modify_row_column_value_event(
+                0,
+                "id".to_string(),
+                Some("two".to_string()),
+            );
+            table_data.undo_last_event();
+            (
+                row_inserted,
+                value_modified,
+                table_data.has_pending_changes(),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(!row_inserted);
+        assert!(!value_modified);
+        // undoing the valid insert leaves nothing, the invalid events were never queued
+        assert!(!has_pending_changes);
+    }
+
+    #[sqlx::test]
+    async fn test_discard_pending_changes(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("1".to_string()), Some("Alice".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let (had_pending_changes, pending_change_count, has_pending_changes, displayed_rows) =
+            task::spawn_blocking(move || {
+                table_data.add_modify_row_column_value_event(
+                    0,
+                    "name".to_string(),
+                    Some("Bob".to_string()),
+                );
+                table_data.add_delete_row_event(0);
+                let had_pending_changes = table_data.has_pending_changes();
+                let pending_change_count = table_data.pending_change_count();
+                table_data.discard_pending_changes();
+                (
+                    had_pending_changes,
+                    pending_change_count,
+                    table_data.has_pending_changes(),
+                    table_data.get_displayed_rows(),
+                )
+            })
+            .await
+            .unwrap();
+        assert!(had_pending_changes);
+        assert_eq!(pending_change_count, 2);
+        assert!(!has_pending_changes);
+        assert_eq!(
+            displayed_rows,
+            vec![vec![Some("1".to_string()), Some("Alice".to_string())]]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_discard_changes(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events = vec![
+            BTableDataChangeEvents::InsertRow(BRowInsertData {
+                column_names: column_names.clone(),
+                data_types: data_types.clone(),
+                values: vec![Some("1".to_string()), Some("Alice".to_string())],
+            }),
+            BTableDataChangeEvents::InsertRow(BRowInsertData {
+                column_names: column_names.clone(),
+                data_types: data_types.clone(),
+                values: vec![Some("2".to_string()), Some("Bob".to_string())],
+            }),
+        ];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data.add_delete_row_event(0);
+            copied_table_data.add_insert_row_event(vec![None, Some("Carol".to_string())]);
+            copied_table_data.discard_changes();
+        })
+        .await
+        .unwrap();
+
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+        assert_eq!(
+            *table_data.current_to_initial_row_indexes.lock().await,
+            HashMap::from([(0, 0), (1, 1)])
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_failed_update_rolls_back(pool: PgPool) {
+        let table_in = default_table_in();
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("1".to_string()), Some("Alice".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        task::spawn_blocking(move || {
+            copied_table_data
+                .add_insert_row_event(vec![Some("2".to_string()), Some("Bob".to_string())]);
+            // duplicate primary key fails the batch
+            copied_table_data
+                .add_insert_row_event(vec![Some("1".to_string()), Some("Liam".to_string())]);
+        })
+        .await;
+        table_data.update_table_data().await;
+
+        let rows = table_data
+            .repository
+            .get_table_data_rows(
+                &table_in.table_name,
+                &column_names,
+                &vec![],
+                &None,
+                &vec![column_names[0].clone()],
+                &BPage::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![vec![Some("1".to_string()), Some("Alice".to_string())]]
+        );
+        assert_eq!(table_data.table_data_change_events.lock().await.len(), 2);
+    }
+
+    #[sqlx::test]
+    async fn test_import_csv(pool: PgPool) {
+        let table_in = default_table_in();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+        let path = std::env::temp_dir().join(format!("crm_import_{}.csv", std::process::id()));
+        fs::write(&path, "id,name\n1,Alice\nsecond,Bob\n3\n4,\"Smith, Jo\"\n").unwrap();
+
+        let copied_table_data = table_data.clone();
+        let copied_path = path.clone();
+        let (imported_row_count, displayed_rows) = task::spawn_blocking(move || {
+            let imported_row_count = copied_table_data.import_csv(&copied_path);
+            (imported_row_count, copied_table_data.get_displayed_rows())
+        })
+        .await
+        .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // the non integer id and the short row are skipped
+        assert_eq!(imported_row_count, 2);
+        assert_eq!(
+            displayed_rows,
+            vec![
+                vec![Some("1".to_string()), Some("Alice".to_string())],
+                vec![Some("4".to_string()), Some("Smith, Jo".to_string())],
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_import_csv_with_mismatched_header(pool: PgPool) {
+        let table_in = default_table_in();
+        let table_data = Arc::new(create_table_data(pool, &table_in, &vec![]).await);
+        let path = std::env::temp_dir().join(format!("crm_header_{}.csv", std::process::id()));
+        fs::write(&path, "id,email\n1,alice@example.com\n").unwrap();
+
+        let copied_table_data = table_data.clone();
+        let copied_path = path.clone();
+        let imported_row_count =
+            task::spawn_blocking(move || copied_table_data.import_csv(&copied_path))
+                .await
+                .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(imported_row_count, 0);
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_modify_not_null_column_to_null(pool: PgPool) {
+        let mut table_in = default_table_in();
+        table_in.columns[1].constraints.push(BConstraint::NotNull);
+        let (column_names, data_types): (Vec<String>, Vec<BDataType>) = table_in
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.datatype.clone()))
+            .unzip();
+        let name = column_names[1].clone();
+        let insert_row_events = vec![BTableDataChangeEvents::InsertRow(BRowInsertData {
+            column_names: column_names.clone(),
+            data_types: data_types.clone(),
+            values: vec![Some("1".to_string()), Some("Alice".to_string())],
+        })];
+        let table_data = Arc::new(create_table_data(pool, &table_in, &insert_row_events).await);
+        let copied_table_data = table_data.clone();
+        let accepted = task::spawn_blocking(move || {
+            copied_table_data.add_modify_row_column_value_event(0, name, None)
+        })
+        .await
+        .unwrap();
+
+        assert!(!accepted);
+        assert!(table_data.table_data_change_events.lock().await.is_empty());
+    }
 }
@@ -1,9 +1,11 @@
 use crate::components::business_components::component::{
-    repository_module::BRepository, BColumn, BColumnForeignKey, BConstraint, BDataType,
-    BTableChangeEvents, BTableData, BTableGeneral, BTableInsertedData, BusinessComponent,
+    repository_module::BRepository, BColumn, BColumnForeignKey, BColumnStats, BCompositeForeignKey,
+    BConstraint, BDataType, BIndex, BLogLevel, BPage, BTableChangeEvents, BTableData,
+    BTableGeneral, BTableInsertedData, BTableMetadata, BusinessComponent,
 };
 use crate::components::business_components::components::BusinessConsole;
 use crate::components::business_components::tables::utils::set_tables_general_info;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::task;
@@ -13,10 +15,14 @@ pub struct TableInfo {
     repository: Arc<BRepository>,
     pub table_name: Arc<AsyncMutex<Option<String>>>,
     pub columns_info: Arc<AsyncMutex<Vec<BColumn>>>,
+    pub composite_foreign_keys: Arc<AsyncMutex<Vec<BCompositeForeignKey>>>,
+    pub indexes: Arc<AsyncMutex<Vec<BIndex>>>,
+    pub metadata: Arc<AsyncMutex<Option<BTableMetadata>>>,
     pub tables_general_info: Arc<AsyncMutex<Vec<BTableGeneral>>>,
     table_change_events: Arc<AsyncMutex<Vec<BTableChangeEvents>>>,
     console: Arc<BusinessConsole>,
-    table_data: Arc<BTableData>,
+    // the open data of a table is reloaded once its columns change
+    open_table_data: Arc<AsyncMutex<HashMap<String, Arc<BTableData>>>>,
 }
 
 impl TableInfo {
@@ -24,16 +30,28 @@ impl TableInfo {
         repository: Arc<BRepository>,
         console: Arc<BusinessConsole>,
         tables_general_info: Arc<AsyncMutex<Vec<BTableGeneral>>>,
-        table_data: Arc<BTableData>,
+        open_table_data: Arc<AsyncMutex<HashMap<String, Arc<BTableData>>>>,
     ) -> Self {
         Self {
             repository,
             table_name: Arc::new(AsyncMutex::new(None)),
             columns_info: Arc::new(AsyncMutex::new(vec![])),
+            composite_foreign_keys: Arc::new(AsyncMutex::new(vec![])),
+            indexes: Arc::new(AsyncMutex::new(vec![])),
+            metadata: Arc::new(AsyncMutex::new(None)),
             table_change_events: Arc::new(AsyncMutex::new(vec![])),
             console,
             tables_general_info,
-            table_data,
+            open_table_data,
+        }
+    }
+
+    async fn reload_open_table_data(&self, table_name: String) {
+        let table_data = self.open_table_data.lock().await.get(&table_name).cloned();
+        if let Some(table_data) = table_data {
+            table_data
+                .set_table_data(table_name, BPage::default())
+                .await;
         }
     }
 
@@ -46,6 +64,12 @@ impl TableInfo {
         *locked_table_name = None;
         let mut columns_info = self.columns_info.blocking_lock();
         *columns_info = vec![];
+        let mut composite_foreign_keys = self.composite_foreign_keys.blocking_lock();
+        *composite_foreign_keys = vec![];
+        let mut indexes = self.indexes.blocking_lock();
+        *indexes = vec![];
+        let mut metadata = self.metadata.blocking_lock();
+        *metadata = None;
         let mut table_change_events = self.table_change_events.blocking_lock();
         *table_change_events = vec![];
     }
@@ -59,19 +83,173 @@ impl TableInfo {
 
             console.clear_messages()
         });
-        let columns_info = self.repository.get_columns_info(&table_name).await.unwrap();
+        let info_result = match self.repository.get_columns_info(&table_name).await {
+            Ok(columns_info) => self
+                .repository
+                .get_composite_foreign_keys_info(&table_name)
+                .await
+                .map(|composite_foreign_keys_info| (columns_info, composite_foreign_keys_info)),
+            Err(error) => Err(error),
+        };
+        let (columns_info, composite_foreign_keys_info) = match info_result {
+            Ok(info) => info,
+            Err(error) => {
+                let console = self.console.clone();
+                task::spawn_blocking(move || {
+                    console.write(
+                        BLogLevel::Error,
+                        format!("Could not load table info: {}", error),
+                    );
+                })
+                .await;
+                return;
+            }
+        };
+        let composite_foreign_keys: Vec<BCompositeForeignKey> = composite_foreign_keys_info
+            .into_iter()
+            .map(|composite_foreign_key_info| {
+                BCompositeForeignKey::to_composite_foreign_key(composite_foreign_key_info)
+            })
+            .collect();
         let columns_info_with_enums = columns_info
             .into_iter()
             .map(|column_info| BColumn::to_column(column_info))
+            .map(|mut column| {
+                // composite foreign keys are table level so they are not per column constraints
+                column.constraints.retain(|constraint| {
+                    if let BConstraint::ForeignKey { table, .. } = constraint {
+                        !composite_foreign_keys.iter().any(|composite_foreign_key| {
+                            composite_foreign_key.referenced_table == *table
+                                && composite_foreign_key.column_names.contains(&column.name)
+                        })
+                    } else {
+                        true
+                    }
+                });
+                column
+            })
             .collect();
 
         // Lock the async mutex and update the columns_info
         let mut locked_columns_info = self.columns_info.lock().await;
         *locked_columns_info = columns_info_with_enums;
+        let mut locked_composite_foreign_keys = self.composite_foreign_keys.lock().await;
+        *locked_composite_foreign_keys = composite_foreign_keys;
+        self.set_indexes(&table_name).await;
+        self.set_metadata(&table_name).await;
         let mut locked_table_name = self.table_name.lock().await;
         *locked_table_name = Some(table_name);
     }
 
+    async fn set_indexes(&self, table_name: &str) {
+        // the repository logs the failed query, the previously loaded indexes are kept
+        if let Ok(indexes) = self.repository.get_indexes(table_name).await {
+            *self.indexes.lock().await = indexes;
+        }
+    }
+
+    async fn set_metadata(&self, table_name: &str) {
+        // a table without metadata shows no overview rather than a stale one
+        *self.metadata.lock().await = self.repository.get_table_metadata(table_name).await.ok();
+    }
+
+    pub async fn add_index(&self, column_names: Vec<String>, unique: bool) {
+        let Some(table_name) = self.table_name.lock().await.clone() else {
+            return;
+        };
+        if column_names.is_empty() {
+            let console = self.console.clone();
+            task::spawn_blocking(move || {
+                console.write(
+                    BLogLevel::Warning,
+                    String::from("Could not create index: no columns selected"),
+                );
+            })
+            .await;
+            return;
+        }
+        // a failed statement is written to the database console by the repository
+        if self
+            .repository
+            .create_index(&table_name, &column_names, unique)
+            .await
+            .is_ok()
+        {
+            self.set_indexes(&table_name).await;
+        }
+    }
+
+    pub async fn drop_index(&self, index_name: String) {
+        let Some(table_name) = self.table_name.lock().await.clone() else {
+            return;
+        };
+        if self.repository.drop_index(&index_name).await.is_ok() {
+            self.set_indexes(&table_name).await;
+        }
+    }
+
+    // the repository logs a failed query
+    pub async fn get_column_stats(&self, column_name: String) -> Option<BColumnStats> {
+        let table_name = self.table_name.lock().await.clone()?;
+        self.repository
+            .get_column_stats(&table_name, &column_name)
+            .await
+            .ok()
+    }
+
+    // the CREATE TABLE statement of the shown table, None when it could not be built
+    pub async fn get_table_schema(&self) -> Option<String> {
+        let table_name = self.table_name.lock().await.clone()?;
+        match self.repository.export_table_schema(&table_name).await {
+            Ok(table_schema) => Some(table_schema),
+            Err(error) => {
+                let console = self.console.clone();
+                task::spawn_blocking(move || {
+                    console.write(
+                        BLogLevel::Error,
+                        format!(
+                            "Could not build the schema of table {}: {}",
+                            table_name, error
+                        ),
+                    );
+                })
+                .await;
+                None
+            }
+        }
+    }
+
+    // applied right away instead of with the table update, returns false when the database
+    // rejects the conversion so it can be retried with a USING expression
+    pub async fn change_column_type(
+        &self,
+        column_name: String,
+        data_type: BDataType,
+        using_expression: Option<String>,
+    ) -> bool {
+        let Some(table_name) = self.table_name.lock().await.clone() else {
+            return false;
+        };
+        // the rejected statement is written to the database console by the repository
+        if self
+            .repository
+            .alter_column_type(
+                &table_name,
+                &column_name,
+                &data_type,
+                using_expression.as_deref(),
+            )
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        self.set_table_info(table_name.clone()).await;
+        set_tables_general_info(self.repository.clone(), self.tables_general_info.clone()).await;
+        self.reload_open_table_data(table_name).await;
+        true
+    }
+
     pub fn add_table_change_event(&self, table_change_event: BTableChangeEvents) {
         let mut locked_table_change_events = self.table_change_events.blocking_lock();
 
@@ -114,7 +292,7 @@ impl TableInfo {
         }
 
         self.console
-            .write(format!("{:?}", *locked_table_change_events));
+            .write_info(format!("{:?}", *locked_table_change_events));
     }
 
     fn handle_change_table_name(
@@ -555,7 +733,8 @@ impl TableInfo {
             .count()
     }
 
-    async fn alter_table(&self) {
+    // false in a dry run, the statements are only logged so the changes stay pending
+    async fn alter_table(&self) -> bool {
         let mut locked_table_change_events = self.table_change_events.lock().await;
         let mut locked_table_name = self.table_name.lock().await;
 
@@ -575,6 +754,9 @@ impl TableInfo {
                 )
                 .await;
             println!("Alter table result: {:?}", res);
+            if self.repository.dry_run() {
+                return false;
+            }
         }
 
         for event in locked_table_change_events.iter() {
@@ -585,6 +767,7 @@ impl TableInfo {
 
         // Clear events
         locked_table_change_events.clear();
+        true
     }
 
     pub fn at_least_one_primary_key(&self) -> bool {
@@ -609,7 +792,8 @@ impl TableInfo {
         (primary_key_column_names.len() + add_primary_key_events_count) > remove_primary_key_count
     }
 
-    pub async fn update_table(&self) {
+    // false when nothing was applied and the table info was left as it is
+    pub async fn update_table(&self) -> bool {
         let table_info = self.clone();
         task::spawn_blocking(move || {
             let at_least_one_primary_key = table_info.at_least_one_primary_key();
@@ -623,15 +807,15 @@ impl TableInfo {
             }
         })
         .await;
-        self.alter_table().await;
+        if !self.alter_table().await {
+            return false;
+        }
         let current_table_name = { self.table_name.lock().await.as_ref().unwrap().clone() };
 
-        self.set_table_info(current_table_name).await;
+        self.set_table_info(current_table_name.clone()).await;
         set_tables_general_info(self.repository.clone(), self.tables_general_info.clone()).await;
-        let locked_table_name = self.table_name.lock().await;
-        self.table_data
-            .set_table_data(locked_table_name.as_ref().unwrap().clone())
-            .await;
+        self.reload_open_table_data(current_table_name).await;
+        true
     }
 }
 
@@ -639,7 +823,7 @@ impl TableInfo {
 mod tests {
     use super::*;
     use crate::components::business_components::component::{
-        repository_module::BRepositoryConsole, BTableIn,
+        repository_module::BRepositoryConsole, BReferentialAction, BTableIn,
     };
     use crate::components::business_components::tables::test_utils::{
         create_btable_general, create_repository_table_and_console, default_table_in, sort_columns,
@@ -654,16 +838,11 @@ mod tests {
     ) -> TableInfo {
         let (repository_result, console_result) =
             create_repository_table_and_console(pool, table_in).await;
-        let table_data = Arc::new(BTableData::new(
-            repository_result.clone(),
-            console_result.clone(),
-            Arc::new(AsyncMutex::new(Vec::new())),
-        ));
         let table_info = TableInfo::new(
             repository_result.clone(),
             console_result,
             tables_general_info.clone(),
-            table_data,
+            Arc::new(AsyncMutex::new(HashMap::new())),
         );
         table_info.set_table_info(table_in.table_name.clone()).await;
         set_tables_general_info(repository_result, tables_general_info).await; // Initialize tables_general_info
@@ -688,6 +867,299 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_column_comment(pool: PgPool) {
+        let mut table_in = default_table_in();
+        table_in.columns[1].comment = Some(String::from("the user's full name"));
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+
+        let table_info = create_table_info(pool, &table_in, tables_general_info).await;
+
+        let name_column = table_info
+            .columns_info
+            .lock()
+            .await
+            .iter()
+            .find(|column| column.name == "name")
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            name_column.comment,
+            Some(String::from("the user's full name"))
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_add_and_drop_index(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+        let table_info = create_table_info(pool, &table_in, tables_general_info).await;
+
+        table_info
+            .add_index(vec![String::from("name"), String::from("id")], true)
+            .await;
+        let index = table_info
+            .indexes
+            .lock()
+            .await
+            .iter()
+            .find(|index| index.name == "idx_users_name_id")
+            .cloned()
+            .unwrap();
+        assert!(index
+            .definition
+            .starts_with("CREATE UNIQUE INDEX idx_users_name_id ON public.users"));
+        assert!(index.definition.ends_with("(name, id)"));
+        assert!(!index.is_constraint);
+        // the primary key index can only be dropped with its constraint
+        assert!(table_info
+            .indexes
+            .lock()
+            .await
+            .iter()
+            .any(|index| index.name == "users_pkey" && index.is_constraint));
+
+        table_info.drop_index(index.name).await;
+        assert!(!table_info
+            .indexes
+            .lock()
+            .await
+            .iter()
+            .any(|index| index.name == "idx_users_name_id"));
+    }
+
+    #[sqlx::test]
+    async fn test_change_column_type(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+        let table_info = create_table_info(pool, &table_in, tables_general_info.clone()).await;
+
+        // text can't be cast to integer implicitly
+        assert!(
+            !table_info
+                .change_column_type(String::from("name"), BDataType::INTEGER, None)
+                .await
+        );
+        assert!(
+            table_info
+                .change_column_type(
+                    String::from("name"),
+                    BDataType::INTEGER,
+                    Some(String::from("\"name\"::INTEGER")),
+                )
+                .await
+        );
+
+        let name_column = table_info
+            .columns_info
+            .lock()
+            .await
+            .iter()
+            .find(|column| column.name == "name")
+            .cloned()
+            .unwrap();
+        assert_eq!(name_column.datatype, BDataType::INTEGER);
+        let locked_tables_general_info = tables_general_info.lock().await;
+        let users_table = locked_tables_general_info
+            .iter()
+            .find(|table| table.table_name == table_in.table_name)
+            .unwrap();
+        // the tables list picks up the new type too
+        assert!(!users_table.data_types.contains(&BDataType::TEXT));
+    }
+
+    #[sqlx::test]
+    async fn test_change_column_type_to_serial(pool: PgPool) {
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+        let table_in = default_table_in();
+        let table_info = create_table_info(pool, &table_in, tables_general_info).await;
+
+        let table_info_copy = table_info.clone();
+        task::spawn_blocking(move || {
+            table_info_copy.add_table_change_event(BTableChangeEvents::ChangeColumnDataType(
+                String::from("id"),
+                BDataType::SERIAL,
+            ));
+        })
+        .await;
+        table_info.update_table().await;
+
+        // the id column takes its default from a sequence like a column created as serial
+        let id_column = table_info
+            .columns_info
+            .lock()
+            .await
+            .iter()
+            .find(|column| column.name == "id")
+            .cloned()
+            .unwrap();
+        assert!(id_column.is_serial());
+        assert!(table_info.table_change_events.lock().await.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_table_metadata(pool: PgPool) {
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+        let table_in = default_table_in();
+        let table_info = create_table_info(pool, &table_in, tables_general_info).await;
+
+        let orders_table = BTableIn {
+            table_name: String::from("orders"),
+            columns: vec![
+                BColumn {
+                    name: String::from("id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("user_id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::ForeignKey {
+                        table: table_in.table_name.clone(),
+                        column: String::from("id"),
+                        on_delete: BReferentialAction::NoAction,
+                    }],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("total"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        table_info
+            .repository
+            .create_table(&orders_table)
+            .await
+            .unwrap();
+        table_info
+            .set_table_info(orders_table.table_name.clone())
+            .await;
+
+        let metadata = table_info.metadata.lock().await.clone().unwrap();
+        assert_eq!(metadata.column_count, 3);
+        assert_eq!(metadata.primary_key_column_names, vec![String::from("id")]);
+        assert_eq!(
+            metadata.foreign_keys,
+            vec![BCompositeForeignKey {
+                column_names: vec![String::from("user_id")],
+                referenced_table: table_in.table_name.clone(),
+                referenced_column_names: vec![String::from("id")],
+            }]
+        );
+        // the primary key index takes up space even while the table is empty
+        assert!(metadata.total_size_bytes.unwrap() > 0);
+    }
+
+    #[sqlx::test]
+    async fn test_get_column_stats(pool: PgPool) {
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+        let table_in = default_table_in();
+        let table_info = create_table_info(pool.clone(), &table_in, tables_general_info).await;
+        table_info.set_table_info(table_in.table_name.clone()).await;
+        sqlx::raw_sql("INSERT INTO users (id, name) VALUES (1, 'Alice'), (2, 'Alice'), (3, NULL);")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            table_info.get_column_stats(String::from("name")).await,
+            Some(BColumnStats {
+                null_count: 1,
+                distinct_count: 1,
+            })
+        );
+        assert_eq!(
+            table_info.get_column_stats(String::from("id")).await,
+            Some(BColumnStats {
+                null_count: 0,
+                distinct_count: 3,
+            })
+        );
+        assert_eq!(
+            table_info.get_column_stats(String::from("missing")).await,
+            None
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_table_schema(pool: PgPool) {
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+        let table_in = default_table_in();
+        let table_info = create_table_info(pool.clone(), &table_in, tables_general_info).await;
+        table_info.set_table_info(table_in.table_name.clone()).await;
+
+        let table_schema = table_info.get_table_schema().await.unwrap();
+        assert!(table_schema.starts_with("CREATE TABLE \"public\".\"users\""));
+
+        // running the statement recreates the same table
+        sqlx::raw_sql("DROP TABLE users;")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(&table_schema).execute(&pool).await.unwrap();
+        assert_eq!(table_info.get_table_schema().await.unwrap(), table_schema);
+    }
+
+    #[sqlx::test]
+    async fn test_foreign_key_on_delete(pool: PgPool) {
+        let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
+
+        let table_in = default_table_in();
+        let table_info = create_table_info(pool, &table_in, tables_general_info).await;
+
+        let orders_table = BTableIn {
+            table_name: String::from("orders"),
+            columns: vec![
+                BColumn {
+                    name: String::from("id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("user_id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::ForeignKey {
+                        table: table_in.table_name.clone(),
+                        column: String::from("id"),
+                        on_delete: BReferentialAction::Cascade,
+                    }],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        table_info
+            .repository
+            .create_table(&orders_table)
+            .await
+            .unwrap();
+        table_info
+            .set_table_info(orders_table.table_name.clone())
+            .await;
+
+        let mut expected_columns = orders_table.columns.clone();
+        sort_columns(&mut expected_columns);
+
+        let columns_info = table_info.columns_info.lock().await;
+        assert_eq!(*columns_info, expected_columns);
+    }
+
     #[sqlx::test]
     async fn test_alter_table(pool: PgPool) {
         let tables_general_info = Arc::new(AsyncMutex::new(Vec::new()));
@@ -698,12 +1170,20 @@ mod tests {
                 name: String::from("id"),
                 datatype: BDataType::INTEGER,
                 constraints: vec![BConstraint::PrimaryKey],
+                default_value: None,
+                comment: None,
+                generated: None,
             }],
+            composite_foreign_keys: vec![],
         };
 
         let table_in = default_table_in();
         let table_info = create_table_info(pool, &table_in, tables_general_info).await;
-        table_info.repository.create_table(&remote_table).await;
+        table_info
+            .repository
+            .create_table(&remote_table)
+            .await
+            .unwrap();
 
         let (remote_table_name, remote_column_name, remote_column_datatype) = (
             remote_table.table_name.clone(),
@@ -757,12 +1237,14 @@ mod tests {
                 column_name: foreign_key_column_name.clone(),
                 referenced_table: remote_table_name.clone(),
                 referenced_column: remote_column_name.clone(),
+                on_delete: BReferentialAction::NoAction,
             }),
             BTableChangeEvents::RemoveForeignKey(foreign_key_column_name.clone()),
             BTableChangeEvents::AddForeignKey(BColumnForeignKey {
                 column_name: foreign_key_column_name.clone(),
                 referenced_table: remote_table_name.clone(),
                 referenced_column: remote_column_name.clone(),
+                on_delete: BReferentialAction::Cascade,
             }),
         ];
 
@@ -781,34 +1263,53 @@ mod tests {
                 name: name_column.name,
                 datatype: BDataType::INTEGER,
                 constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
             BColumn {
                 name: String::from("email"),
                 datatype: BDataType::TEXT,
                 constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
             BColumn {
                 name: String::from("active_status"),
                 datatype: BDataType::BOOLEAN,
                 constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
             BColumn {
                 name: String::from("last_login"),
                 datatype: BDataType::TIMESTAMP,
                 constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
             BColumn {
                 name: String::from("region"),
                 datatype: BDataType::TEXT,
                 constraints: vec![BConstraint::PrimaryKey],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
             BColumn {
                 name: foreign_key_column_name.clone(),
                 datatype: BDataType::INTEGER,
-                constraints: vec![BConstraint::ForeignKey(
-                    remote_table_name.clone(),
-                    remote_column_name.clone(),
-                )],
+                constraints: vec![BConstraint::ForeignKey {
+                    table: remote_table_name.clone(),
+                    column: remote_column_name.clone(),
+                    on_delete: BReferentialAction::Cascade,
+                }],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
         ];
         sort_columns(&mut expected_columns);
@@ -850,9 +1351,98 @@ mod tests {
             name: String::from("id"),
             datatype: BDataType::INTEGER,
             constraints: vec![BConstraint::PrimaryKey],
+            default_value: None,
+            comment: None,
+            generated: None,
         };
         assert!(columns_info
             .iter()
             .any(|column| *column == expected_primary_key_column));
     }
+
+    #[sqlx::test]
+    async fn test_composite_foreign_key(pool: PgPool) {
+        let parent_table = BTableIn {
+            table_name: String::from("orders"),
+            columns: vec![
+                BColumn {
+                    name: String::from("store_id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("order_number"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        let expected_composite_foreign_key = BCompositeForeignKey {
+            column_names: vec![String::from("order_store_id"), String::from("order_number")],
+            referenced_table: parent_table.table_name.clone(),
+            referenced_column_names: vec![String::from("store_id"), String::from("order_number")],
+        };
+        let child_table = BTableIn {
+            table_name: String::from("order_items"),
+            columns: vec![
+                BColumn {
+                    name: String::from("id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("order_store_id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("order_number"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![expected_composite_foreign_key.clone()],
+        };
+
+        let (repository_result, console_result) =
+            create_repository_table_and_console(pool, &parent_table).await;
+        repository_result.create_table(&child_table).await.unwrap();
+        let table_info = TableInfo::new(
+            repository_result,
+            console_result,
+            Arc::new(AsyncMutex::new(Vec::new())),
+            Arc::new(AsyncMutex::new(HashMap::new())),
+        );
+        table_info
+            .set_table_info(child_table.table_name.clone())
+            .await;
+
+        let composite_foreign_keys = table_info.composite_foreign_keys.lock().await;
+        assert_eq!(
+            *composite_foreign_keys,
+            vec![expected_composite_foreign_key]
+        );
+
+        // the composite foreign key is not duplicated as per column constraints
+        let mut expected_columns = child_table.columns.clone();
+        sort_columns(&mut expected_columns);
+        let columns_info = table_info.columns_info.lock().await;
+        assert_eq!(*columns_info, expected_columns);
+    }
 }
@@ -1,10 +1,15 @@
 use crate::components::business_components::component::{
-    repository_module::BRepository, BColumn, BConstraint, BDataType, BTableChangeEvents,
-    BTableData, BTableGeneral, BTableIn, BTableInfo, BTableInsertedData, BusinessComponent,
+    repository_module::BRepository, BColumn, BConstraint, BDataType, BLogLevel, BTableData,
+    BTableGeneral, BTableIn, BTableInfo, BTableInsertedData, BusinessComponent,
 };
 
 use crate::components::business_components::components::BusinessConsole;
 use crate::components::business_components::tables::utils::set_tables_general_info;
+use crate::components::business_components::tables::validation::{
+    get_duplicate_column_indexes, validate_column, validate_identifier, validate_table_in,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::task;
@@ -13,13 +18,20 @@ use tokio::task;
 pub struct Tables {
     repository: Arc<BRepository>,
     pub table_info: Arc<BTableInfo>,
-    pub table_data: Arc<BTableData>,
+    // every open table has its own rows and pending changes, keyed by table name
+    open_table_data: Arc<AsyncMutex<HashMap<String, Arc<BTableData>>>>,
     pub tables_general_info: Arc<AsyncMutex<Vec<BTableGeneral>>>,
+    pub row_counts: Arc<AsyncMutex<HashMap<String, i64>>>,
+    pub schema_names: Arc<AsyncMutex<Vec<String>>>,
     console: Arc<BusinessConsole>,
 }
 
 impl BusinessComponent for Tables {
     async fn initialize_component(&self) {
+        // the repository logs the failed query, the previously loaded schemas are kept
+        if let Ok(schema_names) = self.repository.get_schema_names().await {
+            *self.schema_names.lock().await = schema_names;
+        }
         set_tables_general_info(self.repository.clone(), self.tables_general_info.clone()).await;
     }
 }
@@ -27,27 +39,116 @@ impl BusinessComponent for Tables {
 impl Tables {
     pub fn new(repository: Arc<BRepository>, console: Arc<BusinessConsole>) -> Self {
         let tables_general_info = Arc::new(AsyncMutex::new(vec![]));
-        let table_data = Arc::new(BTableData::new(
-            repository.clone(),
-            console.clone(),
-            tables_general_info.clone(),
-        ));
+        let open_table_data = Arc::new(AsyncMutex::new(HashMap::new()));
 
         Self {
             table_info: Arc::new(BTableInfo::new(
                 repository.clone(),
                 console.clone(),
                 tables_general_info.clone(),
-                table_data.clone(),
+                open_table_data.clone(),
             )),
-            table_data,
+            open_table_data,
             repository,
             tables_general_info,
+            row_counts: Arc::new(AsyncMutex::new(HashMap::new())),
+            schema_names: Arc::new(AsyncMutex::new(vec![])),
             console,
         }
     }
 
-    pub async fn add_table(&self, mut table_in: BTableIn) {
+    pub fn schema(&self) -> String {
+        self.repository.schema()
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.repository.dry_run()
+    }
+
+    pub fn set_dry_run(&self, dry_run: bool) {
+        self.repository.set_dry_run(dry_run);
+        self.console.write_info(if dry_run {
+            String::from("Dry run on, schema changes are only logged")
+        } else {
+            String::from("Dry run off, schema changes are executed")
+        });
+    }
+
+    // the tables of the new schema replace the listed ones
+    pub async fn set_schema(&self, schema: String) {
+        self.repository.set_schema(schema.clone());
+        self.row_counts.lock().await.clear();
+        let closed_table_data: Vec<Arc<BTableData>> = self
+            .open_table_data
+            .lock()
+            .await
+            .drain()
+            .map(|(_, table_data)| table_data)
+            .collect();
+        for table_data in closed_table_data {
+            table_data.teardown_component().await;
+        }
+        let table_info = self.table_info.clone();
+        task::spawn_blocking(move || {
+            table_info.reset_table_info();
+        })
+        .await;
+        self.initialize_component().await;
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write_info(format!("Switched to schema {}", schema));
+        })
+        .await;
+    }
+
+    pub async fn set_row_counts(&self) {
+        // the repository logs the failed query, the previous counts are kept
+        let Ok(row_counts) = self.repository.get_row_counts().await else {
+            return;
+        };
+        *self.row_counts.lock().await = row_counts;
+    }
+
+    // reopening a table returns the instance holding its unsaved changes
+    pub fn open_table_data(&self, table_name: &str) -> Arc<BTableData> {
+        self.open_table_data
+            .blocking_lock()
+            .entry(table_name.to_string())
+            .or_insert_with(|| {
+                Arc::new(BTableData::new(
+                    self.repository.clone(),
+                    self.console.clone(),
+                    self.tables_general_info.clone(),
+                ))
+            })
+            .clone()
+    }
+
+    // the closed instance is returned so it can be torn down without blocking
+    pub fn close_table_data(&self, table_name: &str) -> Option<Arc<BTableData>> {
+        self.open_table_data.blocking_lock().remove(table_name)
+    }
+
+    pub fn validate_table_in(&self, table_in: &BTableIn) -> Result<(), String> {
+        validate_table_in(table_in)
+    }
+
+    pub fn get_duplicate_column_indexes(&self, columns: &Vec<BColumn>) -> Vec<usize> {
+        get_duplicate_column_indexes(columns)
+    }
+
+    pub async fn add_table(&self, mut table_in: BTableIn) -> bool {
+        if let Err(error) = validate_table_in(&table_in) {
+            let console = self.console.clone();
+            task::spawn_blocking(move || {
+                console.write(
+                    BLogLevel::Error,
+                    format!("Could not create table: {}", error),
+                );
+            })
+            .await;
+            return false;
+        }
         // Check if no column has a primary key constraint
         if !table_in.columns.iter().any(|column| {
             column
@@ -60,18 +161,76 @@ impl Tables {
                 name: "id".to_string(),
                 datatype: BDataType::INTEGER,
                 constraints: vec![BConstraint::PrimaryKey],
+                default_value: None,
+                comment: None,
+                generated: None,
             });
         }
 
-        // Create the table and update general info
-        self.repository.create_table(&table_in).await;
+        // Create the table and update general info, errors such as an invalid check
+        // expression come from the database
+        if let Err(error) = self.repository.create_table(&table_in).await {
+            let console = self.console.clone();
+            task::spawn_blocking(move || {
+                console.write(
+                    BLogLevel::Error,
+                    format!("Could not create table: {}", error),
+                );
+            })
+            .await;
+            return false;
+        }
+        // only the statements were logged, the form keeps its input
+        if self.repository.dry_run() {
+            return false;
+        }
         set_tables_general_info(self.repository.clone(), self.tables_general_info.clone()).await;
+        true
+    }
+
+    // the other tables whose foreign keys stop the table from being deleted without cascading
+    pub async fn get_dependent_table_names(&self, table_name: &str) -> Vec<String> {
+        self.repository
+            .get_referencing_table_names(table_name)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|referencing_table_name| referencing_table_name != table_name)
+            .collect()
+    }
+
+    // the repository logs a failed search, which then finds nothing
+    pub async fn search_columns(&self, pattern: String) -> Vec<(String, String)> {
+        if pattern.is_empty() {
+            return vec![];
+        }
+        self.repository
+            .search_columns(&pattern)
+            .await
+            .unwrap_or_default()
     }
 
-    pub async fn delete_table(&self, table_name: String) {
-        self.repository.delete_table(&table_name).await;
+    pub async fn delete_table(&self, table_name: String, cascade: bool) -> bool {
+        if let Err(error) = self.repository.delete_table(&table_name, cascade).await {
+            let console = self.console.clone();
+            task::spawn_blocking(move || {
+                console.write(
+                    BLogLevel::Error,
+                    format!("Could not delete table {}: {}", table_name, error),
+                );
+            })
+            .await;
+            return false;
+        }
+        // only the statement was logged, the table is still there
+        if self.repository.dry_run() {
+            return false;
+        }
+        let closed_table_data = self.open_table_data.lock().await.remove(&table_name);
+        if let Some(table_data) = closed_table_data {
+            table_data.teardown_component().await;
+        }
         let table_info = self.table_info.clone();
-        let table_data = self.table_data.clone();
         task::spawn_blocking(move || {
             let reset_table_info =
                 if let Some(current_table_name) = table_info.table_name.blocking_lock().as_ref() {
@@ -82,31 +241,457 @@ impl Tables {
             if reset_table_info {
                 table_info.reset_table_info();
             }
+        })
+        .await;
+        set_tables_general_info(self.repository.clone(), self.tables_general_info.clone()).await;
+        true
+    }
+
+    // the open table data is refreshed by the ui, loaded row counts are fetched again
+    pub async fn truncate_table(&self, table_name: String, cascade: bool) -> bool {
+        let (level, message, truncated) =
+            if let Err(error) = self.repository.truncate_table(&table_name, cascade).await {
+                (
+                    BLogLevel::Error,
+                    format!("Could not truncate table {}: {}", table_name, error),
+                    false,
+                )
+            } else if self.repository.dry_run() {
+                (
+                    BLogLevel::Info,
+                    format!("Dry run, table {} was not truncated", table_name),
+                    false,
+                )
+            } else {
+                (
+                    BLogLevel::Info,
+                    format!("Truncated table {}", table_name),
+                    true,
+                )
+            };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+        if truncated && !self.row_counts.lock().await.is_empty() {
+            self.set_row_counts().await;
+        }
+        truncated
+    }
+
+    pub async fn add_column(&self, table_name: String, column: BColumn) {
+        let (level, message) = if let Err(error) = validate_column(&column) {
+            (
+                BLogLevel::Error,
+                format!("Could not add column to table {}: {}", table_name, error),
+            )
+        } else if let Err(error) = self.repository.add_column(&table_name, &column).await {
+            (
+                BLogLevel::Error,
+                format!("Could not add column to table {}: {}", table_name, error),
+            )
+        } else if self.repository.dry_run() {
+            (
+                BLogLevel::Info,
+                format!(
+                    "Dry run, column {} was not added to table {}",
+                    column.name, table_name
+                ),
+            )
+        } else {
+            set_tables_general_info(self.repository.clone(), self.tables_general_info.clone())
+                .await;
+            (
+                BLogLevel::Info,
+                format!("Added column {} to table {}", column.name, table_name),
+            )
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+    }
+
+    pub async fn drop_column(&self, table_name: String, column_name: String) {
+        let column_count = self
+            .tables_general_info
+            .lock()
+            .await
+            .iter()
+            .find(|table| table.table_name == table_name)
+            .map(|table| table.column_names.len())
+            .unwrap_or_default();
+        let is_primary_key = self
+            .repository
+            .get_primary_key_column_names(&table_name)
+            .await
+            .unwrap_or_default()
+            .contains(&column_name);
+        let (level, message) = if column_count <= 1 {
+            (
+                BLogLevel::Error,
+                format!(
+                    "Could not drop column {}: it is the last column of table {}",
+                    column_name, table_name
+                ),
+            )
+        } else if is_primary_key {
+            (
+                BLogLevel::Error,
+                format!(
+                    "Could not drop column {}: it is part of the primary key of table {}",
+                    column_name, table_name
+                ),
+            )
+        } else if let Err(error) = self.repository.drop_column(&table_name, &column_name).await {
+            (
+                BLogLevel::Error,
+                format!("Could not drop column {}: {}", column_name, error),
+            )
+        } else if self.repository.dry_run() {
+            (
+                BLogLevel::Info,
+                format!(
+                    "Dry run, column {} was not dropped from table {}",
+                    column_name, table_name
+                ),
+            )
+        } else {
+            set_tables_general_info(self.repository.clone(), self.tables_general_info.clone())
+                .await;
+            (
+                BLogLevel::Info,
+                format!("Dropped column {} from table {}", column_name, table_name),
+            )
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+    }
+
+    // returns whether the table was renamed
+    pub async fn rename_table(&self, old_name: String, new_name: String) -> bool {
+        let table_exists = self
+            .tables_general_info
+            .lock()
+            .await
+            .iter()
+            .any(|table| table.table_name == new_name);
+        let mut renamed = false;
+        let (level, message) = if let Err(error) = validate_identifier(&new_name) {
+            (
+                BLogLevel::Error,
+                format!("Could not rename table {}: {}", old_name, error),
+            )
+        } else if table_exists {
+            (
+                BLogLevel::Error,
+                format!(
+                    "Could not rename table {}: table {} already exists",
+                    old_name, new_name
+                ),
+            )
+        } else if let Err(error) = self.repository.rename_table(&old_name, &new_name).await {
+            (
+                BLogLevel::Error,
+                format!("Could not rename table {}: {}", old_name, error),
+            )
+        } else if self.repository.dry_run() {
+            (
+                BLogLevel::Info,
+                format!("Dry run, table {} was not renamed", old_name),
+            )
+        } else {
+            let table_info = self.table_info.clone();
+            let open_table_data = self.open_table_data.clone();
+            let (current_name, updated_name) = (old_name.clone(), new_name.clone());
+            task::spawn_blocking(move || {
+                let mut locked_table_name = table_info.table_name.blocking_lock();
+                if locked_table_name.as_ref() == Some(&current_name) {
+                    *locked_table_name = Some(updated_name.clone());
+                }
+                // the open data moves to the new name together with its pending changes
+                let mut locked_open_table_data = open_table_data.blocking_lock();
+                if let Some(table_data) = locked_open_table_data.remove(&current_name) {
+                    if let Some(table_inserted_data) =
+                        table_data.table_inserted_data.blocking_lock().as_mut()
+                    {
+                        table_inserted_data.table_name = updated_name.clone();
+                    }
+                    locked_open_table_data.insert(updated_name, table_data);
+                }
+            })
+            .await;
+            set_tables_general_info(self.repository.clone(), self.tables_general_info.clone())
+                .await;
+            renamed = true;
+            (
+                BLogLevel::Info,
+                format!("Renamed table {} to {}", old_name, new_name),
+            )
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+        renamed
+    }
+
+    pub async fn clone_table(
+        &self,
+        source_table_name: String,
+        target_table_name: String,
+        copy_constraints: bool,
+    ) {
+        let table_exists = self
+            .tables_general_info
+            .lock()
+            .await
+            .iter()
+            .any(|table| table.table_name == target_table_name);
+        let (level, message) = if table_exists {
+            (
+                BLogLevel::Error,
+                format!(
+                    "Could not clone table {}: table {} already exists",
+                    source_table_name, target_table_name
+                ),
+            )
+        } else if let Err(error) = self
+            .repository
+            .clone_table(&source_table_name, &target_table_name, copy_constraints)
+            .await
+        {
+            (
+                BLogLevel::Error,
+                format!("Could not clone table {}: {}", source_table_name, error),
+            )
+        } else if self.repository.dry_run() {
+            (
+                BLogLevel::Info,
+                format!(
+                    "Dry run, table {} was not cloned into {}",
+                    source_table_name, target_table_name
+                ),
+            )
+        } else {
+            set_tables_general_info(self.repository.clone(), self.tables_general_info.clone())
+                .await;
+            (
+                BLogLevel::Info,
+                format!(
+                    "Cloned table {} into {}",
+                    source_table_name, target_table_name
+                ),
+            )
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+    }
 
-            let reset_table_data = if let Some(table_inserted_data) =
-                table_data.table_inserted_data.blocking_lock().as_ref()
-            {
-                table_inserted_data.table_name == table_name
+    // the rows are copied after the structure so a failed copy still leaves the empty table
+    pub async fn clone_table_structure(
+        &self,
+        source_table_name: String,
+        target_table_name: String,
+        copy_rows: bool,
+    ) {
+        let table_exists = self
+            .tables_general_info
+            .lock()
+            .await
+            .iter()
+            .any(|table| table.table_name == target_table_name);
+        let (level, message) = if table_exists {
+            (
+                BLogLevel::Error,
+                format!(
+                    "Could not clone the structure of table {}: table {} already exists",
+                    source_table_name, target_table_name
+                ),
+            )
+        } else if let Err(error) = self
+            .repository
+            .clone_table_structure(&source_table_name, &target_table_name)
+            .await
+        {
+            (
+                BLogLevel::Error,
+                format!(
+                    "Could not clone the structure of table {}: {}",
+                    source_table_name, error
+                ),
+            )
+        } else if self.repository.dry_run() {
+            (
+                BLogLevel::Info,
+                format!(
+                    "Dry run, the structure of table {} was not cloned into {}",
+                    source_table_name, target_table_name
+                ),
+            )
+        } else {
+            let copy_result = if copy_rows {
+                self.repository
+                    .copy_table_rows(&source_table_name, &target_table_name)
+                    .await
             } else {
-                false
+                Ok(vec![])
             };
-            if reset_table_data {
-                table_data.reset_table_data();
+            set_tables_general_info(self.repository.clone(), self.tables_general_info.clone())
+                .await;
+            match copy_result {
+                Ok(_) => (
+                    BLogLevel::Info,
+                    format!(
+                        "Cloned the structure of table {} into {}",
+                        source_table_name, target_table_name
+                    ),
+                ),
+                Err(error) => (
+                    BLogLevel::Warning,
+                    format!(
+                        "Cloned the structure of table {} into {} without its rows: {}",
+                        source_table_name, target_table_name, error
+                    ),
+                ),
+            }
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
+    }
+
+    // a failed lookup is logged by the repository and leaves nothing to reference
+    pub async fn get_key_column_names(&self, table_name: String) -> Vec<Vec<String>> {
+        self.repository
+            .get_key_column_names(&table_name)
+            .await
+            .unwrap_or_default()
+    }
+
+    pub async fn get_total_row_count(&self, table_names: &Vec<String>) -> i64 {
+        let mut total_row_count = 0;
+        for table_name in table_names {
+            // a failed count is logged by the repository and counted as empty
+            total_row_count += self
+                .repository
+                .get_row_count(table_name)
+                .await
+                .unwrap_or_default();
+        }
+        total_row_count
+    }
+
+    pub async fn delete_tables(&self, table_names: Vec<String>) {
+        let mut referencing_table_names = HashMap::new();
+        for table_name in &table_names {
+            referencing_table_names.insert(
+                table_name.clone(),
+                self.repository
+                    .get_referencing_table_names(table_name)
+                    .await
+                    .unwrap_or_default(),
+            );
+        }
+
+        // a table can only be dropped once every table referencing it is dropped,
+        // so referenced (parent) tables are deleted after their children
+        let mut remaining_table_names = table_names;
+        let mut deleted_table_names: Vec<String> = vec![];
+        let mut messages = vec![];
+        while let Some(deletable_index) = remaining_table_names.iter().position(|table_name| {
+            referencing_table_names[table_name]
+                .iter()
+                .all(|referencing_table_name| {
+                    referencing_table_name == table_name
+                        || deleted_table_names.contains(referencing_table_name)
+                })
+        }) {
+            let table_name = remaining_table_names.remove(deletable_index);
+            // failures are written to the console by delete_table
+            if self.delete_table(table_name.clone(), false).await {
+                messages.push((BLogLevel::Info, format!("Deleted table {}", table_name)));
+                deleted_table_names.push(table_name);
+            }
+        }
+        for table_name in remaining_table_names {
+            let blockers: Vec<String> = referencing_table_names[&table_name]
+                .iter()
+                .filter(|referencing_table_name| {
+                    **referencing_table_name != table_name
+                        && !deleted_table_names.contains(referencing_table_name)
+                })
+                .cloned()
+                .collect();
+            messages.push((
+                BLogLevel::Error,
+                format!(
+                    "Could not delete table {}: referenced by {}",
+                    table_name,
+                    blockers.join(", ")
+                ),
+            ));
+        }
+
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            for (level, message) in messages {
+                console.write(level, message);
             }
         })
         .await;
-        set_tables_general_info(self.repository.clone(), self.tables_general_info.clone()).await;
+    }
+
+    pub async fn export_schema(&self, path: PathBuf) {
+        let (level, message) = match self.repository.export_schema().await {
+            Ok(schema) => match tokio::fs::write(&path, schema).await {
+                Ok(()) => (
+                    BLogLevel::Info,
+                    format!("Exported schema to {}", path.display()),
+                ),
+                Err(error) => (
+                    BLogLevel::Error,
+                    format!("Could not export schema to {}: {}", path.display(), error),
+                ),
+            },
+            Err(error) => (
+                BLogLevel::Error,
+                format!("Could not export schema: {}", error),
+            ),
+        };
+        let console = self.console.clone();
+        task::spawn_blocking(move || {
+            console.write(level, message);
+        })
+        .await;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::components::business_components::component::repository_module::BRepositoryConsole;
+    use crate::components::business_components::component::repository_module::{
+        BDbPool, BRepositoryConsole,
+    };
+    use crate::components::business_components::component::{
+        BPage, BReferentialAction, BTableChangeEvents,
+    };
     use crate::components::business_components::tables::test_utils::{
-        create_btable_general, create_repository_table_and_console, default_table_in,
-        sort_by_table_name,
+        create_btable_general, create_console, create_database_console,
+        create_repository_table_and_console, default_table_in, sort_by_table_name,
     };
+    use sqlx::sqlite::SqlitePoolOptions;
     use sqlx::PgPool;
 
     async fn tables_component(pool: PgPool, table_in: &BTableIn) -> Tables {
@@ -136,6 +721,276 @@ mod tests {
         assert_eq!(tables_general_info, expected_tables_general_info);
     }
 
+    #[sqlx::test]
+    async fn test_add_table_with_generated_column(pool: PgPool) {
+        let tables = initialized_tables_component(pool, &default_table_in()).await;
+        let column = |name: &str, generated: Option<&str>| BColumn {
+            name: String::from(name),
+            datatype: BDataType::INTEGER,
+            constraints: vec![],
+            default_value: None,
+            comment: None,
+            generated: generated.map(String::from),
+        };
+        let table_in = BTableIn {
+            table_name: String::from("order_lines"),
+            columns: vec![
+                column("price", None),
+                column("quantity", None),
+                column("total", Some("price * quantity")),
+            ],
+            composite_foreign_keys: vec![],
+        };
+        assert!(tables.add_table(table_in).await);
+
+        let total = tables
+            .repository
+            .get_columns_info("order_lines")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(BColumn::to_column)
+            .find(|column| column.name == "total")
+            .unwrap();
+        assert!(total.is_generated());
+        assert!(total
+            .generated
+            .is_some_and(|generated| generated.contains("price * quantity")));
+        // the expression is kept when the schema is exported
+        let table_schema = tables
+            .repository
+            .export_table_schema("order_lines")
+            .await
+            .unwrap();
+        assert!(table_schema.contains("GENERATED ALWAYS AS"));
+    }
+
+    #[sqlx::test]
+    async fn test_add_table_with_varchar_column(pool: PgPool) {
+        let tables = initialized_tables_component(pool, &default_table_in()).await;
+        let table_in = BTableIn {
+            table_name: String::from("customers"),
+            columns: vec![BColumn {
+                name: String::from("email"),
+                datatype: BDataType::VARCHAR(20),
+                constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
+            }],
+            composite_foreign_keys: vec![],
+        };
+        assert!(tables.add_table(table_in).await);
+
+        let email = tables
+            .repository
+            .get_columns_info("customers")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(BColumn::to_column)
+            .find(|column| column.name == "email")
+            .unwrap();
+        assert_eq!(email.datatype, BDataType::VARCHAR(20));
+        // the length is kept when the schema is exported
+        let table_schema = tables
+            .repository
+            .export_table_schema("customers")
+            .await
+            .unwrap();
+        assert!(table_schema.contains("VARCHAR(20)"));
+    }
+
+    #[tokio::test]
+    async fn test_add_table_on_sqlite() {
+        // a single connection so every query sees the same in memory database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let database_console = create_database_console();
+        let console = create_console(database_console.clone());
+        let repository = Arc::new(BRepository::new(BDbPool::Sqlite(pool), database_console).await);
+        let tables = Tables::new(repository, console);
+
+        let table_in = default_table_in();
+        tables.add_table(table_in.clone()).await;
+
+        let tables_general_info = tables.tables_general_info.lock().await.clone();
+        assert_eq!(tables_general_info, vec![create_btable_general(&table_in)]);
+    }
+
+    #[sqlx::test]
+    async fn test_get_key_column_names(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql(
+            "CREATE TABLE order_products (
+                order_id INTEGER,
+                id INTEGER PRIMARY KEY,
+                code TEXT,
+                UNIQUE (order_id, code)
+            );",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            tables
+                .get_key_column_names(String::from("order_products"))
+                .await,
+            vec![
+                vec![String::from("id")],
+                vec![String::from("order_id"), String::from("code")]
+            ]
+        );
+        assert!(tables
+            .get_key_column_names(String::from("missing"))
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_key_column_names_on_sqlite() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE order_products (
+                order_id INTEGER,
+                id INTEGER PRIMARY KEY,
+                code TEXT,
+                UNIQUE (order_id, code)
+            );",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let database_console = create_database_console();
+        let console = create_console(database_console.clone());
+        let repository = Arc::new(BRepository::new(BDbPool::Sqlite(pool), database_console).await);
+        let tables = Tables::new(repository, console);
+
+        assert_eq!(
+            tables
+                .get_key_column_names(String::from("order_products"))
+                .await,
+            vec![
+                vec![String::from("id")],
+                vec![String::from("order_id"), String::from("code")]
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_set_schema(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql("CREATE SCHEMA crm;")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let copied_tables = tables.clone();
+        let users_table_data = task::spawn_blocking(move || copied_tables.open_table_data("users"))
+            .await
+            .unwrap();
+        users_table_data
+            .set_table_data(table_in.table_name.clone(), BPage::default())
+            .await;
+        let copied_users_table_data = users_table_data.clone();
+        let column_count = table_in.columns.len();
+        task::spawn_blocking(move || {
+            assert!(copied_users_table_data.add_insert_row_event(vec![None; column_count]));
+        })
+        .await
+        .unwrap();
+
+        tables.set_schema(String::from("crm")).await;
+        // the queued insert belonged to the users table of the previous schema
+        assert!(
+            !task::spawn_blocking(move || users_table_data.has_pending_changes())
+                .await
+                .unwrap()
+        );
+        assert!(tables
+            .schema_names
+            .lock()
+            .await
+            .contains(&String::from("crm")));
+        // the users table lives in public
+        assert!(tables.tables_general_info.lock().await.is_empty());
+
+        let products_table_in = BTableIn {
+            table_name: String::from("products"),
+            columns: vec![BColumn {
+                name: String::from("id"),
+                datatype: BDataType::INTEGER,
+                constraints: vec![BConstraint::PrimaryKey],
+                default_value: None,
+                comment: None,
+                generated: None,
+            }],
+            composite_foreign_keys: vec![],
+        };
+        tables.add_table(products_table_in.clone()).await;
+        assert_eq!(
+            *tables.tables_general_info.lock().await,
+            vec![create_btable_general(&products_table_in)]
+        );
+
+        tables.set_schema(String::from("public")).await;
+        assert_eq!(
+            *tables.tables_general_info.lock().await,
+            vec![create_btable_general(&table_in)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_add_table_with_check_constraint(pool: PgPool) {
+        let tables = initialized_tables_component(pool.clone(), &default_table_in()).await;
+        let mut products_table_in = BTableIn {
+            table_name: String::from("products"),
+            columns: vec![BColumn {
+                name: String::from("id"),
+                datatype: BDataType::INTEGER,
+                constraints: vec![
+                    BConstraint::PrimaryKey,
+                    BConstraint::Check(String::from("id > 0")),
+                ],
+                default_value: None,
+                comment: None,
+                generated: None,
+            }],
+            composite_foreign_keys: vec![],
+        };
+
+        assert!(tables.add_table(products_table_in.clone()).await);
+        assert!(sqlx::raw_sql("INSERT INTO products (id) VALUES (1);")
+            .execute(&pool)
+            .await
+            .is_ok());
+        assert!(sqlx::raw_sql("INSERT INTO products (id) VALUES (0);")
+            .execute(&pool)
+            .await
+            .is_err());
+
+        // the database rejects the expression and the table is not created
+        products_table_in.table_name = String::from("invalid_products");
+        products_table_in.columns[0].constraints[1] = BConstraint::Check(String::from("id >"));
+        assert!(!tables.add_table(products_table_in).await);
+        assert!(!tables
+            .tables_general_info
+            .lock()
+            .await
+            .iter()
+            .any(|table| table.table_name == "invalid_products"));
+    }
+
     #[sqlx::test]
     async fn test_add_table(pool: PgPool) {
         let initial_table_in = default_table_in();
@@ -147,7 +1002,11 @@ mod tests {
                 name: String::from("product_name"),
                 datatype: BDataType::TEXT,
                 constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
             }],
+            composite_foreign_keys: vec![],
         };
 
         // Add a new table
@@ -159,6 +1018,9 @@ mod tests {
             name: String::from("id"),
             datatype: BDataType::INTEGER,
             constraints: vec![BConstraint::PrimaryKey],
+            default_value: None,
+            comment: None,
+            generated: None,
         });
         let mut expected_tables_general_info = vec![
             create_btable_general(&initial_table_in),
@@ -180,10 +1042,677 @@ mod tests {
         let tables = initialized_tables_component(pool, &table_in).await;
 
         // Delete the initial table
-        tables.delete_table(table_in.table_name.clone()).await;
+        tables
+            .delete_table(table_in.table_name.clone(), false)
+            .await;
 
         // Verify no tables exist in `tables_general_info`
         let tables_general_info = tables.tables_general_info.lock().await;
         assert!(tables_general_info.is_empty());
     }
+
+    #[sqlx::test]
+    async fn test_delete_missing_table(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool, &table_in).await;
+
+        // the failed query is reported instead of panicking
+        assert!(!tables.delete_table(String::from("missing"), false).await);
+
+        let tables_general_info = tables.tables_general_info.lock().await;
+        assert_eq!(tables_general_info.len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_delete_referenced_table(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql(
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            tables.get_dependent_table_names(&table_in.table_name).await,
+            vec![String::from("orders")]
+        );
+
+        // the foreign key of orders stops a plain drop
+        assert!(
+            !tables
+                .delete_table(table_in.table_name.clone(), false)
+                .await
+        );
+        assert_eq!(tables.tables_general_info.lock().await.len(), 1);
+
+        // cascading drops the foreign key but keeps the referencing table
+        assert!(tables.delete_table(table_in.table_name.clone(), true).await);
+        let table_names: Vec<String> = tables
+            .tables_general_info
+            .lock()
+            .await
+            .iter()
+            .map(|table_general| table_general.table_name.clone())
+            .collect();
+        assert_eq!(table_names, vec![String::from("orders")]);
+        assert!(tables
+            .get_dependent_table_names(&table_in.table_name)
+            .await
+            .is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_truncate_table(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql(
+            "INSERT INTO users (id, name) VALUES (1, 'Alice');
+            CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));
+            INSERT INTO orders (id, user_id) VALUES (1, 1);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // postgres refuses to truncate a table referenced by a foreign key
+        assert!(
+            !tables
+                .truncate_table(table_in.table_name.clone(), false)
+                .await
+        );
+        assert!(
+            tables
+                .truncate_table(table_in.table_name.clone(), true)
+                .await
+        );
+        for table_name in ["users", "orders"] {
+            let (row_count,): (i64,) =
+                sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table_name))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(row_count, 0);
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_clone_table_structure(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql("INSERT INTO users (id, name) VALUES (1, 'Alice');")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        tables
+            .clone_table_structure(
+                table_in.table_name.clone(),
+                String::from("users_empty"),
+                false,
+            )
+            .await;
+        tables
+            .clone_table_structure(
+                table_in.table_name.clone(),
+                String::from("users_full"),
+                true,
+            )
+            .await;
+
+        for (table_name, expected_row_count) in [("users_empty", 0), ("users_full", 1)] {
+            let (row_count,): (i64,) =
+                sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table_name))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(row_count, expected_row_count);
+            // the primary key comes along with the columns
+            assert_eq!(
+                tables
+                    .repository
+                    .get_primary_key_column_names(table_name)
+                    .await
+                    .unwrap(),
+                vec![String::from("id")]
+            );
+        }
+        assert_eq!(tables.tables_general_info.lock().await.len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_clone_table(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql(
+            "INSERT INTO users (id, name) VALUES (1, 'Alice');
+            CREATE TABLE accounts (
+                id INTEGER PRIMARY KEY,
+                email TEXT NOT NULL UNIQUE,
+                age INTEGER CHECK (age > 0),
+                user_id INTEGER REFERENCES users (id)
+            );
+            INSERT INTO accounts VALUES (1, 'alice@example.com', 30, 1);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        set_tables_general_info(
+            tables.repository.clone(),
+            tables.tables_general_info.clone(),
+        )
+        .await;
+
+        tables
+            .clone_table(
+                String::from("accounts"),
+                String::from("accounts_copy"),
+                true,
+            )
+            .await;
+        tables
+            .clone_table(
+                table_in.table_name.clone(),
+                String::from("users_plain"),
+                false,
+            )
+            .await;
+
+        for (table_name, expected_constraint_types) in [
+            ("accounts_copy", vec!["c", "f", "n", "p", "u"]),
+            ("users_plain", vec![]),
+        ] {
+            let (row_count,): (i64,) =
+                sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table_name))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(row_count, 1);
+            let constraint_types: Vec<String> = sqlx::query_scalar(
+                "SELECT DISTINCT contype::TEXT FROM pg_constraint
+                    WHERE conrelid = $1::regclass
+                UNION
+                SELECT 'n' FROM information_schema.columns
+                    WHERE table_name = $1 AND column_name = 'email' AND is_nullable = 'NO'
+                ORDER BY 1",
+            )
+            .bind(table_name)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+            assert_eq!(constraint_types, expected_constraint_types);
+        }
+        assert_eq!(tables.tables_general_info.lock().await.len(), 4);
+    }
+
+    #[sqlx::test]
+    async fn test_dry_run(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = Arc::new(initialized_tables_component(pool.clone(), &table_in).await);
+        sqlx::raw_sql("INSERT INTO users VALUES (1, 'Alice');")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let copied_tables = tables.clone();
+        task::spawn_blocking(move || copied_tables.set_dry_run(true))
+            .await
+            .unwrap();
+
+        let mut orders_table_in = default_table_in();
+        orders_table_in.table_name = String::from("orders");
+        assert!(!tables.add_table(orders_table_in).await);
+        tables
+            .add_column(
+                table_in.table_name.clone(),
+                BColumn {
+                    name: String::from("email"),
+                    ..BColumn::default()
+                },
+            )
+            .await;
+        assert!(
+            !tables
+                .rename_table(table_in.table_name.clone(), String::from("people"))
+                .await
+        );
+        assert!(
+            !tables
+                .delete_table(table_in.table_name.clone(), false)
+                .await
+        );
+        assert!(
+            !tables
+                .truncate_table(table_in.table_name.clone(), false)
+                .await
+        );
+        tables
+            .drop_column(table_in.table_name.clone(), String::from("name"))
+            .await;
+        tables
+            .clone_table(
+                table_in.table_name.clone(),
+                String::from("users_copy"),
+                true,
+            )
+            .await;
+        tables
+            .clone_table_structure(
+                table_in.table_name.clone(),
+                String::from("users_structure"),
+                true,
+            )
+            .await;
+        // nothing is reported as done
+        let console = tables.console.clone();
+        let messages = task::spawn_blocking(move || console.get_messages())
+            .await
+            .unwrap();
+        assert!(!messages.iter().any(|(_, message)| {
+            ["Truncated", "Dropped", "Cloned"]
+                .iter()
+                .any(|done| message.starts_with(done))
+        }));
+        assert_eq!(
+            tables
+                .repository
+                .rename_table(&table_in.table_name, "people")
+                .await
+                .unwrap(),
+            vec![String::from(
+                "ALTER TABLE \"public\".\"users\" RENAME TO \"people\""
+            )]
+        );
+        assert_eq!(
+            tables
+                .repository
+                .delete_table(&table_in.table_name, true)
+                .await
+                .unwrap(),
+            vec![String::from("DROP TABLE \"public\".\"users\" CASCADE")]
+        );
+
+        // the pending table info changes are kept to be applied once the dry run is off
+        tables
+            .table_info
+            .set_table_info(table_in.table_name.clone())
+            .await;
+        let table_info = tables.table_info.clone();
+        let table_change_events = task::spawn_blocking(move || {
+            table_info.add_table_change_event(BTableChangeEvents::ChangeTableName(String::from(
+                "people",
+            )));
+            table_info.get_table_change_events()
+        })
+        .await
+        .unwrap();
+        assert!(!tables.table_info.update_table().await);
+        let table_info = tables.table_info.clone();
+        assert_eq!(
+            task::spawn_blocking(move || table_info.get_table_change_events())
+                .await
+                .unwrap(),
+            table_change_events
+        );
+
+        // the schema is unchanged
+        let columns_info = tables
+            .repository
+            .get_columns_info(&table_in.table_name)
+            .await
+            .unwrap();
+        assert_eq!(columns_info.len(), table_in.columns.len());
+        assert_eq!(
+            tables
+                .repository
+                .get_row_count(&table_in.table_name)
+                .await
+                .unwrap(),
+            1
+        );
+        let table_names: Vec<String> = tables
+            .repository
+            .get_general_tables_info()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|table_general_info| table_general_info.table_name)
+            .collect();
+        assert_eq!(table_names, vec![table_in.table_name.clone()]);
+    }
+
+    #[sqlx::test]
+    async fn test_open_table_data(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = Arc::new(initialized_tables_component(pool.clone(), &table_in).await);
+        sqlx::raw_sql("CREATE TABLE orders (id INTEGER PRIMARY KEY);")
+            .execute(&pool)
+            .await
+            .unwrap();
+        tables.initialize_component().await;
+
+        let copied_tables = tables.clone();
+        let (users_table_data, orders_table_data) = task::spawn_blocking(move || {
+            (
+                copied_tables.open_table_data("users"),
+                copied_tables.open_table_data("orders"),
+            )
+        })
+        .await
+        .unwrap();
+        assert!(!Arc::ptr_eq(&users_table_data, &orders_table_data));
+        users_table_data
+            .set_table_data(table_in.table_name.clone(), BPage::default())
+            .await;
+
+        // the pending insert stays with the users table when it is opened again
+        let copied_tables = tables.clone();
+        let column_count = table_in.columns.len();
+        task::spawn_blocking(move || {
+            assert!(users_table_data.add_insert_row_event(vec![None; column_count]));
+            let reopened_table_data = copied_tables.open_table_data("users");
+            assert!(Arc::ptr_eq(&reopened_table_data, &users_table_data));
+            assert!(reopened_table_data.has_pending_changes());
+            assert!(!orders_table_data.has_pending_changes());
+        })
+        .await
+        .unwrap();
+
+        assert!(
+            tables
+                .rename_table(table_in.table_name.clone(), String::from("customers"))
+                .await
+        );
+        assert!(tables
+            .open_table_data
+            .lock()
+            .await
+            .contains_key("customers"));
+        assert!(tables.delete_table(String::from("orders"), false).await);
+        let open_table_names: Vec<String> = tables
+            .open_table_data
+            .lock()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+        assert_eq!(open_table_names, vec![String::from("customers")]);
+    }
+
+    #[sqlx::test]
+    async fn test_search_columns(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql("CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_name TEXT);")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            tables.search_columns(String::from("NAME")).await,
+            vec![
+                (String::from("orders"), String::from("customer_name")),
+                (String::from("users"), String::from("name")),
+            ]
+        );
+        assert!(tables
+            .search_columns(String::from("email"))
+            .await
+            .is_empty());
+        assert!(tables.search_columns(String::new()).await.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_add_column(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool, &table_in).await;
+
+        let new_column = BColumn {
+            name: String::from("email"),
+            datatype: BDataType::TEXT,
+            constraints: vec![BConstraint::Unique],
+            default_value: None,
+            comment: None,
+            generated: None,
+        };
+        tables
+            .add_column(table_in.table_name.clone(), new_column.clone())
+            .await;
+
+        let table_general = tables.tables_general_info.lock().await[0].clone();
+        let column_index = table_general
+            .column_names
+            .iter()
+            .position(|column_name| *column_name == new_column.name)
+            .unwrap();
+        assert_eq!(table_general.data_types[column_index], new_column.datatype);
+        assert!(table_general.is_unique[column_index]);
+    }
+
+    #[sqlx::test]
+    async fn test_drop_column(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool, &table_in).await;
+
+        tables
+            .drop_column(table_in.table_name.clone(), String::from("name"))
+            .await;
+
+        let mut expected_table_in = table_in.clone();
+        expected_table_in.columns.remove(1);
+        let tables_general_info = tables.tables_general_info.lock().await.clone();
+        assert_eq!(
+            tables_general_info,
+            vec![create_btable_general(&expected_table_in)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_drop_primary_key_column(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool, &table_in).await;
+
+        tables
+            .drop_column(table_in.table_name.clone(), String::from("id"))
+            .await;
+
+        let tables_general_info = tables.tables_general_info.lock().await.clone();
+        assert_eq!(tables_general_info, vec![create_btable_general(&table_in)]);
+    }
+
+    #[sqlx::test]
+    async fn test_rename_table(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool, &table_in).await;
+
+        tables
+            .rename_table(table_in.table_name.clone(), String::from("customers"))
+            .await;
+
+        let mut renamed_table_in = table_in.clone();
+        renamed_table_in.table_name = String::from("customers");
+        let tables_general_info = tables.tables_general_info.lock().await.clone();
+        assert_eq!(
+            tables_general_info,
+            vec![create_btable_general(&renamed_table_in)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_rename_table_with_invalid_name(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool, &table_in).await;
+
+        tables
+            .rename_table(table_in.table_name.clone(), String::from("my customers"))
+            .await;
+
+        let tables_general_info = tables.tables_general_info.lock().await.clone();
+        assert_eq!(tables_general_info, vec![create_btable_general(&table_in)]);
+    }
+
+    #[sqlx::test]
+    async fn test_set_row_counts(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql("INSERT INTO users (id, name) VALUES (1, 'Alice'), (2, 'Bob');")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        tables.set_row_counts().await;
+
+        let row_counts = tables.row_counts.lock().await.clone();
+        assert_eq!(row_counts, HashMap::from([(String::from("users"), 2)]));
+    }
+
+    #[sqlx::test]
+    async fn test_column_defaults(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        let column = |name: &str, datatype: BDataType, default_value: Option<&str>| BColumn {
+            name: String::from(name),
+            datatype,
+            constraints: vec![],
+            default_value: default_value.map(String::from),
+            comment: None,
+            generated: None,
+        };
+        let events_table_in = BTableIn {
+            table_name: String::from("events"),
+            columns: vec![
+                BColumn {
+                    constraints: vec![BConstraint::PrimaryKey],
+                    ..column("id", BDataType::INTEGER, None)
+                },
+                column("happened_at", BDataType::TIMESTAMP, Some("2024-01-31")),
+                column("note", BDataType::TEXT, Some("")),
+                column("label", BDataType::TEXT, None),
+            ],
+            composite_foreign_keys: vec![],
+        };
+        assert!(tables.add_table(events_table_in).await);
+
+        let default_values: HashMap<String, Option<String>> = tables
+            .repository
+            .get_columns_info("events")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(BColumn::to_column)
+            .map(|column| (column.name, column.default_value))
+            .collect();
+        assert_eq!(
+            default_values,
+            HashMap::from([
+                (String::from("id"), None),
+                (
+                    String::from("happened_at"),
+                    Some(String::from("2024-01-31 00:00:00"))
+                ),
+                (String::from("note"), Some(String::new())),
+                (String::from("label"), None),
+            ])
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_export_schema(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        let orders_table_in = BTableIn {
+            table_name: String::from("orders"),
+            columns: vec![
+                BColumn {
+                    name: String::from("id"),
+                    datatype: BDataType::SERIAL,
+                    constraints: vec![BConstraint::PrimaryKey],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("user_id"),
+                    datatype: BDataType::INTEGER,
+                    constraints: vec![BConstraint::ForeignKey {
+                        table: table_in.table_name.clone(),
+                        column: String::from("id"),
+                        on_delete: BReferentialAction::Cascade,
+                    }],
+                    default_value: None,
+                    comment: None,
+                    generated: None,
+                },
+                BColumn {
+                    name: String::from("status"),
+                    datatype: BDataType::TEXT,
+                    constraints: vec![BConstraint::NotNull],
+                    default_value: Some(String::from("it's new")),
+                    comment: None,
+                    generated: None,
+                },
+            ],
+            composite_foreign_keys: vec![],
+        };
+        tables.add_table(orders_table_in).await;
+
+        let path = std::env::temp_dir().join(format!("crm_schema_{}.sql", std::process::id()));
+        tables.export_schema(path.clone()).await;
+        let schema = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // users is referenced by orders so it is created first
+        let users_position = schema.find("CREATE TABLE \"public\".\"users\"").unwrap();
+        let orders_position = schema.find("CREATE TABLE \"public\".\"orders\"").unwrap();
+        assert!(users_position < orders_position);
+        // the introspected 'it''s new'::text default is written back quoted once
+        assert!(schema.contains("DEFAULT 'it''s new'"));
+
+        // recreating the exported schema gives back the same schema
+        sqlx::raw_sql("DROP TABLE orders; DROP TABLE users;")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(&schema).execute(&pool).await.unwrap();
+        assert_eq!(tables.repository.export_schema().await.unwrap(), schema);
+    }
+
+    #[sqlx::test]
+    async fn test_export_schema_foreign_key_cycle(pool: PgPool) {
+        let table_in = default_table_in();
+        let tables = initialized_tables_component(pool.clone(), &table_in).await;
+        sqlx::raw_sql(
+            "CREATE TABLE teams (id INTEGER PRIMARY KEY, owner_id INTEGER REFERENCES users(id)); \
+             ALTER TABLE users ADD COLUMN team_id INTEGER REFERENCES teams(id) ON DELETE SET NULL;",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("crm_cycle_schema_{}.sql", std::process::id()));
+        tables.export_schema(path.clone()).await;
+        let schema = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // both tables are created without their foreign keys, which are added afterwards
+        let last_create_position = schema.rfind("CREATE TABLE").unwrap();
+        let users_foreign_key_position = schema
+            .find("ALTER TABLE \"public\".\"users\" ADD FOREIGN KEY (\"team_id\") REFERENCES \"public\".\"teams\"(\"id\") ON DELETE SET NULL")
+            .unwrap();
+        let teams_foreign_key_position = schema
+            .find(
+                "ALTER TABLE \"public\".\"teams\" ADD FOREIGN KEY (\"owner_id\") REFERENCES \"public\".\"users\"(\"id\")",
+            )
+            .unwrap();
+        assert!(last_create_position < users_foreign_key_position);
+        assert!(last_create_position < teams_foreign_key_position);
+
+        sqlx::raw_sql("DROP TABLE teams, users;")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(&schema).execute(&pool).await.unwrap();
+        assert_eq!(tables.repository.export_schema().await.unwrap(), schema);
+    }
 }
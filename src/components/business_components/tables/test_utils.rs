@@ -1,6 +1,6 @@
 use crate::components::business_components::{
     component::{
-        repository_module::{BRepository, BRepositoryConsole},
+        repository_module::{BDbPool, BRepository, BRepositoryConsole},
         BColumn, BConstraint, BDataType, BTableChangeEvents, BTableData, BTableGeneral, BTableIn,
         BTableInfo, BTableInsertedData, BusinessComponent,
     },
@@ -20,7 +20,7 @@ pub async fn create_repository(
     pool: PgPool,
     database_console: Arc<BRepositoryConsole>,
 ) -> Arc<BRepository> {
-    Arc::new(BRepository::new(Some(pool), database_console).await)
+    Arc::new(BRepository::new(BDbPool::Postgres(pool), database_console).await)
 }
 
 pub async fn create_repository_table_and_console(
@@ -30,7 +30,7 @@ pub async fn create_repository_table_and_console(
     let database_console = create_database_console();
     let business_console = create_console(database_console.clone());
     let repository = create_repository(pool, database_console).await;
-    repository.create_table(table_in).await;
+    repository.create_table(table_in).await.unwrap();
     (repository, business_console)
 }
 
@@ -42,13 +42,20 @@ pub fn default_table_in() -> BTableIn {
                 name: String::from("id"),
                 datatype: BDataType::INTEGER,
                 constraints: vec![BConstraint::PrimaryKey],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
             BColumn {
                 name: String::from("name"),
                 datatype: BDataType::TEXT,
                 constraints: vec![],
+                default_value: None,
+                comment: None,
+                generated: None,
             },
         ],
+        composite_foreign_keys: vec![],
     }
 }
 
@@ -9,7 +9,10 @@ pub async fn set_tables_general_info(
     tables_general_info: Arc<AsyncMutex<Vec<BTableGeneral>>>,
 ) {
     let mut locked_tables_general_info = tables_general_info.lock().await;
-    let new_tables_general_info = repository.get_general_tables_info().await.unwrap();
+    // the repository logs the failed query, the previously loaded tables are kept
+    let Ok(new_tables_general_info) = repository.get_general_tables_info().await else {
+        return;
+    };
     let new_tables_general_info_structured = new_tables_general_info
         .into_iter()
         .map(|table| BTableGeneral::to_table(table))
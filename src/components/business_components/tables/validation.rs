@@ -0,0 +1,645 @@
+use crate::components::business_components::component::{
+    BColumn, BCompositeForeignKey, BConstraint, BDataType, BReferentialAction, BTableIn,
+};
+
+// postgres truncates identifiers longer than this
+const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+// keywords reserved by both postgres and sqlite, they would have to be quoted everywhere
+const RESERVED_KEYWORDS: [&str; 45] = [
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "CASE",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "FOREIGN",
+    "FROM",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "LIMIT",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "PRIMARY",
+    "REFERENCES",
+    "RETURNING",
+    "SELECT",
+    "TABLE",
+    "THEN",
+    "UNION",
+    "UNIQUE",
+    "USING",
+    "WHEN",
+    "WHERE",
+];
+
+pub fn validate_identifier(identifier: &str) -> Result<(), String> {
+    let first_character = match identifier.chars().next() {
+        Some(first_character) => first_character,
+        None => return Err(String::from("Name cannot be empty")),
+    };
+    if !(first_character.is_ascii_alphabetic() || first_character == '_') {
+        return Err(format!(
+            "Name {} must start with a letter or an underscore",
+            identifier
+        ));
+    }
+    if !identifier
+        .chars()
+        .all(|character| character.is_ascii_alphanumeric() || character == '_')
+    {
+        return Err(format!(
+            "Name {} can only contain letters, digits and underscores",
+            identifier
+        ));
+    }
+    if identifier.len() > MAX_IDENTIFIER_LENGTH {
+        return Err(format!(
+            "Name {} is longer than {} characters",
+            identifier, MAX_IDENTIFIER_LENGTH
+        ));
+    }
+    if RESERVED_KEYWORDS.contains(&identifier.to_uppercase().as_str()) {
+        return Err(format!("Name {} is a reserved SQL keyword", identifier));
+    }
+    Ok(())
+}
+
+pub fn validate_column(column: &BColumn) -> Result<(), String> {
+    validate_identifier(&column.name)?;
+    let primary_key_count = column
+        .constraints
+        .iter()
+        .filter(|constraint| matches!(constraint, BConstraint::PrimaryKey))
+        .count();
+    if primary_key_count > 1 {
+        return Err(format!(
+            "Column {} has more than one primary key constraint",
+            column.name
+        ));
+    }
+
+    let foreign_keys: Vec<&BConstraint> = column
+        .constraints
+        .iter()
+        .filter(|constraint| matches!(constraint, BConstraint::ForeignKey { .. }))
+        .collect();
+    if foreign_keys.len() > 1 {
+        return Err(format!(
+            "Column {} can only reference one foreign key",
+            column.name
+        ));
+    }
+
+    if column.generated.is_some() {
+        if column.default_value.is_some() {
+            return Err(format!(
+                "Column {} is generated so it cannot have a default value",
+                column.name
+            ));
+        }
+        if column.datatype == BDataType::SERIAL {
+            return Err(format!(
+                "Column {} is SERIAL so it cannot also be generated",
+                column.name
+            ));
+        }
+    }
+
+    if column.datatype == BDataType::SERIAL {
+        // the sequence already is the default, postgres rejects a second one
+        if column.default_value.is_some() {
+            return Err(format!(
+                "Column {} is SERIAL so its values come from a sequence and it cannot have a default value",
+                column.name
+            ));
+        }
+        if let Some(BConstraint::ForeignKey {
+            table,
+            column: referenced_column,
+            ..
+        }) = foreign_keys.first()
+        {
+            return Err(format!(
+                "Column {} is SERIAL so its values are generated and it cannot reference {}.{}",
+                column.name, table, referenced_column
+            ));
+        }
+    }
+
+    // a primary key is NOT NULL as well, rows given NULL would fail to insert or to delete
+    if column
+        .constraints
+        .iter()
+        .any(|constraint| matches!(constraint, BConstraint::PrimaryKey | BConstraint::NotNull))
+    {
+        if column.default_value.as_ref().is_some_and(|default_value| {
+            default_value.trim().eq_ignore_ascii_case("NULL")
+                || column.datatype.is_null(&column.default_value)
+        }) {
+            return Err(format!(
+                "Column {} cannot be NULL so it cannot default to NULL",
+                column.name
+            ));
+        }
+        if let Some(BConstraint::ForeignKey {
+            table,
+            on_delete: BReferentialAction::SetNull,
+            ..
+        }) = foreign_keys.first()
+        {
+            return Err(format!(
+                "Column {} cannot be NULL so it cannot be set to NULL when the referenced {} row is deleted",
+                column.name, table
+            ));
+        }
+    }
+
+    // the default is written as a literal of the column type like an inserted value
+    if let Some(default_value) = &column.default_value {
+        if !column.datatype.is_null(&column.default_value) {
+            validate_value(&column.datatype, default_value).map_err(|error| {
+                format!("Column {} has an invalid default: {}", column.name, error)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+// NULL is handled by the callers, an empty string is only a value of the text types
+pub fn validate_value(data_type: &BDataType, value: &str) -> Result<(), String> {
+    let is_valid = match data_type {
+        // parsing into the matching width rejects values the column would overflow
+        BDataType::SMALLINT => value.parse::<i16>().is_ok(),
+        BDataType::INTEGER | BDataType::SERIAL => value.parse::<i32>().is_ok(),
+        BDataType::BIGINT => value.parse::<i64>().is_ok(),
+        // NaN and infinity would be written as unquoted identifiers so only finite values pass
+        BDataType::DOUBLE => value.parse::<f64>().is_ok_and(|value| value.is_finite()),
+        BDataType::BOOLEAN => matches!(
+            value.to_lowercase().as_str(),
+            "true" | "false" | "t" | "f" | "1" | "0"
+        ),
+        BDataType::TIMESTAMP => is_valid_timestamp(value),
+        // canonical 8-4-4-4-12 hex form, postgres also accepts braces and no hyphens
+        BDataType::UUID => {
+            let groups: Vec<&str> = value.split('-').collect();
+            groups.len() == 5
+                && [8, 4, 4, 4, 12].iter().zip(&groups).all(|(length, group)| {
+                    group.len() == *length && group.chars().all(|c| c.is_ascii_hexdigit())
+                })
+        }
+        BDataType::VARCHAR(length) => value.chars().count() <= *length as usize,
+        BDataType::TEXT => true,
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!("Value {} is not a valid {}", value, data_type))
+    }
+}
+
+// YYYY-MM-DD with an optional HH:MM[:SS[.fraction]] time separated by a space or T
+fn is_valid_timestamp(value: &str) -> bool {
+    let (date, time) = match value.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let is_number = |part: &str, length: usize| {
+        part.len() == length && part.chars().all(|c| c.is_ascii_digit())
+    };
+    let date_parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = date_parts[..] else {
+        return false;
+    };
+    if !(is_number(year, 4) && is_number(month, 2) && is_number(day, 2)) {
+        return false;
+    }
+    let (year, month, day) = (
+        year.parse::<u32>().unwrap(),
+        month.parse::<u32>().unwrap(),
+        day.parse::<u32>().unwrap(),
+    );
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return false,
+    };
+    if day == 0 || day > days_in_month {
+        return false;
+    }
+    let Some(time) = time else {
+        return true;
+    };
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let time_parts: Vec<&str> = time.split(':').collect();
+    let (hour, minute, second) = match time_parts[..] {
+        [hour, minute] if fraction.is_none() => (hour, minute, "00"),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return false,
+    };
+    let limits = [(hour, 23), (minute, 59), (second, 59)];
+    limits
+        .iter()
+        .all(|(part, limit)| is_number(part, 2) && part.parse::<u32>().unwrap() <= *limit)
+        && fraction.map_or(true, |fraction| {
+            !fraction.is_empty() && fraction.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+// column names are compared case insensitively because unquoted names are folded
+pub fn get_duplicate_column_indexes(columns: &Vec<BColumn>) -> Vec<usize> {
+    let column_names: Vec<String> = columns
+        .iter()
+        .map(|column| column.name.to_lowercase())
+        .collect();
+    (0..column_names.len())
+        .filter(|index| {
+            column_names
+                .iter()
+                .filter(|column_name| **column_name == column_names[*index])
+                .count()
+                > 1
+        })
+        .collect()
+}
+
+pub fn validate_table_in(table_in: &BTableIn) -> Result<(), String> {
+    validate_identifier(&table_in.table_name)?;
+    if table_in.columns.is_empty() {
+        return Err(format!(
+            "Table {} must have at least one column",
+            table_in.table_name
+        ));
+    }
+    if let Some(index) = get_duplicate_column_indexes(&table_in.columns).first() {
+        return Err(format!(
+            "Column {} is defined more than once",
+            table_in.columns[*index].name
+        ));
+    }
+    for column in &table_in.columns {
+        validate_column(column)?;
+    }
+    for composite_foreign_key in &table_in.composite_foreign_keys {
+        validate_composite_foreign_key(table_in, composite_foreign_key)?;
+    }
+    Ok(())
+}
+
+// every column of the referenced key needs its own column of the new table
+fn validate_composite_foreign_key(
+    table_in: &BTableIn,
+    composite_foreign_key: &BCompositeForeignKey,
+) -> Result<(), String> {
+    if composite_foreign_key.referenced_table.is_empty() {
+        return Err(String::from("Composite foreign key must reference a table"));
+    }
+    if composite_foreign_key.referenced_column_names.is_empty() {
+        return Err(format!(
+            "Composite foreign key to {} must reference a primary key or unique constraint",
+            composite_foreign_key.referenced_table
+        ));
+    }
+    let column_names: Vec<String> = composite_foreign_key
+        .column_names
+        .iter()
+        .map(|column_name| column_name.to_lowercase())
+        .collect();
+    for (index, column_name) in column_names.iter().enumerate() {
+        if column_name.is_empty() {
+            return Err(format!(
+                "Composite foreign key to {} has no column for {}",
+                composite_foreign_key.referenced_table,
+                composite_foreign_key.referenced_column_names[index]
+            ));
+        }
+        if !table_in
+            .columns
+            .iter()
+            .any(|column| column.name.to_lowercase() == *column_name)
+        {
+            return Err(format!(
+                "Composite foreign key to {} uses unknown column {}",
+                composite_foreign_key.referenced_table, composite_foreign_key.column_names[index]
+            ));
+        }
+        if column_names[..index].contains(column_name) {
+            return Err(format!(
+                "Column {} is used more than once in the composite foreign key to {}",
+                composite_foreign_key.column_names[index], composite_foreign_key.referenced_table
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(datatype: BDataType, constraints: Vec<BConstraint>) -> BColumn {
+        BColumn {
+            name: String::from("quantity"),
+            datatype,
+            constraints,
+            default_value: None,
+            comment: None,
+            generated: None,
+        }
+    }
+
+    fn foreign_key(table: &str, column: &str) -> BConstraint {
+        BConstraint::ForeignKey {
+            table: String::from(table),
+            column: String::from(column),
+            on_delete: BReferentialAction::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate_value() {
+        assert!(validate_value(&BDataType::INTEGER, "42").is_ok());
+        assert!(validate_value(&BDataType::INTEGER, "").is_err());
+        assert!(validate_value(&BDataType::BOOLEAN, "").is_err());
+        assert!(validate_value(&BDataType::TEXT, "").is_ok());
+        assert!(validate_value(&BDataType::INTEGER, "forty").is_err());
+        assert!(validate_value(&BDataType::INTEGER, "2147483648").is_err());
+        assert!(validate_value(&BDataType::SMALLINT, "-32768").is_ok());
+        assert!(validate_value(&BDataType::SMALLINT, "32768").is_err());
+        assert!(validate_value(&BDataType::BIGINT, "2147483648").is_ok());
+        assert!(validate_value(&BDataType::BIGINT, "9223372036854775808").is_err());
+        assert!(validate_value(&BDataType::DOUBLE, "-1.5e-10").is_ok());
+        assert!(validate_value(&BDataType::DOUBLE, "NaN").is_err());
+        assert!(validate_value(&BDataType::DOUBLE, "1,5").is_err());
+        assert!(validate_value(&BDataType::VARCHAR(5), "héllo").is_ok());
+        assert!(validate_value(&BDataType::VARCHAR(5), "hello!").is_err());
+        assert!(validate_value(&BDataType::BOOLEAN, "TRUE").is_ok());
+        assert!(validate_value(&BDataType::BOOLEAN, "yes").is_err());
+        assert!(validate_value(&BDataType::TIMESTAMP, "2024-01-31 10:00:00").is_ok());
+        assert!(validate_value(&BDataType::TIMESTAMP, "31/01/2024").is_err());
+        assert!(validate_value(&BDataType::TIMESTAMP, "2024-02-29T10:00:00.123456").is_ok());
+        assert!(validate_value(&BDataType::TIMESTAMP, "2024-01-31").is_ok());
+        assert!(validate_value(&BDataType::TIMESTAMP, "2023-02-29").is_err());
+        assert!(validate_value(&BDataType::TIMESTAMP, "2024-01-31 25:00:00").is_err());
+        assert!(validate_value(&BDataType::TIMESTAMP, "2024-01-31 10:00:00 garbage").is_err());
+        assert!(validate_value(&BDataType::TEXT, "anything").is_ok());
+        assert!(validate_value(&BDataType::UUID, "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11").is_ok());
+        assert!(validate_value(&BDataType::UUID, "a0eebc99-9c0b-4ef8-bb6d").is_err());
+    }
+
+    #[test]
+    fn test_valid_identifier() {
+        assert!(validate_identifier("order_items_2").is_ok());
+        assert!(validate_identifier("_internal").is_ok());
+    }
+
+    #[test]
+    fn test_empty_identifier() {
+        assert!(validate_identifier("").is_err());
+    }
+
+    #[test]
+    fn test_identifier_starting_with_digit() {
+        assert!(validate_identifier("2fa_codes").is_err());
+    }
+
+    #[test]
+    fn test_identifier_with_invalid_characters() {
+        assert!(validate_identifier("order items").is_err());
+        assert!(validate_identifier("users\"; DROP TABLE users").is_err());
+    }
+
+    #[test]
+    fn test_identifier_too_long() {
+        assert!(validate_identifier(&"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_reserved_keyword_identifier() {
+        assert!(validate_identifier("select").is_err());
+        assert!(validate_identifier("Order").is_err());
+        assert!(validate_identifier("orders").is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_column_indexes() {
+        let mut columns = vec![
+            column(BDataType::INTEGER, vec![]),
+            column(BDataType::TEXT, vec![]),
+            column(BDataType::TEXT, vec![]),
+        ];
+        columns[0].name = String::from("id");
+        columns[1].name = String::from("Email");
+        columns[2].name = String::from("email");
+        assert_eq!(get_duplicate_column_indexes(&columns), vec![1, 2]);
+
+        columns[2].name = String::from("name");
+        assert!(get_duplicate_column_indexes(&columns).is_empty());
+    }
+
+    #[test]
+    fn test_table_without_columns() {
+        let table_in = BTableIn {
+            table_name: String::from("users"),
+            columns: vec![],
+            composite_foreign_keys: vec![],
+        };
+        assert!(validate_table_in(&table_in).is_err());
+    }
+
+    #[test]
+    fn test_composite_foreign_key() {
+        let mut table_in = BTableIn {
+            table_name: String::from("order_items"),
+            columns: ["order_id", "product_id", "quantity"]
+                .iter()
+                .map(|name| BColumn {
+                    name: String::from(*name),
+                    ..column(BDataType::INTEGER, vec![])
+                })
+                .collect(),
+            composite_foreign_keys: vec![BCompositeForeignKey {
+                column_names: vec![String::from("order_id"), String::from("product_id")],
+                referenced_table: String::from("order_products"),
+                referenced_column_names: vec![String::from("order_id"), String::from("id")],
+            }],
+        };
+        assert!(validate_table_in(&table_in).is_ok());
+
+        let valid_composite_foreign_key = table_in.composite_foreign_keys[0].clone();
+        let invalid_changes: [fn(&mut BCompositeForeignKey); 5] = [
+            |composite_foreign_key| composite_foreign_key.referenced_table.clear(),
+            |composite_foreign_key| {
+                composite_foreign_key.column_names.clear();
+                composite_foreign_key.referenced_column_names.clear();
+            },
+            |composite_foreign_key| composite_foreign_key.column_names[1].clear(),
+            |composite_foreign_key| composite_foreign_key.column_names[1] = String::from("price"),
+            |composite_foreign_key| {
+                composite_foreign_key.column_names[1] = String::from("Order_Id")
+            },
+        ];
+        for invalid_change in invalid_changes {
+            let mut composite_foreign_key = valid_composite_foreign_key.clone();
+            invalid_change(&mut composite_foreign_key);
+            table_in.composite_foreign_keys = vec![composite_foreign_key];
+            assert!(validate_table_in(&table_in).is_err());
+        }
+    }
+
+    #[test]
+    fn test_valid_column() {
+        let valid_column = column(
+            BDataType::INTEGER,
+            vec![BConstraint::PrimaryKey, foreign_key("users", "id")],
+        );
+        assert!(validate_column(&valid_column).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_primary_key() {
+        let invalid_column = column(
+            BDataType::INTEGER,
+            vec![BConstraint::PrimaryKey, BConstraint::PrimaryKey],
+        );
+        assert!(validate_column(&invalid_column).is_err());
+    }
+
+    #[test]
+    fn test_multiple_foreign_keys() {
+        let invalid_column = column(
+            BDataType::INTEGER,
+            vec![foreign_key("users", "id"), foreign_key("products", "id")],
+        );
+        assert!(validate_column(&invalid_column).is_err());
+    }
+
+    #[test]
+    fn test_serial_foreign_key() {
+        let invalid_column = column(BDataType::SERIAL, vec![foreign_key("users", "id")]);
+        assert!(validate_column(&invalid_column).is_err());
+    }
+
+    #[test]
+    fn test_generated_column() {
+        let mut generated_column = column(BDataType::INTEGER, vec![]);
+        generated_column.generated = Some(String::from("price * quantity"));
+        assert!(validate_column(&generated_column).is_ok());
+
+        let mut invalid_column = generated_column.clone();
+        invalid_column.default_value = Some(String::from("0"));
+        assert!(validate_column(&invalid_column).is_err());
+
+        let mut invalid_column = generated_column;
+        invalid_column.datatype = BDataType::SERIAL;
+        assert!(validate_column(&invalid_column).is_err());
+    }
+
+    #[test]
+    fn test_generated_column_with_default() {
+        let mut invalid_column = column(BDataType::INTEGER, vec![]);
+        invalid_column.generated = Some(String::from("price * quantity"));
+        invalid_column.default_value = Some(String::from("0"));
+        assert!(validate_column(&invalid_column)
+            .unwrap_err()
+            .contains("cannot have a default value"));
+    }
+
+    #[test]
+    fn test_serial_column_with_default() {
+        let mut invalid_column = column(BDataType::SERIAL, vec![BConstraint::PrimaryKey]);
+        invalid_column.default_value = Some(String::from("1"));
+        assert!(validate_column(&invalid_column)
+            .unwrap_err()
+            .contains("values come from a sequence"));
+
+        invalid_column.default_value = None;
+        assert!(validate_column(&invalid_column).is_ok());
+    }
+
+    #[test]
+    fn test_column_default() {
+        let mut invalid_column = column(BDataType::TIMESTAMP, vec![]);
+        invalid_column.default_value = Some(String::from("2024-02-30"));
+        assert!(validate_column(&invalid_column)
+            .unwrap_err()
+            .contains("invalid default"));
+
+        let mut valid_column = invalid_column;
+        valid_column.default_value = Some(String::from("2024-02-29 10:00:00"));
+        assert!(validate_column(&valid_column).is_ok());
+
+        // an empty default is the empty string for text columns and NULL for the others
+        let mut valid_column = column(BDataType::TEXT, vec![BConstraint::NotNull]);
+        valid_column.default_value = Some(String::new());
+        assert!(validate_column(&valid_column).is_ok());
+
+        let mut invalid_column = column(BDataType::INTEGER, vec![BConstraint::NotNull]);
+        invalid_column.default_value = Some(String::new());
+        assert!(validate_column(&invalid_column)
+            .unwrap_err()
+            .contains("cannot default to NULL"));
+    }
+
+    #[test]
+    fn test_nullable_primary_key() {
+        let mut invalid_column = column(BDataType::INTEGER, vec![BConstraint::PrimaryKey]);
+        invalid_column.default_value = Some(String::from("null"));
+        assert!(validate_column(&invalid_column)
+            .unwrap_err()
+            .contains("cannot default to NULL"));
+
+        let invalid_column = column(
+            BDataType::INTEGER,
+            vec![
+                BConstraint::PrimaryKey,
+                BConstraint::ForeignKey {
+                    table: String::from("users"),
+                    column: String::from("id"),
+                    on_delete: BReferentialAction::SetNull,
+                },
+            ],
+        );
+        assert!(validate_column(&invalid_column)
+            .unwrap_err()
+            .contains("set to NULL"));
+
+        // a nullable column may still be cleared when the referenced row goes away
+        let valid_column = column(
+            BDataType::INTEGER,
+            vec![BConstraint::ForeignKey {
+                table: String::from("users"),
+                column: String::from("id"),
+                on_delete: BReferentialAction::SetNull,
+            }],
+        );
+        assert!(validate_column(&valid_column).is_ok());
+    }
+}
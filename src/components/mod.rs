@@ -1,2 +1,2 @@
-mod business_components;
+pub mod business_components;
 pub mod ui_components;
@@ -9,4 +9,12 @@ pub trait UIComponent {
 
 pub trait Event {
     fn message(self) -> Message;
+
+    // for follow up events that don't depend on each other
+    fn batch(events: Vec<Self>) -> Task<Message>
+    where
+        Self: Sized,
+    {
+        Task::batch(events.into_iter().map(|event| Task::done(event.message())))
+    }
 }
@@ -8,14 +8,21 @@ use crate::components::ui_components::{
 use crate::components::ui_components::{
     console::console::ConsoleUI,
     home::{events::HomeMessage, home::HomeUI},
+    query_runner::query_runner::QueryRunnerUI,
     tables::{events::TablesMessage, tables::TablesUI},
 };
-use iced::Task;
+use iced::{Subscription, Task};
 
 #[derive(Debug, Clone)]
 pub enum ComponentsMessage {
     InitializeComponents(UIComponents),
     ShowOrRemoveConsole,
+    ShowOrRemoveQueryRunner,
+    KeepAlive,
+    KeepAliveResult(bool),
+    ReconnectResult(bool),
+    OpenTableData(String),
+    ShowHome,
 }
 
 impl Event for ComponentsMessage {
@@ -27,6 +34,8 @@ impl Event for ComponentsMessage {
 #[derive(Debug, Clone)]
 pub enum CurrentComponent {
     Home,
+    // the rows of the open tables, one tab each
+    TableData,
 }
 
 #[derive(Debug, Clone)]
@@ -34,8 +43,13 @@ pub struct UIComponents {
     pub home_ui: HomeUI,
     pub tables_ui: TablesUI,
     pub console_ui: ConsoleUI,
+    pub query_runner_ui: QueryRunnerUI,
     pub current_component: CurrentComponent,
     pub show_console: bool,
+    pub show_query_runner: bool,
+    pub connection_alive: bool,
+    // a failed keep-alive ping is followed by reconnect attempts, no pings are sent meanwhile
+    pub reconnecting: bool,
 }
 
 impl UIComponent for UIComponents {
@@ -47,21 +61,66 @@ impl UIComponent for UIComponents {
                 self.show_console = !self.show_console;
                 Task::none()
             }
+            Self::EventType::ShowOrRemoveQueryRunner => {
+                self.show_query_runner = !self.show_query_runner;
+                Task::none()
+            }
+            Self::EventType::KeepAlive if self.reconnecting => Task::none(),
+            Self::EventType::KeepAlive => {
+                let home = self.home_ui.home.clone();
+                Task::perform(async move { home.keep_alive().await }, |is_alive| {
+                    Self::EventType::KeepAliveResult(is_alive).message()
+                })
+            }
+            Self::EventType::KeepAliveResult(is_alive) => {
+                self.connection_alive = is_alive;
+                if is_alive {
+                    return Task::none();
+                }
+                self.reconnecting = true;
+                let home = self.home_ui.home.clone();
+                Task::perform(async move { home.reconnect().await }, |is_reconnected| {
+                    Self::EventType::ReconnectResult(is_reconnected).message()
+                })
+            }
+            Self::EventType::ReconnectResult(is_reconnected) => {
+                self.reconnecting = false;
+                self.connection_alive = is_reconnected;
+                Task::none()
+            }
+            Self::EventType::OpenTableData(table_name) => {
+                self.current_component = CurrentComponent::TableData;
+                Task::done(TablesMessage::OpenTableData(table_name).message())
+            }
+            Self::EventType::ShowHome => {
+                self.current_component = CurrentComponent::Home;
+                Task::none()
+            }
             _ => Task::none(),
         }
     }
 }
 
 impl UIComponents {
-    pub async fn new() -> Self {
-        /* creates repositories */
-        let business_components = BusinessComponents::new().await;
+    pub fn new(business_components: BusinessComponents) -> Self {
         Self {
             home_ui: HomeUI::new(business_components.home),
             tables_ui: TablesUI::new(business_components.tables),
             console_ui: ConsoleUI::new(business_components.console.clone()),
+            query_runner_ui: QueryRunnerUI::new(business_components.query_runner),
             current_component: CurrentComponent::Home,
             show_console: false,
+            show_query_runner: false,
+            connection_alive: true,
+            reconnecting: false,
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        if let Some(keep_alive_interval) = self.home_ui.home.keep_alive_interval() {
+            iced::time::every(keep_alive_interval).map(|_| ComponentsMessage::KeepAlive.message())
+        } else {
+            Subscription::none()
         }
     }
 
@@ -0,0 +1,200 @@
+use crate::components::business_components::{
+    component::BConnectionConfig,
+    components::{BusinessComponents, BusinessConnection},
+};
+use crate::components::ui_components::{
+    component::{Event, UIComponent},
+    components::{ComponentsMessage, UIComponents},
+    connection::events::ConnectionMessage,
+    events::Message,
+};
+use iced::{
+    border::Radius,
+    widget::{button, container, text, text_input, Column},
+    Background, Border, Color, Element, Length, Task,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct ConnectionUI {
+    connection: Arc<BusinessConnection>,
+    host: String,
+    port: String,
+    username: String,
+    password: String,
+    database: String,
+    is_connecting: bool,
+    error: Option<String>,
+    // read once, the environment doesn't change while the app runs
+    is_configured_by_env: bool,
+}
+
+impl UIComponent for ConnectionUI {
+    type EventType = ConnectionMessage;
+
+    fn update(&mut self, message: Self::EventType) -> Task<Message> {
+        match message {
+            Self::EventType::UpdateHost(host) => {
+                self.host = host;
+                Task::none()
+            }
+            Self::EventType::UpdatePort(port) => {
+                self.port = port;
+                Task::none()
+            }
+            Self::EventType::UpdateUsername(username) => {
+                self.username = username;
+                Task::none()
+            }
+            Self::EventType::UpdatePassword(password) => {
+                self.password = password;
+                Task::none()
+            }
+            Self::EventType::UpdateDatabase(database) => {
+                self.database = database;
+                Task::none()
+            }
+            Self::EventType::Connect => {
+                let Ok(port) = self.port.trim().parse::<u16>() else {
+                    self.error = Some(format!("Invalid port: {}", self.port));
+                    return Task::none();
+                };
+                self.is_connecting = true;
+                self.error = None;
+                let connection = self.connection.clone();
+                let config = BConnectionConfig {
+                    host: self.host.trim().to_string(),
+                    port,
+                    username: self.username.trim().to_string(),
+                    database: self.database.trim().to_string(),
+                };
+                let password = self.password.clone();
+                Task::perform(
+                    async move { connection.connect(config, password).await },
+                    connected_message,
+                )
+            }
+            Self::EventType::ConnectionFailed(error) => {
+                self.is_connecting = false;
+                self.is_configured_by_env = false;
+                self.error = Some(error);
+                Task::none()
+            }
+        }
+    }
+}
+
+impl ConnectionUI {
+    pub fn new(connection: Arc<BusinessConnection>) -> Self {
+        let config = connection.last_connection_config();
+        let is_configured_by_env = connection.is_configured_by_env();
+        Self {
+            connection,
+            host: config.host,
+            port: config.port.to_string(),
+            username: config.username,
+            password: String::new(),
+            database: config.database,
+            is_connecting: false,
+            error: None,
+            is_configured_by_env,
+        }
+    }
+
+    // the environment connection is made at startup without the form,
+    // the form is shown with the error when it fails
+    pub fn startup_task(&self) -> Task<Message> {
+        if self.is_configured_by_env {
+            let connection = self.connection.clone();
+            Task::perform(
+                async move { connection.connect_from_env().await },
+                connected_message,
+            )
+        } else {
+            Task::none()
+        }
+    }
+
+    pub fn content<'a>(&'a self) -> Element<'a, Message> {
+        if self.is_configured_by_env {
+            return container("loading").into();
+        }
+        let connect_button = button("Connect")
+            .on_press_maybe(
+                (!self.is_connecting)
+                    .then(|| <ConnectionUI as UIComponent>::EventType::Connect.message()),
+            )
+            .padding(10);
+        let mut form = Column::new()
+            .spacing(10)
+            .width(400)
+            .push(text("Connect to PostgreSQL").size(24))
+            .push(self.connection_input("Host", &self.host, |input| {
+                <ConnectionUI as UIComponent>::EventType::UpdateHost(input).message()
+            }))
+            .push(self.connection_input("Port", &self.port, |input| {
+                <ConnectionUI as UIComponent>::EventType::UpdatePort(input).message()
+            }))
+            .push(self.connection_input("Username", &self.username, |input| {
+                <ConnectionUI as UIComponent>::EventType::UpdateUsername(input).message()
+            }))
+            .push(
+                self.connection_input("Password", &self.password, |input| {
+                    <ConnectionUI as UIComponent>::EventType::UpdatePassword(input).message()
+                })
+                .secure(true),
+            )
+            .push(self.connection_input("Database", &self.database, |input| {
+                <ConnectionUI as UIComponent>::EventType::UpdateDatabase(input).message()
+            }))
+            .push(connect_button);
+        if self.is_connecting {
+            form = form.push(text("Connecting..."));
+        }
+        if let Some(error) = &self.error {
+            form = form.push(text(error).color(Color::from_rgb(1.0, 0.4, 0.4)));
+        }
+        container(form)
+            .padding(20)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn connection_input<'a>(
+        &'a self,
+        placeholder: &'a str,
+        value: &'a str,
+        on_input: impl Fn(String) -> Message + 'a,
+    ) -> text_input::TextInput<'a, Message> {
+        text_input(placeholder, value)
+            .on_input(on_input)
+            .padding(10)
+            .style(|_, _| text_input_style())
+    }
+}
+
+fn connected_message(result: Result<BusinessComponents, String>) -> Message {
+    match result {
+        Ok(business_components) => {
+            ComponentsMessage::InitializeComponents(UIComponents::new(business_components))
+                .message()
+        }
+        Err(error) => <ConnectionUI as UIComponent>::EventType::ConnectionFailed(error).message(),
+    }
+}
+
+fn text_input_style() -> text_input::Style {
+    text_input::Style {
+        background: Background::Color(Color::from_rgb(0.2, 0.2, 0.2)), // Darker input background
+        border: Border {
+            width: 1.5,
+            color: Color::from_rgb(0.0, 0.74, 0.84),
+            radius: Radius::from(5.0),
+        },
+        placeholder: Color::from_rgb(0.6, 0.6, 0.6), // Color for placeholder text
+        value: Color::WHITE,                         // Color for input text
+        selection: Color::from_rgb(0.0, 0.74, 0.84), // Color for selected text
+        icon: Color::from_rgb(0.8, 0.8, 0.8),        // Color for any input icons
+    }
+}
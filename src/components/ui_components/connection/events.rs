@@ -0,0 +1,18 @@
+use crate::components::ui_components::{component::Event, events::Message};
+
+#[derive(Debug, Clone)]
+pub enum ConnectionMessage {
+    UpdateHost(String),
+    UpdatePort(String),
+    UpdateUsername(String),
+    UpdatePassword(String),
+    UpdateDatabase(String),
+    Connect,
+    ConnectionFailed(String),
+}
+
+impl Event for ConnectionMessage {
+    fn message(self) -> Message {
+        Message::Connection(self)
+    }
+}
@@ -1,17 +1,20 @@
+use crate::components::business_components::component::BLogLevel;
 use crate::components::business_components::components::BusinessConsole;
 use crate::components::ui_components::component::{Event, UIComponent};
 use crate::components::ui_components::console::events::ConsoleMessage;
 use crate::components::ui_components::events::Message;
+use crate::components::ui_components::styles::{button_style, ButtonKind};
 use iced::{
     border,
     border::Radius,
     font::Font,
     widget::{
-        button, column, container, row, scrollable, text, text_input, Column, Container, PickList,
-        Row, Scrollable, Text, TextInput,
+        button, checkbox, column, container, row, scrollable, text, text_input, Column, Container,
+        PickList, Row, Scrollable, Text, TextInput,
     },
     Alignment, Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector,
 };
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 
@@ -25,8 +28,14 @@ pub enum SelectedConsole {
 #[derive(Debug, Clone)]
 pub struct ConsoleUI {
     console: Arc<BusinessConsole>,
-    messages: Vec<String>,
+    messages: Vec<(BLogLevel, String)>,
     selected_console: SelectedConsole, // Track the selected tab
+    wrap_messages: bool,
+    min_log_level: BLogLevel, // messages below this level are hidden
+    search: String,
+    export_path: String,
+    // the typed cap, only applied once it is a positive number
+    max_messages: String,
 }
 
 impl UIComponent for ConsoleUI {
@@ -35,7 +44,8 @@ impl UIComponent for ConsoleUI {
     fn update(&mut self, message: Self::EventType) -> Task<Message> {
         match message {
             Self::EventType::LogMessage(message) => {
-                self.messages.push(message);
+                self.messages.push((BLogLevel::Info, message));
+                self.console.evict_oldest_messages(&mut self.messages);
                 Task::none()
             }
             Self::EventType::ClearMessages(selected_console) => {
@@ -56,6 +66,47 @@ impl UIComponent for ConsoleUI {
                 self.selected_console = selected_console;
                 Task::none()
             }
+            Self::EventType::ToggleWrapMessages => {
+                self.wrap_messages = !self.wrap_messages;
+                Task::none()
+            }
+            Self::EventType::SetLogLevelFilter(min_log_level) => {
+                self.min_log_level = min_log_level;
+                Task::none()
+            }
+            Self::EventType::Search(search) => {
+                self.search = search;
+                Task::none()
+            }
+            Self::EventType::SetMaxMessages(max_messages) => {
+                if let Ok(max_messages) = max_messages.parse::<usize>() {
+                    if max_messages > 0 {
+                        self.set_max_messages(max_messages);
+                    }
+                }
+                self.max_messages = max_messages;
+                Task::none()
+            }
+            Self::EventType::UpdateExportPath(export_path) => {
+                self.export_path = export_path;
+                Task::none()
+            }
+            Self::EventType::ExportLog(path) => {
+                match self.console.export_log(&path, &self.messages) {
+                    Ok(()) => self
+                        .console
+                        .write_info(format!("Exported console log to {}", path.display())),
+                    Err(error) => self.console.write(
+                        BLogLevel::Error,
+                        format!(
+                            "Could not export console log to {}: {}",
+                            path.display(),
+                            error
+                        ),
+                    ),
+                }
+                Task::none()
+            }
         }
     }
 }
@@ -66,83 +117,107 @@ impl ConsoleUI {
             messages: vec![],
             console,
             selected_console: SelectedConsole::UI,
+            wrap_messages: true,
+            min_log_level: BLogLevel::Info,
+            search: String::new(),
+            export_path: String::new(),
+            max_messages: String::new(),
         }
     }
 
+    pub fn set_max_messages(&mut self, max_messages: usize) {
+        self.console.set_max_messages(max_messages);
+        self.console.evict_oldest_messages(&mut self.messages);
+    }
+
+    // also raised as an error once the current message is handled
+    pub fn write_warning(&self, message: String) {
+        self.console.write(BLogLevel::Warning, message);
+    }
+
+    pub fn take_error(&self) -> Option<String> {
+        self.console.take_error()
+    }
+
     pub fn content(&self) -> Column<'_, Message> {
         let mut console_display = Column::new().spacing(10).padding(10);
 
         // Select which messages to display based on the selected console tab
-        match self.selected_console {
-            SelectedConsole::UI => {
-                for message in &self.messages {
-                    let text_widget = Text::new(message)
-                        .size(16)
-                        .width(Length::Fill)
-                        .color(Color::from_rgb(0.8, 0.8, 0.8));
-                    let message_container = Container::new(text_widget)
-                        .padding(10)
-                        .width(Length::Fill)
-                        .style(|_| console_message_style());
-                    console_display = console_display.push(message_container);
-                }
-            }
-            SelectedConsole::Business => {
-                // Display business console messages
-                for message in self.console.get_messages() {
-                    let text_widget = Text::new(message)
-                        .size(16)
-                        .width(Length::Fill)
-                        .color(Color::from_rgb(0.8, 0.8, 0.8));
-                    let message_container = Container::new(text_widget)
-                        .padding(10)
-                        .width(Length::Fill)
-                        .style(|_| console_message_style());
-                    console_display = console_display.push(message_container);
-                }
-            }
-            SelectedConsole::Database => {
-                // Display database messages
-                for message in self.console.get_database_messages() {
-                    let text_widget = Text::new(message)
-                        .size(16)
-                        .width(Length::Fill)
-                        .color(Color::from_rgb(0.8, 0.8, 0.8));
-                    let message_container = Container::new(text_widget)
-                        .padding(10)
-                        .width(Length::Fill)
-                        .style(|_| console_message_style());
-                    console_display = console_display.push(message_container);
-                }
+        let messages = match self.selected_console {
+            SelectedConsole::UI => self.messages.clone(),
+            SelectedConsole::Business => self.console.get_messages(),
+            SelectedConsole::Database => self.console.get_database_messages(),
+        };
+        // the search only hides messages, the stored messages are left untouched
+        let search = self.search.to_lowercase();
+        for (level, message) in messages {
+            if level >= self.min_log_level && message.to_lowercase().contains(&search) {
+                console_display = console_display.push(self.console_message(level, message));
             }
         }
 
         // Wrap the messages in a scrollable container
-        let scrollable_console = scrollable(
-            container(console_display)
-                .style(|_| console_style())
-                .padding(10),
-        )
-        .height(Length::Fill)
-        .width(400)
-        .style(|_, _| scrollbar_style());
+        let scrollable_console =
+            scrollable(container(console_display).style(console_style).padding(10))
+                .direction(if self.wrap_messages {
+                    scrollable::Direction::Vertical(scrollable::Scrollbar::new())
+                } else {
+                    scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::new(),
+                        horizontal: scrollable::Scrollbar::new(),
+                    }
+                })
+                .height(Length::Fill)
+                .width(400)
+                .style(|theme, _| scrollbar_style(theme));
+
+        let wrap_messages_checkbox = checkbox("Wrap Messages", self.wrap_messages)
+            .on_toggle(|_| <ConsoleUI as UIComponent>::EventType::ToggleWrapMessages.message());
+
+        let log_level_filter = Row::new()
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .push(Text::new("Minimum level"))
+            .push(PickList::new(
+                BLogLevel::ALL,
+                Some(self.min_log_level),
+                |min_log_level| {
+                    <ConsoleUI as UIComponent>::EventType::SetLogLevelFilter(min_log_level)
+                        .message()
+                },
+            ));
 
         // Tab switch buttons with styling
         let ui_button = button(Text::new("UI Messages"))
-            .style(|_, _| button_style(self.selected_console == SelectedConsole::UI))
+            .style(|theme, _| {
+                button_style(
+                    theme,
+                    ButtonKind::tab(self.selected_console == SelectedConsole::UI),
+                )
+            })
             .on_press(
                 <ConsoleUI as UIComponent>::EventType::SwitchTab(SelectedConsole::UI).message(),
             );
 
         let business_button = button(Text::new("Business Messages"))
-            .style(|_, _| button_style(self.selected_console == SelectedConsole::Business))
+            .style(|theme, _| {
+                button_style(
+                    theme,
+                    ButtonKind::tab(self.selected_console == SelectedConsole::Business),
+                )
+            })
             .on_press(
                 <ConsoleUI as UIComponent>::EventType::SwitchTab(SelectedConsole::Business)
                     .message(),
             );
 
         let database_button = button(Text::new("Database Messages"))
-            .style(|_, _| button_style(self.selected_console == SelectedConsole::Database))
+            .style(|theme, _| {
+                button_style(
+                    theme,
+                    ButtonKind::tab(self.selected_console == SelectedConsole::Database),
+                )
+            })
             .on_press(
                 <ConsoleUI as UIComponent>::EventType::SwitchTab(SelectedConsole::Database)
                     .message(),
@@ -176,24 +251,104 @@ impl ConsoleUI {
                 .push(database_button),
         ); // Row for tab buttons
 
+        column = column.push(wrap_messages_checkbox);
+        column = column.push(log_level_filter);
+        // older messages of every tab are dropped past the cap
+        column = column.push(
+            Row::new()
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .push(Text::new("Keep last"))
+                .push(
+                    text_input(&self.console.max_messages().to_string(), &self.max_messages)
+                        .on_input(|max_messages| {
+                            <ConsoleUI as UIComponent>::EventType::SetMaxMessages(max_messages)
+                                .message()
+                        })
+                        .width(80)
+                        .padding(5),
+                )
+                .push(Text::new("messages")),
+        );
+        column = column.push(
+            text_input("Search messages", &self.search)
+                .on_input(|search| <ConsoleUI as UIComponent>::EventType::Search(search).message())
+                .padding(5),
+        );
         column = column.push(scrollable_console);
 
         // Add the clear button if it exists
         column = column.push(clear_button);
 
+        // every tab is exported together so the log keeps the full session
+        column = column.push(
+            Row::new()
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .push(
+                    text_input("Log file path", &self.export_path)
+                        .on_input(|export_path| {
+                            <ConsoleUI as UIComponent>::EventType::UpdateExportPath(export_path)
+                                .message()
+                        })
+                        .padding(5),
+                )
+                .push(button(Text::new("Export Log")).padding(10).on_press_maybe(
+                    (!self.export_path.is_empty()).then(|| {
+                        <ConsoleUI as UIComponent>::EventType::ExportLog(PathBuf::from(
+                            &self.export_path,
+                        ))
+                        .message()
+                    }),
+                )),
+        );
+
         column
     }
+
+    fn console_message<'a>(&'a self, level: BLogLevel, message: String) -> Container<'a, Message> {
+        let text_widget = Text::new(message)
+            .size(16)
+            .style(move |theme: &Theme| text::Style {
+                color: Some(log_level_color(theme, level)),
+            });
+        // long single tokens still wrap when wrapping is enabled
+        let text_widget = if self.wrap_messages {
+            text_widget
+                .width(Length::Fill)
+                .wrapping(text::Wrapping::WordOrGlyph)
+        } else {
+            text_widget.wrapping(text::Wrapping::None)
+        };
+        let message_container = Container::new(text_widget).padding(10);
+        if self.wrap_messages {
+            message_container
+                .width(Length::Fill)
+                .style(console_message_style)
+        } else {
+            message_container.style(console_message_style)
+        }
+    }
 }
 
 // ======================== STYLES ========================
 
+fn log_level_color(theme: &Theme, level: BLogLevel) -> Color {
+    match level {
+        BLogLevel::Info => theme.extended_palette().background.weak.text,
+        BLogLevel::Warning => Color::from_rgb(0.9, 0.65, 0.1), // Amber text, readable on either theme
+        BLogLevel::Error => theme.palette().danger,
+    }
+}
+
 // Style for the individual console messages
-fn console_message_style() -> container::Style {
+fn console_message_style(theme: &Theme) -> container::Style {
+    let palette = theme.extended_palette();
     iced::widget::container::Style {
-        background: Some(Background::Color(Color::from_rgb(0.15, 0.15, 0.15))),
-        text_color: Some(Color::from_rgb(0.8, 0.8, 0.8)), // Light gray text
+        background: Some(Background::Color(palette.background.weak.color)),
+        text_color: Some(palette.background.weak.text),
         border: Border {
-            color: Color::from_rgb(0.4, 0.4, 0.4),
+            color: palette.background.strong.color,
             width: 1.0,
             radius: Radius::from(5.0),
         },
@@ -206,15 +361,16 @@ fn console_message_style() -> container::Style {
 }
 
 // Style for the overall console container
-fn console_style() -> container::Style {
+fn console_style(theme: &Theme) -> container::Style {
+    let palette = theme.extended_palette();
     iced::widget::container::Style {
-        background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.1))),
+        background: Some(Background::Color(palette.background.base.color)),
         border: Border {
-            color: Color::from_rgb(0.3, 0.3, 0.3),
+            color: palette.background.strong.color,
             width: 2.0,
             radius: Radius::new(0),
         },
-        text_color: Some(Color::from_rgb(0.9, 0.9, 0.9)),
+        text_color: Some(palette.background.base.text),
         shadow: Shadow {
             color: Color::BLACK,
             offset: Vector::new(0.0, 4.0),
@@ -224,11 +380,12 @@ fn console_style() -> container::Style {
 }
 
 // Style for the scrollable area
-fn scrollbar_style() -> scrollable::Style {
+fn scrollbar_style(theme: &Theme) -> scrollable::Style {
+    let palette = theme.extended_palette();
     scrollable::Style {
         container: container::Style {
             text_color: None,
-            background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.1))),
+            background: Some(Background::Color(palette.background.base.color)),
             border: Border {
                 color: Color::TRANSPARENT,
                 width: 0.0,
@@ -241,9 +398,9 @@ fn scrollbar_style() -> scrollable::Style {
             },
         },
         vertical_rail: scrollable::Rail {
-            background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.2))),
+            background: Some(Background::Color(palette.background.weak.color)),
             border: Border {
-                color: Color::from_rgb(0.3, 0.3, 0.3),
+                color: palette.background.strong.color,
                 width: 1.0,
                 radius: Radius::from(3.0),
             },
@@ -257,9 +414,9 @@ fn scrollbar_style() -> scrollable::Style {
             },
         },
         horizontal_rail: scrollable::Rail {
-            background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.2))),
+            background: Some(Background::Color(palette.background.weak.color)),
             border: Border {
-                color: Color::from_rgb(0.3, 0.3, 0.3),
+                color: palette.background.strong.color,
                 width: 1.0,
                 radius: Radius::from(3.0),
             },
@@ -272,40 +429,6 @@ fn scrollbar_style() -> scrollable::Style {
                 },
             },
         },
-        gap: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.1))),
-    }
-}
-
-fn button_style(is_selected: bool) -> button::Style {
-    if is_selected {
-        button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.3, 0.7, 0.3))), // Light green for selected
-            border: Border {
-                color: Color::from_rgb(0.2, 0.5, 0.2), // Darker green border
-                width: 2.0,                            // Slightly thicker border for emphasis
-                radius: Radius::from(8.0),             // Rounded corners
-            },
-            text_color: Color::WHITE, // White text for readability
-            shadow: Shadow {
-                color: Color::BLACK,           // Dark shadow
-                offset: Vector::new(0.0, 2.0), // Slight shadow offset
-                blur_radius: 4.0,              // Subtle blur
-            },
-        }
-    } else {
-        button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.3, 0.3, 0.3))), // Dark gray for unselected
-            border: Border {
-                color: Color::from_rgb(0.2, 0.2, 0.2), // Slightly lighter gray border
-                width: 1.0,                            // Standard border width
-                radius: Radius::from(5.0),             // Slightly less rounded corners
-            },
-            text_color: Color::WHITE, // White text for contrast
-            shadow: Shadow {
-                color: Color::BLACK,           // Minimal shadow
-                offset: Vector::new(0.0, 1.0), // Small shadow offset
-                blur_radius: 2.0,              // Subtle blur for unselected state
-            },
-        }
+        gap: Some(Background::Color(palette.background.base.color)),
     }
 }
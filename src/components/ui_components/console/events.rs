@@ -1,12 +1,20 @@
+use crate::components::business_components::component::BLogLevel;
 use crate::components::ui_components::component::Event;
 use crate::components::ui_components::console::console::SelectedConsole;
 use crate::components::ui_components::events::Message;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub enum ConsoleMessage {
     LogMessage(String),
     SwitchTab(SelectedConsole),
     ClearMessages(SelectedConsole),
+    ToggleWrapMessages,
+    SetLogLevelFilter(BLogLevel),
+    Search(String),
+    SetMaxMessages(String),
+    UpdateExportPath(String),
+    ExportLog(PathBuf),
 }
 
 impl Event for ConsoleMessage {
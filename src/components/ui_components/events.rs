@@ -1,7 +1,10 @@
 use crate::components::ui_components::components::{ComponentsMessage, UIComponents};
 use crate::components::ui_components::{
-    console::events::ConsoleMessage, home::events::HomeMessage, tables::events::TablesMessage,
+    connection::events::ConnectionMessage, console::events::ConsoleMessage,
+    home::events::HomeMessage, query_runner::events::QueryRunnerMessage,
+    tables::events::TablesMessage,
 };
+use iced::{window, Size};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -9,4 +12,15 @@ pub enum Message {
     Home(HomeMessage),
     Tables(TablesMessage),
     Console(ConsoleMessage),
+    QueryRunner(QueryRunnerMessage),
+    Connection(ConnectionMessage),
+    ShowError(String),
+    DismissError,
+    BusyStarted,
+    BusyFinished,
+    AdvanceLoadingIndicator,
+    ToggleTheme,
+    WindowResized(Size),
+    // the session is saved before the window is closed
+    WindowCloseRequested(window::Id),
 }
@@ -4,6 +4,7 @@ use crate::components::ui_components::{component::Event, events::Message};
 pub enum HomeMessage {
     InitializeComponent,
     ComponentInitialized,
+    Refresh,
 }
 
 impl Event for HomeMessage {
@@ -3,8 +3,10 @@ use crate::components::business_components::{
 };
 use crate::components::ui_components::{
     component::{Event, UIComponent},
+    components::ComponentsMessage,
     events::Message,
     home::events::HomeMessage,
+    tables::events::TablesMessage,
 };
 use iced::{
     widget::{
@@ -35,6 +37,16 @@ impl UIComponent for HomeUI {
                 )
             }
             Self::EventType::ComponentInitialized => Task::none(),
+            // the tables ask before discarding uncommitted edits
+            Self::EventType::Refresh => {
+                let home = self.home.clone();
+                Task::perform(
+                    async move {
+                        home.initialize_component().await;
+                    },
+                    |_| TablesMessage::Refresh.message(),
+                )
+            }
         }
     }
 }
@@ -44,8 +56,58 @@ impl HomeUI {
         Self { home }
     }
 
-    pub fn content<'a>(&'a self) -> Element<'a, Message> {
-        self.title()
+    pub fn content<'a>(&'a self, recent_tables: Vec<String>) -> Element<'a, Message> {
+        column![
+            row![
+                self.title(),
+                button("Refresh").on_press(<HomeUI as UIComponent>::EventType::Refresh.message())
+            ]
+            .spacing(10),
+            self.recent_tables(recent_tables),
+            self.shortcuts()
+        ]
+        .spacing(10)
+        .padding(10)
+        .into()
+    }
+
+    fn recent_tables<'a>(&'a self, recent_tables: Vec<String>) -> Element<'a, Message> {
+        if recent_tables.is_empty() {
+            return Column::new().into();
+        }
+        recent_tables
+            .into_iter()
+            .fold(
+                Column::new()
+                    .spacing(5)
+                    .push(text("Recent tables").size(14)),
+                |recent_tables, table_name| {
+                    recent_tables.push(
+                        button(text(table_name.clone()).size(12))
+                            .on_press(ComponentsMessage::OpenTableData(table_name).message())
+                            .padding(5),
+                    )
+                },
+            )
+            .into()
+    }
+
+    // the bindings handled by the keyboard subscription in main
+    fn shortcuts<'a>(&'a self) -> Element<'a, Message> {
+        [
+            "Ctrl+N  New table",
+            "Ctrl+S  Save table data",
+            "Ctrl+F  Search tables",
+            "Esc  Close the table form",
+            "Tab / Shift+Tab  Next / previous table cell",
+            "Up / Down  Table cell above / below",
+            "Enter  Commit the table cell and move down",
+        ]
+        .into_iter()
+        .fold(Column::new().spacing(2), |shortcuts, shortcut| {
+            shortcuts.push(text(shortcut).size(12))
+        })
+        .into()
     }
 
     fn title<'a>(&'a self) -> Element<'a, Message> {
@@ -0,0 +1,24 @@
+use crate::components::ui_components::events::Message;
+use iced::{
+    widget::{row, text},
+    Element, Task,
+};
+
+const SPINNER_FRAMES: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
+
+// the indicator stays visible until the wrapped task has produced all of its messages
+pub fn busy_task(task: Task<Message>) -> Task<Message> {
+    Task::done(Message::BusyStarted)
+        .chain(task)
+        .chain(Task::done(Message::BusyFinished))
+}
+
+pub fn loading_indicator<'a>(frame: usize) -> Element<'a, Message> {
+    row![
+        text(SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]).size(16),
+        text("Working...").size(14)
+    ]
+    .spacing(5)
+    .padding(5)
+    .into()
+}
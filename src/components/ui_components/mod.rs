@@ -1,6 +1,10 @@
 pub mod component;
 pub mod components;
+pub mod connection;
 pub mod console;
 pub mod events;
 pub mod home;
+pub mod loading;
+pub mod query_runner;
+pub mod styles;
 pub mod tables;
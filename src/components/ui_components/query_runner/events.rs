@@ -0,0 +1,20 @@
+use crate::components::business_components::component::BTableInsertedData;
+use crate::components::ui_components::{component::Event, events::Message};
+use iced::widget::text_editor;
+
+#[derive(Debug, Clone)]
+pub enum QueryRunnerMessage {
+    EditQuery(text_editor::Action),
+    ToggleAllowWrites(bool),
+    RunQuery,
+    SetQueryResult(Option<BTableInsertedData>),
+    UpdateExportPath(String),
+    ExportCsv,
+    CsvExported,
+}
+
+impl Event for QueryRunnerMessage {
+    fn message(self) -> Message {
+        Message::QueryRunner(self)
+    }
+}
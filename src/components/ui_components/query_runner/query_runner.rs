@@ -0,0 +1,247 @@
+use crate::components::business_components::{
+    component::BTableInsertedData, components::BusinessQueryRunner,
+};
+use crate::components::ui_components::{
+    component::{Event, UIComponent},
+    events::Message,
+    query_runner::events::QueryRunnerMessage,
+};
+use iced::{
+    alignment::Vertical,
+    border::Radius,
+    widget::{button, checkbox, container, scrollable, text, text_editor, text_input, Column, Row},
+    Background, Border, Color, Element, Length, Task,
+};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub struct QueryRunnerUI {
+    query_runner: Arc<BusinessQueryRunner>,
+    query: text_editor::Content,
+    allow_writes: bool,
+    is_running: bool,
+    // None until a query succeeds, errors are shown in the database console
+    query_result: Option<BTableInsertedData>,
+    export_path: String,
+    is_exporting: bool,
+}
+
+// the editor content isn't Clone so it is rebuilt from its text
+impl Clone for QueryRunnerUI {
+    fn clone(&self) -> Self {
+        Self {
+            query_runner: self.query_runner.clone(),
+            query: text_editor::Content::with_text(&self.query.text()),
+            allow_writes: self.allow_writes,
+            is_running: self.is_running,
+            query_result: self.query_result.clone(),
+            export_path: self.export_path.clone(),
+            is_exporting: self.is_exporting,
+        }
+    }
+}
+
+impl fmt::Debug for QueryRunnerUI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("QueryRunnerUI")
+            .field("query", &self.query.text())
+            .field("allow_writes", &self.allow_writes)
+            .field("is_running", &self.is_running)
+            .field("query_result", &self.query_result)
+            .field("export_path", &self.export_path)
+            .field("is_exporting", &self.is_exporting)
+            .finish()
+    }
+}
+
+impl UIComponent for QueryRunnerUI {
+    type EventType = QueryRunnerMessage;
+
+    fn update(&mut self, message: Self::EventType) -> Task<Message> {
+        match message {
+            Self::EventType::EditQuery(action) => {
+                self.query.perform(action);
+                Task::none()
+            }
+            Self::EventType::ToggleAllowWrites(allow_writes) => {
+                self.allow_writes = allow_writes;
+                Task::none()
+            }
+            Self::EventType::RunQuery => {
+                self.is_running = true;
+                let query_runner = self.query_runner.clone();
+                let query = self.query.text();
+                let allow_writes = self.allow_writes;
+                Task::perform(
+                    async move { query_runner.run_query(query, allow_writes).await },
+                    |query_result| Self::EventType::SetQueryResult(query_result).message(),
+                )
+            }
+            Self::EventType::SetQueryResult(query_result) => {
+                self.is_running = false;
+                self.query_result = query_result;
+                Task::none()
+            }
+            Self::EventType::UpdateExportPath(export_path) => {
+                self.export_path = export_path;
+                Task::none()
+            }
+            // the outcome is written to the database console
+            Self::EventType::ExportCsv => {
+                let Some(query_result) = self.query_result.clone() else {
+                    return Task::none();
+                };
+                self.is_exporting = true;
+                let query_runner = self.query_runner.clone();
+                let path = PathBuf::from(&self.export_path);
+                Task::perform(
+                    async move { query_runner.export_csv(&query_result, path).await },
+                    |_| Self::EventType::CsvExported.message(),
+                )
+            }
+            Self::EventType::CsvExported => {
+                self.is_exporting = false;
+                Task::none()
+            }
+        }
+    }
+}
+
+impl QueryRunnerUI {
+    pub fn new(query_runner: Arc<BusinessQueryRunner>) -> Self {
+        Self {
+            query_runner,
+            query: text_editor::Content::new(),
+            allow_writes: false,
+            is_running: false,
+            query_result: None,
+            export_path: String::new(),
+            is_exporting: false,
+        }
+    }
+
+    pub fn content<'a>(&'a self) -> Element<'a, Message> {
+        let query_input = text_editor(&self.query)
+            .placeholder("SELECT * FROM ...")
+            .height(120)
+            .on_action(|action| {
+                <QueryRunnerUI as UIComponent>::EventType::EditQuery(action).message()
+            });
+
+        let allow_writes_checkbox = checkbox(
+            "Allow writes (statements run in one transaction)",
+            self.allow_writes,
+        )
+        .on_toggle(|allow_writes| {
+            <QueryRunnerUI as UIComponent>::EventType::ToggleAllowWrites(allow_writes).message()
+        });
+
+        let run_button = button(text(if self.is_running { "Running..." } else { "Run" }))
+            .padding(10)
+            .on_press_maybe(
+                (!self.is_running && !self.query.text().trim().is_empty())
+                    .then(|| <QueryRunnerUI as UIComponent>::EventType::RunQuery.message()),
+            );
+
+        Column::new()
+            .spacing(10)
+            .padding(10)
+            .push(text("Query Runner").size(24))
+            .push(query_input)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_y(Vertical::Center)
+                    .push(run_button)
+                    .push(allow_writes_checkbox),
+            )
+            .push(self.query_result_table())
+            .into()
+    }
+
+    fn query_result_table<'a>(&'a self) -> Element<'a, Message> {
+        let Some(query_result) = &self.query_result else {
+            return text("Run a query to see its results").into();
+        };
+        let header =
+            query_result
+                .column_names
+                .iter()
+                .fold(Row::new().spacing(10), |header, column_name| {
+                    header.push(container(text(column_name.clone()).size(16)).width(120))
+                });
+        let rows = query_result
+            .rows
+            .iter()
+            .fold(Column::new().spacing(5), |rows, row| {
+                rows.push(row.iter().fold(Row::new().spacing(10), |cells, value| {
+                    let value = value.clone().unwrap_or(String::from("NULL"));
+                    cells.push(container(text(value).size(14)).width(120))
+                }))
+            });
+        container(
+            Column::new()
+                .spacing(10)
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .align_y(Vertical::Center)
+                        .push(text(format!("{} rows", query_result.total_row_count)).size(14))
+                        .push(self.export_csv_controls()),
+                )
+                .push(
+                    scrollable(Column::new().spacing(5).push(header).push(rows)).direction(
+                        scrollable::Direction::Both {
+                            vertical: scrollable::Scrollbar::new(),
+                            horizontal: scrollable::Scrollbar::new(),
+                        },
+                    ),
+                ),
+        )
+        .padding(10)
+        .height(Length::Fixed(300.0))
+        .style(|_| query_result_style())
+        .into()
+    }
+
+    fn export_csv_controls<'a>(&'a self) -> Row<'a, Message> {
+        Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(
+                text_input("Path to CSV file", &self.export_path)
+                    .on_input(|export_path| {
+                        <QueryRunnerUI as UIComponent>::EventType::UpdateExportPath(export_path)
+                            .message()
+                    })
+                    .padding(5)
+                    .width(250),
+            )
+            .push(
+                button(text(if self.is_exporting {
+                    "Exporting..."
+                } else {
+                    "Export CSV"
+                }))
+                .padding(5)
+                .on_press_maybe(
+                    (!self.is_exporting && !self.export_path.trim().is_empty())
+                        .then(|| <QueryRunnerUI as UIComponent>::EventType::ExportCsv.message()),
+                ),
+            )
+    }
+}
+
+// ======================== STYLES ========================
+fn query_result_style() -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.15))),
+        border: Border {
+            color: Color::from_rgb(0.3, 0.3, 0.3),
+            width: 1.0,
+            radius: Radius::from(5.0),
+        },
+        ..container::Style::default()
+    }
+}
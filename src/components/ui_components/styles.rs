@@ -0,0 +1,168 @@
+use iced::{
+    border::Radius,
+    widget::{button, container, text_input},
+    Background, Border, Color, Shadow, Theme, Vector,
+};
+
+// shared by the views so the same kind of widget looks the same everywhere
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ButtonKind {
+    // the main action of a form
+    Primary,
+    // actions that add to what is being edited
+    Accent,
+    // actions that remove something
+    Danger,
+    // tabs, the one whose content is shown is selected
+    Selected,
+    Unselected,
+}
+
+impl ButtonKind {
+    pub fn tab(is_selected: bool) -> Self {
+        if is_selected {
+            ButtonKind::Selected
+        } else {
+            ButtonKind::Unselected
+        }
+    }
+}
+
+pub fn button_style(theme: &Theme, kind: ButtonKind) -> button::Style {
+    match kind {
+        ButtonKind::Primary => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.0, 0.5, 0.9))),
+            border: Border {
+                color: Color::from_rgb(0.0, 0.4, 0.7),
+                width: 2.0,
+                radius: Radius::from(8.0),
+            },
+            text_color: Color::WHITE,
+            shadow: Shadow {
+                color: Color::BLACK,
+                offset: Vector::new(0.0, 3.0),
+                blur_radius: 7.0,
+            },
+        },
+        ButtonKind::Accent => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.0, 0.75, 0.65))),
+            border: Border {
+                color: Color::from_rgb(0.0, 0.6, 0.5),
+                width: 2.0,
+                radius: Radius::from(5.0),
+            },
+            text_color: Color::WHITE,
+            shadow: Shadow {
+                color: Color::BLACK,
+                offset: Vector::new(0.0, 3.0),
+                blur_radius: 5.0,
+            },
+        },
+        ButtonKind::Danger => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.8, 0.2, 0.2))), // Soft red background
+            border: Border {
+                color: Color::from_rgb(0.6, 0.1, 0.1), // Dark red border
+                width: 2.0,
+                radius: Radius::from(5.0),
+            },
+            text_color: Color::WHITE, // White text for contrast
+            shadow: Shadow {
+                color: Color::BLACK,
+                offset: Vector::new(0.0, 3.0),
+                blur_radius: 5.0,
+            },
+        },
+        ButtonKind::Selected => button::Style {
+            background: Some(Background::Color(Color::from_rgb(0.3, 0.7, 0.3))), // Light green for selected
+            border: Border {
+                color: Color::from_rgb(0.2, 0.5, 0.2), // Darker green border
+                width: 2.0,                            // Slightly thicker border for emphasis
+                radius: Radius::from(8.0),             // Rounded corners
+            },
+            text_color: Color::WHITE, // White text for readability
+            shadow: Shadow {
+                color: Color::BLACK,           // Dark shadow
+                offset: Vector::new(0.0, 2.0), // Slight shadow offset
+                blur_radius: 4.0,              // Subtle blur
+            },
+        },
+        ButtonKind::Unselected => {
+            let palette = theme.extended_palette();
+            button::Style {
+                background: Some(Background::Color(palette.background.strong.color)), // Muted for unselected
+                border: Border {
+                    color: palette.background.weak.color, // Subtle border
+                    width: 1.0,                           // Standard border width
+                    radius: Radius::from(5.0),            // Slightly less rounded corners
+                },
+                text_color: palette.background.strong.text, // Readable on the muted background
+                shadow: Shadow {
+                    color: Color::BLACK,           // Minimal shadow
+                    offset: Vector::new(0.0, 1.0), // Small shadow offset
+                    blur_radius: 2.0,              // Subtle blur for unselected state
+                },
+            }
+        }
+    }
+}
+
+pub fn container_style(theme: &Theme) -> container::Style {
+    let palette = theme.extended_palette();
+    container::Style {
+        background: Some(Background::Color(palette.background.base.color)), // Background color
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 1.5,
+            radius: Radius::from(5.0),
+        },
+        text_color: Some(palette.background.base.text), // Text color for the content inside the container
+        shadow: Shadow {
+            color: Color::BLACK,
+            offset: Vector::new(0.0, 2.0),
+            blur_radius: 5.0,
+        },
+    }
+}
+
+pub fn tooltip_style(theme: &Theme) -> container::Style {
+    let palette = theme.extended_palette();
+    container::Style {
+        background: Some(Background::Color(palette.background.strong.color)),
+        border: Border {
+            color: Color::from_rgb(0.0, 0.6, 0.9),
+            width: 1.0,
+            radius: Radius::from(6.0),
+        },
+        text_color: Some(palette.background.strong.text),
+        shadow: Shadow::default(),
+    }
+}
+
+pub fn text_input_style(theme: &Theme) -> text_input::Style {
+    let palette = theme.extended_palette();
+    text_input::Style {
+        background: Background::Color(palette.background.weak.color), // Input background
+        border: Border {
+            width: 1.5,
+            color: Color::from_rgb(0.0, 0.74, 0.84),
+            radius: Radius::from(5.0),
+        },
+        placeholder: palette.background.strong.color, // Color for placeholder text
+        value: palette.background.weak.text,          // Color for input text
+        selection: Color::from_rgb(0.0, 0.74, 0.84),  // Color for selected text
+        icon: palette.background.weak.text,           // Color for any input icons
+    }
+}
+
+// highlights input the form won't accept
+pub fn invalid_text_input_style(theme: &Theme) -> text_input::Style {
+    text_input::Style {
+        border: Border {
+            width: 1.5,
+            color: theme.palette().danger,
+            radius: Radius::from(5.0),
+        },
+        ..text_input_style(theme)
+    }
+}
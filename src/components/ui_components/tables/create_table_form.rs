@@ -1,23 +1,28 @@
 use crate::components::business_components::component::{
-    BColumn, BConstraint, BDataType, BTableGeneral, BTableIn, BusinessComponent,
+    BColumn, BCompositeForeignKey, BConstraint, BDataType, BReferentialAction, BTableGeneral,
+    BTableIn, BusinessComponent,
 };
 use crate::components::business_components::components::BusinessTables;
 use crate::components::ui_components::{
     component::{Event, UIComponent},
     events::Message,
-    tables::events::CreateTableFormMessage,
+    styles::{
+        button_style, container_style, invalid_text_input_style, text_input_style, tooltip_style,
+        ButtonKind,
+    },
+    tables::events::{CreateTableFormMessage, ReferencedKey, TablesMessage},
     tables::foreign_key_dropdown::{ForeignKeyDropDownUI, ForeignKeyDropdownEvents},
 };
 use iced::{
     alignment,
     alignment::{Alignment, Vertical},
-    border::Radius,
     widget::{
-        button, checkbox, column, container, row, scrollable, text, text_input, Button, Checkbox,
-        Column, PickList, Row, Text,
+        button, checkbox, column, container, row, scrollable, text, text_input, tooltip, Button,
+        Checkbox, Column, PickList, Row, Text,
     },
-    Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector,
+    Element, Length, Task, Theme,
 };
+use std::collections::HashMap;
 use std::iter::zip;
 use std::sync::Arc;
 use tokio::sync::Mutex as AsyncMutex;
@@ -32,8 +37,13 @@ impl ForeignKeyDropdownEvents for CreateTableFormForeignKeyDropdownEvents {
         referenced_table_name: String,
         referenced_column_name: String,
     ) -> Message {
-        CreateTableFormMessage::AddForeignKey(index, referenced_table_name, referenced_column_name)
-            .message()
+        CreateTableFormMessage::AddForeignKey(
+            index,
+            referenced_table_name,
+            referenced_column_name,
+            None,
+        )
+        .message()
     }
     fn remove_foreign_key(&self, index: usize) -> Message {
         CreateTableFormMessage::RemoveForeignKey(index).message()
@@ -47,6 +57,8 @@ impl ForeignKeyDropdownEvents for CreateTableFormForeignKeyDropdownEvents {
 pub struct CreateTableFormUI {
     create_table_input: BTableIn,
     tables: Arc<BusinessTables>,
+    // keys of the tables picked for composite foreign keys
+    referenced_table_keys: HashMap<String, Vec<ReferencedKey>>,
     active_foreign_key_dropdown:
         Option<ForeignKeyDropDownUI<CreateTableFormForeignKeyDropdownEvents>>, // column index that wants the foreign key dropdown
                                                                                // activated
@@ -67,6 +79,22 @@ impl UIComponent for CreateTableFormUI {
                 }
                 Task::none()
             }
+            Self::EventType::MoveColumn(from, to) => {
+                let column_count = self.create_table_input.columns.len();
+                if from < column_count && to < column_count {
+                    // the constraints are part of the column so they move with it
+                    self.create_table_input.columns.swap(from, to);
+                    // an open foreign key dropdown keeps pointing at its column
+                    if let Some(foreign_key_dropdown) = &mut self.active_foreign_key_dropdown {
+                        if foreign_key_dropdown.index == from {
+                            foreign_key_dropdown.index = to;
+                        } else if foreign_key_dropdown.index == to {
+                            foreign_key_dropdown.index = from;
+                        }
+                    }
+                }
+                Task::none()
+            }
             Self::EventType::UpdateColumnName(index, input) => {
                 if let Some(column) = self.create_table_input.columns.get_mut(index) {
                     column.name = input;
@@ -79,6 +107,32 @@ impl UIComponent for CreateTableFormUI {
                 }
                 Task::none()
             }
+            Self::EventType::UpdateColumnVarcharLength(index, input) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    if let Some(length) = input.parse::<u32>().ok().filter(|length| *length > 0) {
+                        column.datatype = BDataType::VARCHAR(length);
+                    }
+                }
+                Task::none()
+            }
+            Self::EventType::UpdateColumnDefault(index, input) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    column.default_value = Some(input);
+                }
+                Task::none()
+            }
+            Self::EventType::UpdateColumnComment(index, input) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    column.comment = if input.is_empty() { None } else { Some(input) };
+                }
+                Task::none()
+            }
+            Self::EventType::UpdateColumnGenerated(index, input) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    column.generated = if input.is_empty() { None } else { Some(input) };
+                }
+                Task::none()
+            }
             Self::EventType::SetOrRemovePrimaryKey(index) => {
                 if let Some(column) = self.create_table_input.columns.get_mut(index) {
                     if let Some(existing_index) = column
@@ -93,31 +147,82 @@ impl UIComponent for CreateTableFormUI {
                 }
                 Task::none()
             }
+            // an empty default is kept, it is the empty string for text columns and NULL otherwise
+            Self::EventType::SetOrRemoveDefault(index) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    column.default_value = if column.default_value.is_some() {
+                        None
+                    } else {
+                        Some(String::new())
+                    };
+                }
+                Task::none()
+            }
+            Self::EventType::SetOrRemoveNotNull(index) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    if let Some(existing_index) = column
+                        .constraints
+                        .iter()
+                        .position(|constraint| matches!(constraint, BConstraint::NotNull))
+                    {
+                        column.constraints.remove(existing_index);
+                    } else {
+                        column.constraints.push(BConstraint::NotNull);
+                    }
+                }
+                Task::none()
+            }
+            Self::EventType::SetOrRemoveUnique(index) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    if let Some(existing_index) = column
+                        .constraints
+                        .iter()
+                        .position(|constraint| matches!(constraint, BConstraint::Unique))
+                    {
+                        column.constraints.remove(existing_index);
+                    } else {
+                        column.constraints.push(BConstraint::Unique);
+                    }
+                }
+                Task::none()
+            }
+            Self::EventType::UpdateCheckConstraint(index, expression) => {
+                if let Some(column) = self.create_table_input.columns.get_mut(index) {
+                    column
+                        .constraints
+                        .retain(|constraint| !matches!(constraint, BConstraint::Check(_)));
+                    if !expression.is_empty() {
+                        column.constraints.push(BConstraint::Check(expression));
+                    }
+                }
+                Task::none()
+            }
             Self::EventType::AddForeignKey(
                 index,
                 referenced_table_name,
                 referenced_column_name,
+                on_delete,
             ) => {
                 if let Some(column) = self.create_table_input.columns.get_mut(index) {
-                    if let Some(existing_index) = column.constraints.iter().position(|constraint| {
-                        matches!(
-                            constraint,
-                            BConstraint::ForeignKey(existing_table_name, existing_column_name)
-                        )
-                    }) {
+                    let mut existing_on_delete = None;
+                    if let Some(existing_index) = column
+                        .constraints
+                        .iter()
+                        .position(|constraint| matches!(constraint, BConstraint::ForeignKey { .. }))
+                    {
                         // Remove the foreign key constraint if it exists
-                        column.constraints.remove(existing_index);
-                        column.constraints.push(BConstraint::ForeignKey(
-                            referenced_table_name,
-                            referenced_column_name,
-                        ));
-                    } else {
-                        // Add the foreign key constraint if it does not exist
-                        column.constraints.push(BConstraint::ForeignKey(
-                            referenced_table_name,
-                            referenced_column_name,
-                        ));
+                        if let BConstraint::ForeignKey { on_delete, .. } =
+                            column.constraints.remove(existing_index)
+                        {
+                            existing_on_delete = Some(on_delete);
+                        }
                     }
+                    // choosing another referenced column keeps the picked delete action
+                    column.constraints.push(BConstraint::ForeignKey {
+                        table: referenced_table_name,
+                        column: referenced_column_name,
+                        on_delete: on_delete.or(existing_on_delete).unwrap_or_default(),
+                    });
                 }
 
                 self.active_foreign_key_dropdown = None;
@@ -125,12 +230,11 @@ impl UIComponent for CreateTableFormUI {
             }
             Self::EventType::RemoveForeignKey(index) => {
                 if let Some(column) = self.create_table_input.columns.get_mut(index) {
-                    if let Some(existing_index) = column.constraints.iter().position(|constraint| {
-                        matches!(
-                            constraint,
-                            BConstraint::ForeignKey(existing_table_name, existing_column_name)
-                        )
-                    }) {
+                    if let Some(existing_index) = column
+                        .constraints
+                        .iter()
+                        .position(|constraint| matches!(constraint, BConstraint::ForeignKey { .. }))
+                    {
                         column.constraints.remove(existing_index);
                     }
                 }
@@ -151,10 +255,17 @@ impl UIComponent for CreateTableFormUI {
                 Task::perform(
                     async move {
                         let table_name = create_table_input.table_name.clone();
-                        tables.add_table(create_table_input).await;
-                        table_name
+                        let created = tables.add_table(create_table_input).await;
+                        (created, table_name)
+                    },
+                    // the form keeps its input when the table could not be created
+                    |(created, table_name)| {
+                        if created {
+                            Self::EventType::TableCreated(table_name).message()
+                        } else {
+                            TablesMessage::SetTables.message()
+                        }
                     },
-                    |table_name| Self::EventType::TableCreated(table_name).message(),
                 )
             }
             Self::EventType::ShowOrRemoveCreateTableForm => {
@@ -164,6 +275,9 @@ impl UIComponent for CreateTableFormUI {
                             name: String::from("id"),
                             datatype: BDataType::INTEGER,
                             constraints: vec![BConstraint::PrimaryKey],
+                            default_value: None,
+                            comment: None,
+                            generated: None,
                         });
                     }
                 }
@@ -205,6 +319,90 @@ impl UIComponent for CreateTableFormUI {
                 }
                 Task::none()
             }
+            Self::EventType::AddCompositeForeignKey => {
+                self.create_table_input
+                    .composite_foreign_keys
+                    .push(BCompositeForeignKey::default());
+                Task::none()
+            }
+            Self::EventType::RemoveCompositeForeignKey(index) => {
+                if index < self.create_table_input.composite_foreign_keys.len() {
+                    self.create_table_input.composite_foreign_keys.remove(index);
+                }
+                Task::none()
+            }
+            Self::EventType::UpdateCompositeForeignKeyTable(index, referenced_table_name) => {
+                let Some(composite_foreign_key) = self
+                    .create_table_input
+                    .composite_foreign_keys
+                    .get_mut(index)
+                else {
+                    return Task::none();
+                };
+                // the referenced columns are picked among the keys of the table once they are loaded
+                *composite_foreign_key = BCompositeForeignKey {
+                    referenced_table: referenced_table_name.clone(),
+                    ..BCompositeForeignKey::default()
+                };
+                let tables = self.tables.clone();
+                Task::perform(
+                    async move {
+                        let keys = tables
+                            .get_key_column_names(referenced_table_name.clone())
+                            .await;
+                        (referenced_table_name, keys)
+                    },
+                    |(referenced_table_name, keys)| {
+                        Self::EventType::SetReferencedTableKeys(
+                            referenced_table_name,
+                            keys.into_iter().map(ReferencedKey).collect(),
+                        )
+                        .message()
+                    },
+                )
+            }
+            Self::EventType::SetReferencedTableKeys(referenced_table_name, keys) => {
+                // a table with a single key leaves nothing to pick
+                if let [key] = keys.as_slice() {
+                    for composite_foreign_key in &mut self.create_table_input.composite_foreign_keys
+                    {
+                        if composite_foreign_key.referenced_table == referenced_table_name
+                            && composite_foreign_key.referenced_column_names.is_empty()
+                        {
+                            composite_foreign_key.column_names = vec![String::new(); key.0.len()];
+                            composite_foreign_key.referenced_column_names = key.0.clone();
+                        }
+                    }
+                }
+                self.referenced_table_keys
+                    .insert(referenced_table_name, keys);
+                Task::none()
+            }
+            Self::EventType::UpdateCompositeForeignKeyKey(index, key) => {
+                if let Some(composite_foreign_key) = self
+                    .create_table_input
+                    .composite_foreign_keys
+                    .get_mut(index)
+                {
+                    composite_foreign_key.column_names = vec![String::new(); key.0.len()];
+                    composite_foreign_key.referenced_column_names = key.0;
+                }
+                Task::none()
+            }
+            Self::EventType::UpdateCompositeForeignKeyColumn(index, position, column_name) => {
+                if let Some(composite_foreign_key) = self
+                    .create_table_input
+                    .composite_foreign_keys
+                    .get_mut(index)
+                {
+                    if let Some(existing_column_name) =
+                        composite_foreign_key.column_names.get_mut(position)
+                    {
+                        *existing_column_name = column_name;
+                    }
+                }
+                Task::none()
+            }
         }
     }
 }
@@ -214,6 +412,7 @@ impl CreateTableFormUI {
         Self {
             create_table_input: BTableIn::default(),
             tables,
+            referenced_table_keys: HashMap::new(),
             active_foreign_key_dropdown: None,
         }
     }
@@ -226,7 +425,7 @@ impl CreateTableFormUI {
 
         container(create_form)
             .padding(20)
-            .style(|_| container_style())
+            .style(container_style)
             .into()
     }
 
@@ -236,18 +435,29 @@ impl CreateTableFormUI {
         form = form.push(self.table_form_columns());
 
         let add_column_button = button("➕ Add Column")
-            .style(|_, _| button_style())
+            .style(|theme, _| button_style(theme, ButtonKind::Accent))
             .on_press(<CreateTableFormUI as UIComponent>::EventType::AddColumn.message())
             .padding(10);
         form = form.push(add_column_button);
+        form = form.push(self.composite_foreign_keys_section());
 
+        let validation_result = self.tables.validate_table_in(&self.create_table_input);
+        if let Err(error) = &validation_result {
+            form = form.push(text(error.clone()).style(|theme: &Theme| text::Style {
+                color: Some(theme.palette().danger),
+            }));
+        }
+
+        // the create button is disabled until the table is valid
         let create_table_button = button("📋 Create Table")
-            .style(|_, _| create_button_style())
-            .on_press(<CreateTableFormUI as UIComponent>::EventType::message(
-                <CreateTableFormUI as UIComponent>::EventType::SubmitCreateTable(
-                    self.create_table_input.clone(),
-                ),
-            ))
+            .style(|theme, _| button_style(theme, ButtonKind::Primary))
+            .on_press_maybe(validation_result.ok().map(|_| {
+                <CreateTableFormUI as UIComponent>::EventType::message(
+                    <CreateTableFormUI as UIComponent>::EventType::SubmitCreateTable(
+                        self.create_table_input.clone(),
+                    ),
+                )
+            }))
             .padding(15);
 
         form.push(
@@ -271,14 +481,21 @@ impl CreateTableFormUI {
             })
             .width(Length::Fill)
             .padding(10)
-            .style(|_, _| text_input_style())
+            .style(|theme, _| text_input_style(theme))
             .into()
     }
 
     fn table_form_columns<'a>(&'a self) -> Element<'a, Message> {
         let mut columns_list = Column::new().spacing(10);
+        let duplicate_column_indexes = self
+            .tables
+            .get_duplicate_column_indexes(&self.create_table_input.columns);
         for (index, column) in self.create_table_input.columns.iter().enumerate() {
-            columns_list = columns_list.push(self.column_input_row(index, column));
+            columns_list = columns_list.push(self.column_input_row(
+                index,
+                column,
+                duplicate_column_indexes.contains(&index),
+            ));
         }
         scrollable(columns_list)
             .height(Length::Fill)
@@ -289,8 +506,13 @@ impl CreateTableFormUI {
             .into()
     }
 
-    fn column_input_row<'a>(&'a self, index: usize, column: &'a BColumn) -> Element<'a, Message> {
-        // Column name input
+    fn column_input_row<'a>(
+        &'a self,
+        index: usize,
+        column: &'a BColumn,
+        is_duplicate: bool,
+    ) -> Element<'a, Message> {
+        // Column name input, duplicated names are highlighted
         let name_input = text_input("📝 Column Name", &column.name)
             .on_input(move |value| {
                 <CreateTableFormUI as UIComponent>::EventType::message(
@@ -298,11 +520,40 @@ impl CreateTableFormUI {
                 )
             })
             .width(200)
-            .style(|_, _| text_input_style());
+            .style(move |theme, _| {
+                if is_duplicate {
+                    invalid_text_input_style(theme)
+                } else {
+                    text_input_style(theme)
+                }
+            });
+
+        // the comment is shown when hovering the name
+        let name_input: Element<'a, Message> = match &column.comment {
+            Some(comment) => tooltip(
+                name_input,
+                container(text(comment.as_str()).size(14))
+                    .padding(5)
+                    .style(tooltip_style),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+            None => name_input.into(),
+        };
 
         // Data type picker
         let datatype_input = PickList::new(
-            vec![BDataType::TEXT, BDataType::INTEGER, BDataType::TIMESTAMP],
+            vec![
+                BDataType::TEXT,
+                BDataType::VARCHAR(BDataType::DEFAULT_VARCHAR_LENGTH),
+                BDataType::SMALLINT,
+                BDataType::INTEGER,
+                BDataType::SERIAL,
+                BDataType::BIGINT,
+                BDataType::DOUBLE,
+                BDataType::TIMESTAMP,
+                BDataType::UUID,
+            ],
             Some(&column.datatype),
             move |value| {
                 <CreateTableFormUI as UIComponent>::EventType::message(
@@ -311,6 +562,45 @@ impl CreateTableFormUI {
             },
         )
         .width(150);
+        let mut datatype_input = Row::new()
+            .spacing(5)
+            .align_y(Vertical::Center)
+            .push(datatype_input);
+
+        // Length input, only shown next to the picker for VARCHAR columns
+        if let BDataType::VARCHAR(length) = column.datatype {
+            datatype_input = datatype_input.push(
+                text_input("Length", &length.to_string())
+                    .on_input(move |value| {
+                        <CreateTableFormUI as UIComponent>::EventType::message(
+                            <CreateTableFormUI as UIComponent>::EventType::UpdateColumnVarcharLength(
+                                index, value,
+                            ),
+                        )
+                    })
+                    .width(70)
+                    .style(|theme, _| text_input_style(theme)),
+            );
+        }
+
+        // Default value input, only editable once the column has a default
+        let default_checkbox =
+            checkbox("Default", column.default_value.is_some()).on_toggle(move |_| {
+                <CreateTableFormUI as UIComponent>::EventType::message(
+                    <CreateTableFormUI as UIComponent>::EventType::SetOrRemoveDefault(index),
+                )
+            });
+        let default_input = text_input(
+            "Default",
+            column.default_value.as_deref().unwrap_or_default(),
+        )
+        .on_input_maybe(column.default_value.is_some().then_some(move |value| {
+            <CreateTableFormUI as UIComponent>::EventType::message(
+                <CreateTableFormUI as UIComponent>::EventType::UpdateColumnDefault(index, value),
+            )
+        }))
+        .width(100)
+        .style(|theme, _| text_input_style(theme));
 
         // Primary key checkbox
         let primary_key_checkbox = checkbox(
@@ -323,10 +613,93 @@ impl CreateTableFormUI {
             )
         });
 
+        // Not null checkbox
+        let not_null_checkbox = checkbox(
+            "🚫 Not Null",
+            column.constraints.contains(&BConstraint::NotNull),
+        )
+        .on_toggle(move |_| {
+            <CreateTableFormUI as UIComponent>::EventType::message(
+                <CreateTableFormUI as UIComponent>::EventType::SetOrRemoveNotNull(index),
+            )
+        });
+
+        // Unique checkbox
+        let unique_checkbox = checkbox(
+            "✨ Unique",
+            column.constraints.contains(&BConstraint::Unique),
+        )
+        .on_toggle(move |_| {
+            <CreateTableFormUI as UIComponent>::EventType::message(
+                <CreateTableFormUI as UIComponent>::EventType::SetOrRemoveUnique(index),
+            )
+        });
+
+        // Comment input
+        let comment_input = text_input("Comment", column.comment.as_deref().unwrap_or_default())
+            .on_input(move |value| {
+                <CreateTableFormUI as UIComponent>::EventType::message(
+                    <CreateTableFormUI as UIComponent>::EventType::UpdateColumnComment(
+                        index, value,
+                    ),
+                )
+            })
+            .width(150)
+            .style(|theme, _| text_input_style(theme));
+
+        // Generation expression input, the column is computed from the others of its row
+        let generated_input = text_input(
+            "Generated as",
+            column.generated.as_deref().unwrap_or_default(),
+        )
+        .on_input(move |value| {
+            <CreateTableFormUI as UIComponent>::EventType::message(
+                <CreateTableFormUI as UIComponent>::EventType::UpdateColumnGenerated(index, value),
+            )
+        })
+        .width(150)
+        .style(|theme, _| text_input_style(theme));
+
+        // Check constraint input
+        let check_expression = column
+            .constraints
+            .iter()
+            .find_map(|constraint| match constraint {
+                BConstraint::Check(expression) => Some(expression.as_str()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let check_input = text_input("Check", check_expression)
+            .on_input(move |value| {
+                <CreateTableFormUI as UIComponent>::EventType::message(
+                    <CreateTableFormUI as UIComponent>::EventType::UpdateCheckConstraint(
+                        index, value,
+                    ),
+                )
+            })
+            .width(150)
+            .style(|theme, _| text_input_style(theme));
+
+        // Move buttons, disabled at the ends of the list
+        let move_up_button = button("⬆")
+            .style(|theme, _| button_style(theme, ButtonKind::Accent))
+            .on_press_maybe((index > 0).then(|| {
+                <CreateTableFormUI as UIComponent>::EventType::MoveColumn(index, index - 1)
+                    .message()
+            }));
+        let move_down_button = button("⬇")
+            .style(|theme, _| button_style(theme, ButtonKind::Accent))
+            .on_press_maybe(
+                (index + 1 < self.create_table_input.columns.len()).then(|| {
+                    <CreateTableFormUI as UIComponent>::EventType::MoveColumn(index, index + 1)
+                        .message()
+                }),
+            );
+
         // Foreign key dropdown
         let foreign_key_dropdown = self.render_foreign_key_button(index, &column);
         let remove_button = button("❌ Remove")
-            .style(|_, _| delete_button_style())
+            .style(|theme, _| button_style(theme, ButtonKind::Danger))
             .on_press(<CreateTableFormUI as UIComponent>::EventType::message(
                 <CreateTableFormUI as UIComponent>::EventType::RemoveColumn(index),
             ))
@@ -336,8 +709,144 @@ impl CreateTableFormUI {
         row![
             name_input,
             datatype_input,
+            default_checkbox,
+            default_input,
+            generated_input,
             primary_key_checkbox,
+            not_null_checkbox,
+            unique_checkbox,
+            check_input,
+            comment_input,
             foreign_key_dropdown,
+            move_up_button,
+            move_down_button,
+            remove_button
+        ]
+        .spacing(10)
+        .align_y(Vertical::Center)
+        .into()
+    }
+
+    fn composite_foreign_keys_section<'a>(&'a self) -> Element<'a, Message> {
+        let mut section = Column::new()
+            .spacing(10)
+            .push(text("🔗 Composite Foreign Keys").size(18));
+        for (index, composite_foreign_key) in self
+            .create_table_input
+            .composite_foreign_keys
+            .iter()
+            .enumerate()
+        {
+            section = section.push(self.composite_foreign_key_row(index, composite_foreign_key));
+        }
+        let add_composite_foreign_key_button = button("➕ Add Composite Foreign Key")
+            .style(|theme, _| button_style(theme, ButtonKind::Accent))
+            .on_press(
+                <CreateTableFormUI as UIComponent>::EventType::AddCompositeForeignKey.message(),
+            )
+            .padding(10);
+        section.push(add_composite_foreign_key_button).into()
+    }
+
+    fn composite_foreign_key_row<'a>(
+        &'a self,
+        index: usize,
+        composite_foreign_key: &'a BCompositeForeignKey,
+    ) -> Element<'a, Message> {
+        let table_names: Vec<String> = self
+            .tables
+            .tables_general_info
+            .blocking_lock()
+            .iter()
+            .map(|table| table.table_name.clone())
+            .collect();
+        let selected_table_name = if composite_foreign_key.referenced_table.is_empty() {
+            None
+        } else {
+            Some(composite_foreign_key.referenced_table.clone())
+        };
+        let referenced_table_picker =
+            PickList::new(table_names, selected_table_name, move |table_name| {
+                <CreateTableFormUI as UIComponent>::EventType::UpdateCompositeForeignKeyTable(
+                    index, table_name,
+                )
+                .message()
+            })
+            .placeholder("Referenced Table")
+            .width(150);
+        let keys = self
+            .referenced_table_keys
+            .get(&composite_foreign_key.referenced_table)
+            .cloned()
+            .unwrap_or_default();
+        let selected_key = if composite_foreign_key.referenced_column_names.is_empty() {
+            None
+        } else {
+            Some(ReferencedKey(
+                composite_foreign_key.referenced_column_names.clone(),
+            ))
+        };
+        let referenced_key_picker = PickList::new(keys, selected_key, move |key| {
+            <CreateTableFormUI as UIComponent>::EventType::UpdateCompositeForeignKeyKey(index, key)
+                .message()
+        })
+        .placeholder("Referenced Key")
+        .width(150);
+
+        let column_names: Vec<String> = self
+            .create_table_input
+            .columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect();
+        // one picker per referenced column to choose the local column mapped to it
+        let column_pickers = zip(
+            &composite_foreign_key.column_names,
+            &composite_foreign_key.referenced_column_names,
+        )
+        .enumerate()
+        .fold(
+            Column::new().spacing(5),
+            |column_pickers, (position, (column_name, referenced_column_name))| {
+                let selected_column_name = if column_name.is_empty() {
+                    None
+                } else {
+                    Some(column_name.clone())
+                };
+                column_pickers.push(
+                    row![
+                        PickList::new(
+                            column_names.clone(),
+                            selected_column_name,
+                            move |column_name| {
+                                <CreateTableFormUI as UIComponent>::EventType::UpdateCompositeForeignKeyColumn(
+                                    index, position, column_name,
+                                )
+                                .message()
+                            },
+                        )
+                        .placeholder("Column")
+                        .width(150),
+                        text(format!("→ {}", referenced_column_name)),
+                    ]
+                    .spacing(10)
+                    .align_y(Vertical::Center),
+                )
+            },
+        );
+
+        let remove_button = button("❌ Remove")
+            .style(|theme, _| button_style(theme, ButtonKind::Danger))
+            .on_press(
+                <CreateTableFormUI as UIComponent>::EventType::RemoveCompositeForeignKey(index)
+                    .message(),
+            )
+            .padding(10);
+
+        row![
+            referenced_table_picker,
+            referenced_key_picker,
+            column_pickers,
             remove_button
         ]
         .spacing(10)
@@ -350,31 +859,62 @@ impl CreateTableFormUI {
         index: usize,
         column: &BColumn,
     ) -> Element<'a, Message> {
-        // Button to show the foreign key tables
-        let button_text = if let Some(foreign_key_constraint) = column
-            .constraints
-            .iter()
-            .find(|constraint| matches!(constraint, BConstraint::ForeignKey(_, _)))
-        {
-            if let BConstraint::ForeignKey(referenced_table_name, referenced_column_name) =
-                foreign_key_constraint
+        let foreign_key_constraint = column.constraints.iter().find_map(|constraint| {
+            if let BConstraint::ForeignKey {
+                table,
+                column,
+                on_delete,
+            } = constraint
             {
-                text(format!(
-                    "🔗 {}.{}",
-                    referenced_table_name, referenced_column_name
-                ))
+                Some((table.clone(), column.clone(), on_delete.clone()))
             } else {
-                text("➕ Set Foreign Key")
+                None
             }
+        });
+
+        // Button to show the foreign key tables
+        let button_text = if let Some((referenced_table_name, referenced_column_name, _)) =
+            &foreign_key_constraint
+        {
+            text(format!(
+                "🔗 {}.{}",
+                referenced_table_name, referenced_column_name
+            ))
         } else {
             text("➕ Set Foreign Key")
         };
 
-        let button = button(button_text).style(|_, _| button_style()).on_press(
-            <CreateTableFormUI as UIComponent>::EventType::message(
+        let foreign_key_button = button(button_text)
+            .style(|theme, _| button_style(theme, ButtonKind::Accent))
+            .on_press(<CreateTableFormUI as UIComponent>::EventType::message(
                 <CreateTableFormUI as UIComponent>::EventType::ToggleForeignKeyDropdown(index),
-            ),
-        );
+            ));
+
+        // the delete action can only be picked once the referenced column is chosen
+        let button: Element<'a, Message> =
+            if let Some((referenced_table_name, referenced_column_name, on_delete)) =
+                foreign_key_constraint
+            {
+                row![
+                    foreign_key_button,
+                    text("On delete"),
+                    PickList::new(BReferentialAction::ALL, Some(on_delete), move |on_delete| {
+                        <CreateTableFormUI as UIComponent>::EventType::AddForeignKey(
+                            index,
+                            referenced_table_name.clone(),
+                            referenced_column_name.clone(),
+                            Some(on_delete),
+                        )
+                        .message()
+                    })
+                    .width(130),
+                ]
+                .spacing(10)
+                .align_y(Vertical::Center)
+                .into()
+            } else {
+                foreign_key_button.into()
+            };
 
         // Check if the current column's foreign key dropdown is active
         if let Some(active_foreign_key_dropdown) = &self.active_foreign_key_dropdown {
@@ -393,154 +933,3 @@ impl CreateTableFormUI {
         }
     }
 }
-
-// ======================== STYLES ========================
-fn container_style() -> container::Style {
-    container::Style {
-        background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.1))), // Background color
-        border: Border {
-            color: Color::TRANSPARENT,
-            width: 1.5,
-            radius: Radius::from(5.0),
-        },
-        text_color: Some(Color::WHITE), // Text color for the content inside the container
-        shadow: Shadow {
-            color: Color::BLACK,
-            offset: Vector::new(0.0, 2.0),
-            blur_radius: 5.0,
-        },
-    }
-}
-
-fn constraints_container_style() -> container::Style {
-    container::Style {
-        background: Some(Background::Color(Color::from_rgb(0.95, 0.95, 0.95))),
-        border: Border {
-            color: Color::from_rgb(0.85, 0.85, 0.85),
-            width: 1.0,
-            radius: Radius::from(5.0),
-        },
-        text_color: Some(Color::BLACK),
-        shadow: Shadow {
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.05),
-            offset: Vector::new(0.0, 1.0),
-            blur_radius: 2.0,
-        },
-    }
-}
-fn button_style() -> button::Style {
-    button::Style {
-        background: Some(Background::Color(Color::from_rgb(0.0, 0.75, 0.65))),
-        border: Border {
-            color: Color::from_rgb(0.0, 0.6, 0.5),
-            width: 2.0,
-            radius: Radius::from(5.0),
-        },
-        text_color: Color::WHITE,
-        shadow: Shadow {
-            color: Color::BLACK,
-            offset: Vector::new(0.0, 3.0),
-            blur_radius: 5.0,
-        },
-    }
-}
-
-fn table_button_style() -> button::Style {
-    button::Style {
-        background: Some(Background::Color(Color::from_rgb(0.2, 0.4, 0.8))), // Blue background
-        border: Border {
-            color: Color::from_rgb(0.1, 0.3, 0.6), // Darker blue border
-            width: 2.0,
-            radius: Radius::from(6.0),
-        },
-        text_color: Color::WHITE, // White text for contrast
-        shadow: Shadow {
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.5), // Slight shadow for depth
-            offset: Vector::new(0.0, 2.0),
-            blur_radius: 10.0,
-        },
-    }
-}
-
-fn column_button_style() -> button::Style {
-    button::Style {
-        background: Some(Background::Color(Color::from_rgb(0.4, 0.8, 0.2))), // Green background
-        border: Border {
-            color: Color::from_rgb(0.3, 0.6, 0.1), // Darker green border
-            width: 1.5,
-            radius: Radius::from(5.0),
-        },
-        text_color: Color::BLACK, // Black text for contrast
-        shadow: Shadow {
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.3), // Subtle shadow
-            offset: Vector::new(0.0, 1.0),
-            blur_radius: 5.0,
-        },
-    }
-}
-
-fn dropdown_style() -> container::Style {
-    container::Style {
-        background: Some(Background::Color(Color::from_rgb(0.2, 0.2, 0.2))), // Dark background
-        border: Border {
-            color: Color::from_rgb(0.0, 0.6, 0.6), // Aqua border color
-            width: 2.0,
-            radius: Radius::from(5.0),
-        },
-        text_color: Some(Color::WHITE), // White text color
-        shadow: Shadow {
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.5), // Slight shadow for depth
-            offset: Vector::new(0.0, 2.0),
-            blur_radius: 10.0,
-        },
-    }
-}
-
-fn delete_button_style() -> button::Style {
-    button::Style {
-        background: Some(Background::Color(Color::from_rgb(0.8, 0.2, 0.2))), // Soft red background
-        border: Border {
-            color: Color::from_rgb(0.6, 0.1, 0.1), // Dark red border
-            width: 2.0,
-            radius: Radius::from(5.0),
-        },
-        text_color: Color::WHITE, // White text for contrast
-        shadow: Shadow {
-            color: Color::BLACK,
-            offset: Vector::new(0.0, 3.0),
-            blur_radius: 5.0,
-        },
-    }
-}
-
-fn create_button_style() -> button::Style {
-    button::Style {
-        background: Some(Background::Color(Color::from_rgb(0.0, 0.5, 0.9))),
-        border: Border {
-            color: Color::from_rgb(0.0, 0.4, 0.7),
-            width: 2.0,
-            radius: Radius::from(8.0),
-        },
-        text_color: Color::WHITE,
-        shadow: Shadow {
-            color: Color::BLACK,
-            offset: Vector::new(0.0, 3.0),
-            blur_radius: 7.0,
-        },
-    }
-}
-
-fn text_input_style() -> text_input::Style {
-    text_input::Style {
-        background: Background::Color(Color::from_rgb(0.2, 0.2, 0.2)), // Darker input background
-        border: Border {
-            width: 1.5,
-            color: Color::from_rgb(0.0, 0.74, 0.84),
-            radius: Radius::from(5.0),
-        },
-        placeholder: Color::from_rgb(0.6, 0.6, 0.6), // Color for placeholder text
-        value: Color::WHITE,                         // Color for input text
-        selection: Color::from_rgb(0.0, 0.74, 0.84), // Color for selected text
-        icon: Color::from_rgb(0.8, 0.8, 0.8),        // Color for any input icons
-    }
-}
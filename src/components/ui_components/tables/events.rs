@@ -1,5 +1,7 @@
 use crate::components::business_components::{
-    component::{BDataType, BTableChangeEvents, BTableGeneral, BTableIn, BTableInfo},
+    component::{
+        BDataType, BReferentialAction, BTableChangeEvents, BTableGeneral, BTableIn, BTableInfo,
+    },
     components::BusinessTables,
 };
 use crate::components::ui_components::{
@@ -7,25 +9,78 @@ use crate::components::ui_components::{
     events::Message,
     tables::{table_data::events::TableDataMessage, table_info::events::TableInfoMessage},
 };
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub enum TablesMessage {
     UpdateTableFilter(String),
+    UpdateColumnSearch(String),
+    SearchColumns,
+    SetColumnSearchResults(Vec<(String, String)>),
+    ToggleTableFilterCaseSensitive,
+    SelectTableFilterMode(TableFilterMode),
+    SetTableSort(TableSort),
+    SelectSchema(String),
+    ConfirmSelectSchema,
+    CancelSelectSchema,
     ShowOrRemoveCreateTableForm,
+    OpenCreateTableForm,
+    CloseCreateTableForm,
+    FocusTableFilter,
     ShowOrRemoveTableData,
     ShowOrRemoveTables,
     GetSingleTableInfo(String),
     SetSingleTableInfo,
     UndisplayTableInfo,
     SingleTableInfo(TableInfoMessage),
+    // goes to the table data shown in the active tab
     SingleTableData(TableDataMessage),
+    // goes to the open table data of the named table, tasks report back to the same one
+    TableData(String, TableDataMessage),
+    OpenTableData(String),
+    SelectTableDataTab(String),
+    CloseTableData(String),
+    ConfirmCloseTableData,
+    TableDataClosed,
+    CancelCloseTableData,
     CreateTableForm(CreateTableFormMessage),
     InitializeComponent,
     SetTables,
+    RowCountsLoaded,
     ComponentInitialized,
     RequestDeleteTable(String),
+    SetDeleteTableDependents(Vec<String>),
+    ToggleDeleteTableCascade,
     ConfirmDeleteTable,
     CancelDeleteTable,
+    ToggleTableSelection(String),
+    RequestDeleteSelectedTables,
+    SetSelectedTablesRowCount(i64),
+    ConfirmDeleteSelectedTables,
+    CancelDeleteSelectedTables,
+    RequestCloneTable(String),
+    UpdateCloneTableName(String),
+    ToggleCloneTableConstraints,
+    RequestCloneTableStructure(String),
+    ToggleCloneTableRows,
+    ConfirmCloneTable,
+    CancelCloneTable,
+    RequestRenameTable(String),
+    UpdateRenameTableName(String),
+    ConfirmRenameTable,
+    TableRenamed(String, String),
+    CancelRenameTable,
+    RequestTruncateTable(String),
+    ToggleTruncateTableCascade,
+    ConfirmTruncateTable,
+    TableTruncated(bool),
+    CancelTruncateTable,
+    ToggleDryRun(bool),
+    UpdateSchemaExportPath(String),
+    ExportSchema,
+    Refresh,
+    ConfirmRefresh,
+    CancelRefresh,
 }
 
 impl Event for TablesMessage {
@@ -34,21 +89,104 @@ impl Event for TablesMessage {
     }
 }
 
+// how the search pattern has to match a table name
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableFilterMode {
+    #[default]
+    Contains,
+    Exact,
+    Prefix,
+}
+
+impl TableFilterMode {
+    pub const ALL: [TableFilterMode; 3] = [
+        TableFilterMode::Contains,
+        TableFilterMode::Exact,
+        TableFilterMode::Prefix,
+    ];
+}
+
+impl fmt::Display for TableFilterMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            TableFilterMode::Contains => "Contains",
+            TableFilterMode::Exact => "Exact",
+            TableFilterMode::Prefix => "Prefix",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
+// the order of the table list, tables without a loaded row count come last by row count
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableSort {
+    #[default]
+    NameAscending,
+    NameDescending,
+    RowCountDescending,
+}
+
+impl TableSort {
+    pub const ALL: [TableSort; 3] = [
+        TableSort::NameAscending,
+        TableSort::NameDescending,
+        TableSort::RowCountDescending,
+    ];
+}
+
+impl fmt::Display for TableSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sort = match self {
+            TableSort::NameAscending => "Name A-Z",
+            TableSort::NameDescending => "Name Z-A",
+            TableSort::RowCountDescending => "Most rows",
+        };
+        write!(f, "{}", sort)
+    }
+}
+
+// the columns of a primary key or unique constraint, the column sets a foreign key can reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferencedKey(pub Vec<String>);
+
+impl fmt::Display for ReferencedKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({})", self.0.join(", "))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum CreateTableFormMessage {
     SubmitCreateTable(BTableIn),
     AddColumn,                          // Event to add a new column to the form
     RemoveColumn(usize),                // Event to remove a specific column by index
+    MoveColumn(usize, usize),           // Event to swap a column with another one (from, to)
     UpdateColumnName(usize, String),    // Event to update the name of a specific column
     UpdateColumnType(usize, BDataType), // Event to update the type of a specific column
+    // Event to update the length of a specific VARCHAR column, input that isn't a length is ignored
+    UpdateColumnVarcharLength(usize, String),
+    UpdateColumnDefault(usize, String), // Event to update the default value of a specific column
+    UpdateColumnComment(usize, String), // Event to update the comment of a specific column
+    // Event to update the generation expression of a specific column
+    UpdateColumnGenerated(usize, String),
     UpdateTableName(String),
     TableCreated(String),
     SetOrRemovePrimaryKey(usize),
-    AddForeignKey(usize, String, String),
+    SetOrRemoveDefault(usize),
+    SetOrRemoveNotNull(usize),
+    SetOrRemoveUnique(usize),
+    UpdateCheckConstraint(usize, String), // an empty expression removes the check constraint
+    AddForeignKey(usize, String, String, Option<BReferentialAction>), // (column index, table, column, on delete action, None keeps the current one)
     RemoveForeignKey(usize),
     ShowOrRemoveCreateTableForm,
     ToggleForeignKeyDropdown(usize),
     ToggleForeignKeyTable(usize, String),
+    AddCompositeForeignKey,
+    RemoveCompositeForeignKey(usize),
+    UpdateCompositeForeignKeyTable(usize, String),
+    SetReferencedTableKeys(String, Vec<ReferencedKey>), // (table name, its keys once loaded)
+    UpdateCompositeForeignKeyKey(usize, ReferencedKey), // (foreign key index, referenced key)
+    UpdateCompositeForeignKeyColumn(usize, usize, String), // (foreign key index, position, column name)
 }
 
 impl Event for CreateTableFormMessage {
@@ -1,20 +1,76 @@
 use crate::components::business_components::{
-    component::{BDataType, BTableChangeEvents, BTableGeneral, BTableIn, BTableInsertedData},
+    component::{
+        BDataType, BOperator, BTableChangeEvents, BTableGeneral, BTableIn, BTableInsertedData,
+    },
     components::BusinessTables,
 };
 use crate::components::ui_components::{
     component::Event, events::Message, tables::events::TablesMessage,
 };
+use iced::advanced::widget::Id;
+
+// where the keyboard moves the focus from the focused cell of the grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellFocusMove {
+    // tab order, wrapping at the row ends
+    Next,
+    Previous,
+    Up,
+    Down,
+}
 
 #[derive(Debug, Clone)]
 pub enum TableDataMessage {
     GetTableData(String),
+    RefreshTableData,
     SetTableData,
     UpdateCell(usize, usize, String),
+    SubmitCell(usize, usize),
+    MoveCellFocus(CellFocusMove),
+    FocusedWidgetFound(Id, CellFocusMove),
+    SetCellNull(usize, usize),
     DeleteRow(usize),
+    ToggleRowSelection(usize),
+    ToggleAllRowsSelection(bool),
+    DeleteSelectedRows,
+    DuplicateRow(usize),
     AddRow,
+    UpdateNewRowValue(String, String), // (column, value)
+    InsertNewRow,
     UpdateTableData,
+    ConfirmUpdateTableData,
+    CancelUpdateTableData,
     ResetTableData,
+    DiscardChanges,
+    PreviewForeignKey(usize, usize),
+    SetForeignKeyPreview(usize, usize, Option<Vec<(String, String)>>),
+    FollowForeignKey(String, String, String), // (referenced table, referenced column, value)
+    CopyToClipboard(String),
+    ToggleRowNumbers,
+    ToggleAutoCommit(bool),
+    ChangePage(usize),
+    SortByColumn(String),
+    SelectFilterColumn(String),
+    SelectFilterOperator(BOperator),
+    UpdateFilterValue(String),
+    AddFilter,
+    RemoveFilter(usize),
+    UpdatePrimaryKeyLookupValue(String, String), // (primary key column, value)
+    LookUpPrimaryKey,
+    UndoEvent,
+    RedoEvent,
+    PreviewChanges,
+    SetChangesPreview(Vec<String>),
+    CloseChangesPreview,
+    TogglePendingDiff,
+    UpdateImportPath(String),
+    ImportCsv,
+    ConfirmDiscardChanges,
+    CancelDiscardChanges,
+    ToggleDuplicateColumn(String),
+    FindDuplicates,
+    SetDuplicateGroups(Vec<(Vec<Option<String>>, i64)>),
+    CloseDuplicateGroups,
 }
 
 impl Event for TableDataMessage {
@@ -1,27 +1,34 @@
 use crate::components::business_components::{
     component::{
-        BColumn, BConstraint, BDataType, BRowColumnValue, BTableData, BTableDataChangeEvents,
+        BChangeSummary, BColumn, BColumnForeignKey, BCondition, BConstraint, BDataType, BOperator,
+        BPage, BRowColumnValue, BRowDiff, BSortDirection, BTableData, BTableDataChangeEvents,
         BTableGeneral, BTableIn, BTableInsertedData, BusinessComponent,
     },
     components::BusinessTables,
 };
 use crate::components::ui_components::component::{Event, UIComponent};
 use crate::components::ui_components::{
-    events::Message, tables::table_data::events::TableDataMessage,
+    components::ComponentsMessage,
+    events::Message,
+    styles::tooltip_style,
+    tables::table_data::events::{CellFocusMove, TableDataMessage},
 };
 use iced::{
+    advanced::widget::{operate, operation::focusable::find_focused, Id},
     alignment,
     alignment::{Alignment, Vertical},
     border::Radius,
     futures::join,
     widget::{
-        button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Button,
-        Checkbox, Column, PickList, Row, Scrollable, Text, TextInput,
+        button, checkbox, column, container, pick_list, row, scrollable, text, text_input, tooltip,
+        Button, Checkbox, Column, PickList, Row, Scrollable, Text, TextInput,
     },
     Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector,
 };
 use regex::Regex;
+use std::collections::HashMap;
 use std::iter::zip;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 
@@ -30,14 +37,80 @@ pub struct TableDataUI {
     table_data: Arc<BTableData>,
     table_inserted_data: Option<BTableInsertedData>,
     selected_table_name: Option<String>,
+    foreign_keys: Vec<BColumnForeignKey>,
+    column_comments: HashMap<String, String>,
+    // (row index, column index, referenced row) of the opened foreign key preview
+    foreign_key_preview: Option<(usize, usize, Option<Vec<(String, String)>>)>,
+    show_row_numbers: bool,
+    order_by: Option<(String, BSortDirection)>,
+    filters: Vec<BCondition>,
+    filter_column_name: Option<String>,
+    filter_operator: BOperator,
+    filter_value: String,
+    changes_preview: Option<Vec<String>>,
+    // the diff is built from the queued events every time it is drawn so it never goes stale
+    show_pending_diff: bool,
+    import_path: String,
+    // primary key column name to the value typed in the row lookup
+    primary_key_lookup_values: HashMap<String, String>,
+    // column name to the value typed in the new row form, columns left empty are not inserted
+    new_row_values: HashMap<String, String>,
+    // (row index, column index) to the value typed in the cell and why it was rejected,
+    // it is shown in place of the cell and only checked and queued once it is submitted
+    cell_drafts: HashMap<(usize, usize), (String, Option<String>)>,
+    // indexes of the displayed rows checked for a bulk delete
+    selected_row_indexes: Vec<usize>,
+    // navigation held back until the user agrees to discard the queued edits
+    message_awaiting_discard: Option<TableDataMessage>,
+    // pending changes the user is asked to confirm before they are committed
+    update_summary: Option<BChangeSummary>,
+    // columns the duplicate finder groups the rows by
+    duplicate_column_names: Vec<String>,
+    // (values, row count) of the groups found, None until a search was run
+    duplicate_groups: Option<Vec<(Vec<Option<String>>, i64)>>,
 }
 
 impl UIComponent for TableDataUI {
     type EventType = TableDataMessage;
 
     fn update(&mut self, message: Self::EventType) -> Task<Message> {
+        if discards_pending_changes(&message) && self.table_data.has_pending_changes() {
+            self.message_awaiting_discard = Some(message);
+            return Task::none();
+        }
         match message {
+            Self::EventType::ConfirmDiscardChanges => {
+                if let Some(message) = self.message_awaiting_discard.take() {
+                    self.table_data.discard_pending_changes();
+                    self.refresh_displayed_rows();
+                    self.update(message)
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::CancelDiscardChanges => {
+                self.message_awaiting_discard = None;
+                Task::none()
+            }
+            // nothing to confirm when there are no pending changes, the rows are only reloaded
             Self::EventType::UpdateTableData => {
+                // a rejected cell keeps its value and error until it is corrected
+                if !self.submit_cell_drafts() {
+                    return Task::none();
+                }
+                let change_summary = self.table_data.change_summary();
+                if change_summary.is_empty() {
+                    return Task::done(Self::EventType::ConfirmUpdateTableData.message());
+                }
+                self.update_summary = Some(change_summary);
+                Task::none()
+            }
+            Self::EventType::CancelUpdateTableData => {
+                self.update_summary = None;
+                Task::none()
+            }
+            Self::EventType::ConfirmUpdateTableData => {
+                self.update_summary = None;
                 let table_data = self.table_data.clone();
                 Task::perform(
                     async move {
@@ -47,15 +120,193 @@ impl UIComponent for TableDataUI {
                 )
             }
             Self::EventType::GetTableData(table_name) => {
+                // the sort and filters only apply to the table they were chosen for
+                let table_changed = self.selected_table_name.as_ref() != Some(&table_name);
+                if table_changed {
+                    self.filter_column_name = None;
+                    self.filter_value = String::new();
+                }
                 self.selected_table_name = Some(table_name.clone());
                 let table_data = self.table_data.clone();
                 Task::perform(
                     async move {
-                        table_data.set_table_data(table_name).await;
+                        if table_changed {
+                            table_data.set_order_by(None).await;
+                            table_data.set_filters(vec![]).await;
+                        }
+                        table_data
+                            .set_table_data(table_name, BPage::default())
+                            .await;
+                    },
+                    |_| Self::EventType::SetTableData.message(),
+                )
+            }
+            Self::EventType::RefreshTableData => {
+                if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
+                    let table_name = table_inserted_data.table_name.clone();
+                    let page = table_inserted_data.page.clone();
+                    let table_data = self.table_data.clone();
+                    Task::perform(
+                        async move {
+                            table_data.set_table_data(table_name, page).await;
+                        },
+                        |_| Self::EventType::SetTableData.message(),
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::SortByColumn(column_name) => {
+                if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
+                    // clicking the sorted column again flips the direction
+                    let direction = match self.order_by.as_ref() {
+                        Some((sorted_column_name, BSortDirection::Ascending))
+                            if *sorted_column_name == column_name =>
+                        {
+                            BSortDirection::Descending
+                        }
+                        _ => BSortDirection::Ascending,
+                    };
+                    let table_name = table_inserted_data.table_name.clone();
+                    let page = BPage {
+                        size: table_inserted_data.page.size,
+                        number: 0,
+                    };
+                    let table_data = self.table_data.clone();
+                    Task::perform(
+                        async move {
+                            table_data
+                                .set_order_by(Some((column_name, direction)))
+                                .await;
+                            table_data.set_table_data(table_name, page).await;
+                        },
+                        |_| Self::EventType::SetTableData.message(),
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::FollowForeignKey(referenced_table, referenced_column, value) => {
+                // the referenced table is opened showing only the referenced row
+                let data_type = self
+                    .table_data
+                    .tables_general_info
+                    .blocking_lock()
+                    .iter()
+                    .find(|table| table.table_name == referenced_table)
+                    .and_then(|table| {
+                        zip(&table.column_names, &table.data_types)
+                            .find(|(column_name, _)| **column_name == referenced_column)
+                            .map(|(_, data_type)| data_type.clone())
+                    });
+                let Some(data_type) = data_type else {
+                    return Task::none();
+                };
+                self.filter_column_name = None;
+                self.filter_value = String::new();
+                self.selected_table_name = Some(referenced_table.clone());
+                let filters = vec![BCondition {
+                    column_name: referenced_column,
+                    data_type,
+                    operator: BOperator::Equal,
+                    value,
+                }];
+                let table_data = self.table_data.clone();
+                Task::perform(
+                    async move {
+                        table_data.set_order_by(None).await;
+                        table_data.set_filters(filters).await;
+                        table_data
+                            .set_table_data(referenced_table, BPage::default())
+                            .await;
+                    },
+                    |_| Self::EventType::SetTableData.message(),
+                )
+            }
+            Self::EventType::CopyToClipboard(contents) => iced::clipboard::write(contents),
+            Self::EventType::SelectFilterColumn(column_name) => {
+                self.filter_column_name = Some(column_name);
+                Task::none()
+            }
+            Self::EventType::SelectFilterOperator(operator) => {
+                self.filter_operator = operator;
+                Task::none()
+            }
+            Self::EventType::UpdateFilterValue(value) => {
+                self.filter_value = value;
+                Task::none()
+            }
+            Self::EventType::AddFilter => {
+                let Some(table_inserted_data) = self.table_inserted_data.as_ref() else {
+                    return Task::none();
+                };
+                let Some(column_index) = self.filter_column_name.as_ref().and_then(|column_name| {
+                    table_inserted_data
+                        .column_names
+                        .iter()
+                        .position(|name| name == column_name)
+                }) else {
+                    return Task::none();
+                };
+                let mut filters = self.filters.clone();
+                filters.push(BCondition {
+                    column_name: table_inserted_data.column_names[column_index].clone(),
+                    data_type: table_inserted_data.data_types[column_index].clone(),
+                    operator: self.filter_operator.clone(),
+                    value: std::mem::take(&mut self.filter_value),
+                });
+                self.apply_filters(filters)
+            }
+            Self::EventType::RemoveFilter(filter_index) => {
+                let mut filters = self.filters.clone();
+                if filter_index < filters.len() {
+                    filters.remove(filter_index);
+                }
+                self.apply_filters(filters)
+            }
+            Self::EventType::UpdatePrimaryKeyLookupValue(column_name, value) => {
+                self.primary_key_lookup_values.insert(column_name, value);
+                Task::none()
+            }
+            // the found row is shown through the filters so removing them shows the others again
+            Self::EventType::LookUpPrimaryKey => {
+                let primary_key_values: Vec<String> = self
+                    .table_data
+                    .get_primary_key_column_names()
+                    .iter()
+                    .map(|column_name| {
+                        self.primary_key_lookup_values
+                            .get(column_name)
+                            .cloned()
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                let table_data = self.table_data.clone();
+                Task::perform(
+                    async move {
+                        table_data.look_up_primary_key(primary_key_values).await;
                     },
                     |_| Self::EventType::SetTableData.message(),
                 )
             }
+            Self::EventType::ChangePage(page_number) => {
+                if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
+                    let table_name = table_inserted_data.table_name.clone();
+                    let page = BPage {
+                        size: table_inserted_data.page.size,
+                        number: page_number,
+                    };
+                    let table_data = self.table_data.clone();
+                    Task::perform(
+                        async move {
+                            table_data.set_table_data(table_name, page).await;
+                        },
+                        |_| Self::EventType::SetTableData.message(),
+                    )
+                } else {
+                    Task::none()
+                }
+            }
             Self::EventType::ResetTableData => {
                 if let Some(table_name) = self.selected_table_name.as_ref() {
                     Task::done(Self::EventType::GetTableData(table_name.clone()).message())
@@ -63,82 +314,477 @@ impl UIComponent for TableDataUI {
                     Task::none()
                 }
             }
+            Self::EventType::DiscardChanges => {
+                self.table_data.discard_changes();
+                self.refresh_displayed_rows();
+                Task::none()
+            }
             Self::EventType::SetTableData => {
-                let updated_table_inserted_data =
-                    self.table_data.table_inserted_data.blocking_lock();
-
-                self.table_inserted_data = updated_table_inserted_data.clone();
+                self.table_inserted_data =
+                    self.table_data.table_inserted_data.blocking_lock().clone();
+                // a renamed table is reloaded under its new name
+                if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
+                    self.selected_table_name = Some(table_inserted_data.table_name.clone());
+                }
+                // events kept after a failed update are shown on top of the loaded rows
+                self.refresh_displayed_rows();
+                self.foreign_keys = self.table_data.foreign_keys.blocking_lock().clone();
+                self.column_comments = self.table_data.column_comments.blocking_lock().clone();
+                self.order_by = self.table_data.order_by.blocking_lock().clone();
+                self.filters = self.table_data.filters.blocking_lock().clone();
+                self.foreign_key_preview = None;
+                self.changes_preview = None;
+                self.update_summary = None;
+                // the duplicates were found in what was loaded before
+                self.duplicate_groups = None;
+                let column_names = self
+                    .table_inserted_data
+                    .as_ref()
+                    .map(|table_inserted_data| table_inserted_data.column_names.clone())
+                    .unwrap_or_default();
+                self.duplicate_column_names
+                    .retain(|column_name| column_names.contains(column_name));
+                let primary_key_column_names = self.table_data.get_primary_key_column_names();
+                self.primary_key_lookup_values
+                    .retain(|column_name, _| primary_key_column_names.contains(column_name));
+                self.new_row_values
+                    .retain(|column_name, _| column_names.contains(column_name));
                 Task::none()
             }
-            Self::EventType::UpdateCell(row_index, col_index, new_value) => {
-                if let Some(table_inserted_data) = self.table_inserted_data.as_mut() {
-                    if let Some(row_data) = table_inserted_data.rows.get_mut(row_index) {
-                        if let Some(cell) = row_data.get_mut(col_index) {
-                            let column_name = table_inserted_data.column_names[col_index].clone();
-
-                            self.table_data.add_modify_row_column_value_event(
-                                row_index,
-                                column_name,
-                                new_value.clone(),
-                            );
-
-                            *cell = new_value;
-                        }
+            Self::EventType::PreviewForeignKey(row_index, col_index) => {
+                if let Some((preview_row_index, preview_col_index, _)) = self.foreign_key_preview {
+                    if preview_row_index == row_index && preview_col_index == col_index {
+                        self.foreign_key_preview = None;
+                        return Task::none();
                     }
                 }
+                if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
+                    let column_name = table_inserted_data.column_names[col_index].clone();
+                    // NULL never references a row
+                    let Some(value) = table_inserted_data.rows[row_index][col_index].clone() else {
+                        self.foreign_key_preview = Some((row_index, col_index, None));
+                        return Task::none();
+                    };
+                    let table_data = self.table_data.clone();
+                    Task::perform(
+                        async move { table_data.get_foreign_key_preview(column_name, value).await },
+                        move |referenced_row| {
+                            Self::EventType::SetForeignKeyPreview(
+                                row_index,
+                                col_index,
+                                referenced_row,
+                            )
+                            .message()
+                        },
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::SetForeignKeyPreview(row_index, col_index, referenced_row) => {
+                self.foreign_key_preview = Some((row_index, col_index, referenced_row));
+                Task::none()
+            }
+            Self::EventType::ToggleRowNumbers => {
+                self.show_row_numbers = !self.show_row_numbers;
+                Task::none()
+            }
+            Self::EventType::ToggleAutoCommit(auto_commit) => {
+                self.table_data.set_auto_commit(auto_commit);
+                Task::none()
+            }
+            Self::EventType::UpdateCell(row_index, col_index, new_value) => {
+                self.cell_drafts
+                    .insert((row_index, col_index), (new_value, None));
                 Task::none()
             }
+            // a typed value is only checked and auto committed once it is complete,
+            // the cell below is focused so a column can be filled in from the keyboard
+            Self::EventType::SubmitCell(row_index, col_index) => {
+                if !self.submit_cell_draft(row_index, col_index) {
+                    return Task::none();
+                }
+                Task::batch([
+                    self.auto_commit(),
+                    self.focus_moved_cell((row_index, col_index), CellFocusMove::Down),
+                ])
+            }
+            Self::EventType::MoveCellFocus(cell_focus_move) => {
+                operate(find_focused()).map(move |focused_id| {
+                    Self::EventType::FocusedWidgetFound(focused_id, cell_focus_move).message()
+                })
+            }
+            // the focus may be outside of the grid, then the key is ignored
+            Self::EventType::FocusedWidgetFound(focused_id, cell_focus_move) => {
+                match self.cell_with_id(&focused_id) {
+                    Some(cell) => self.focus_moved_cell(cell, cell_focus_move),
+                    None => Task::none(),
+                }
+            }
+            Self::EventType::SetCellNull(row_index, col_index) => {
+                self.cell_drafts.remove(&(row_index, col_index));
+                self.set_cell_value(row_index, col_index, None);
+                self.auto_commit()
+            }
             Self::EventType::DeleteRow(row_index) => {
                 if let Some(table_inserted_data) = self.table_inserted_data.as_mut() {
                     self.table_data.add_delete_row_event(row_index);
                     table_inserted_data.rows.remove(row_index);
                 }
+                self.cell_drafts.clear();
+                self.selected_row_indexes.clear();
+                self.auto_commit()
+            }
+            Self::EventType::ToggleRowSelection(row_index) => {
+                if let Some(position) = self
+                    .selected_row_indexes
+                    .iter()
+                    .position(|selected_row_index| *selected_row_index == row_index)
+                {
+                    self.selected_row_indexes.remove(position);
+                } else {
+                    self.selected_row_indexes.push(row_index);
+                }
+                Task::none()
+            }
+            Self::EventType::ToggleAllRowsSelection(is_selected) => {
+                self.selected_row_indexes = match self.table_inserted_data.as_ref() {
+                    Some(table_inserted_data) if is_selected => {
+                        (0..table_inserted_data.rows.len()).collect()
+                    }
+                    _ => vec![],
+                };
+                Task::none()
+            }
+            Self::EventType::DeleteSelectedRows => {
+                self.table_data
+                    .add_delete_rows_event(&self.selected_row_indexes);
+                self.refresh_displayed_rows();
+                self.auto_commit()
+            }
+            Self::EventType::DuplicateRow(row_index) => {
+                if self.table_data.duplicate_row(row_index) {
+                    self.refresh_displayed_rows();
+                }
+                self.auto_commit()
+            }
+            Self::EventType::PreviewChanges => {
+                let table_data = self.table_data.clone();
+                Task::perform(async move { table_data.preview_sql().await }, |queries| {
+                    Self::EventType::SetChangesPreview(queries).message()
+                })
+            }
+            Self::EventType::SetChangesPreview(queries) => {
+                self.changes_preview = Some(queries);
+                Task::none()
+            }
+            Self::EventType::CloseChangesPreview => {
+                self.changes_preview = None;
+                Task::none()
+            }
+            Self::EventType::TogglePendingDiff => {
+                self.show_pending_diff = !self.show_pending_diff;
+                Task::none()
+            }
+            Self::EventType::ToggleDuplicateColumn(column_name) => {
+                if let Some(column_index) = self
+                    .duplicate_column_names
+                    .iter()
+                    .position(|duplicate_column_name| *duplicate_column_name == column_name)
+                {
+                    self.duplicate_column_names.remove(column_index);
+                } else {
+                    self.duplicate_column_names.push(column_name);
+                }
+                Task::none()
+            }
+            Self::EventType::FindDuplicates => {
+                let table_data = self.table_data.clone();
+                let column_names = self.duplicate_column_names.clone();
+                Task::perform(
+                    async move { table_data.find_duplicates(column_names).await },
+                    |duplicate_groups| {
+                        Self::EventType::SetDuplicateGroups(duplicate_groups).message()
+                    },
+                )
+            }
+            Self::EventType::SetDuplicateGroups(duplicate_groups) => {
+                self.duplicate_groups = Some(duplicate_groups);
+                Task::none()
+            }
+            Self::EventType::CloseDuplicateGroups => {
+                self.duplicate_groups = None;
+                Task::none()
+            }
+            Self::EventType::UpdateImportPath(import_path) => {
+                self.import_path = import_path;
+                Task::none()
+            }
+            Self::EventType::ImportCsv => {
+                if self.table_data.import_csv(Path::new(&self.import_path)) > 0 {
+                    self.refresh_displayed_rows();
+                }
+                self.auto_commit()
+            }
+            Self::EventType::UndoEvent => {
+                if self.table_data.undo_last_event() {
+                    self.refresh_displayed_rows();
+                }
+                Task::none()
+            }
+            Self::EventType::RedoEvent => {
+                if self.table_data.redo_last_event() {
+                    self.refresh_displayed_rows();
+                }
                 Task::none()
             }
             Self::EventType::AddRow => {
                 if let Some(table_inserted_data) = self.table_inserted_data.as_mut() {
-                    let values: Vec<String> = table_inserted_data
-                        .column_names
-                        .iter()
-                        .map(|col_name| String::new())
-                        .collect();
-
-                    self.table_data.add_insert_row_event(values.clone());
+                    let values: Vec<Option<String>> =
+                        vec![None; table_inserted_data.column_names.len()];
 
-                    table_inserted_data.rows.push(values);
+                    if self.table_data.add_insert_row_event(values.clone()) {
+                        table_inserted_data.rows.push(values);
+                    }
                 }
 
+                self.auto_commit()
+            }
+            Self::EventType::UpdateNewRowValue(column_name, value) => {
+                if value.is_empty() {
+                    self.new_row_values.remove(&column_name);
+                } else {
+                    self.new_row_values.insert(column_name, value);
+                }
                 Task::none()
             }
+            // the form keeps its values when the row is rejected so they can be corrected
+            Self::EventType::InsertNewRow => {
+                if self
+                    .table_data
+                    .add_partial_insert_row_event(self.new_row_values.clone())
+                {
+                    self.new_row_values.clear();
+                    self.refresh_displayed_rows();
+                }
+                self.auto_commit()
+            }
         }
     }
 }
 
+// loading another table, page, sort or filter drops the queued change events
+fn discards_pending_changes(message: &TableDataMessage) -> bool {
+    matches!(
+        message,
+        TableDataMessage::GetTableData(_)
+            | TableDataMessage::FollowForeignKey(..)
+            | TableDataMessage::ChangePage(_)
+            | TableDataMessage::SortByColumn(_)
+            | TableDataMessage::AddFilter
+            | TableDataMessage::RemoveFilter(_)
+            | TableDataMessage::LookUpPrimaryKey
+    )
+}
+
 impl TableDataUI {
     pub fn new(table_data: Arc<BTableData>) -> Self {
         Self {
             table_data,
             table_inserted_data: None,
             selected_table_name: None,
+            foreign_keys: vec![],
+            column_comments: HashMap::new(),
+            foreign_key_preview: None,
+            show_row_numbers: false,
+            order_by: None,
+            filters: vec![],
+            filter_column_name: None,
+            filter_operator: BOperator::default(),
+            filter_value: String::new(),
+            changes_preview: None,
+            show_pending_diff: false,
+            import_path: String::new(),
+            primary_key_lookup_values: HashMap::new(),
+            new_row_values: HashMap::new(),
+            cell_drafts: HashMap::new(),
+            selected_row_indexes: vec![],
+            message_awaiting_discard: None,
+            duplicate_column_names: vec![],
+            duplicate_groups: None,
+            update_summary: None,
         }
     }
 
-    pub fn get_table_name(&self) -> Option<String> {
+    // filtered results start again from the first page
+    fn apply_filters(&self, filters: Vec<BCondition>) -> Task<Message> {
         if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
-            Some(table_inserted_data.table_name.clone())
+            let table_name = table_inserted_data.table_name.clone();
+            let page = BPage {
+                size: table_inserted_data.page.size,
+                number: 0,
+            };
+            let table_data = self.table_data.clone();
+            Task::perform(
+                async move {
+                    table_data.set_filters(filters).await;
+                    table_data.set_table_data(table_name, page).await;
+                },
+                |_| <TableDataUI as UIComponent>::EventType::SetTableData.message(),
+            )
         } else {
-            None
+            Task::none()
+        }
+    }
+
+    // returns false if the value was rejected
+    fn set_cell_value(
+        &mut self,
+        row_index: usize,
+        col_index: usize,
+        new_value: Option<String>,
+    ) -> bool {
+        if let Some(table_inserted_data) = self.table_inserted_data.as_mut() {
+            if let Some(row_data) = table_inserted_data.rows.get_mut(row_index) {
+                if let Some(cell) = row_data.get_mut(col_index) {
+                    let column_name = table_inserted_data.column_names[col_index].clone();
+
+                    if !self.table_data.add_modify_row_column_value_event(
+                        row_index,
+                        column_name,
+                        new_value.clone(),
+                    ) {
+                        return false;
+                    }
+                    *cell = new_value;
+                }
+            }
+        }
+        true
+    }
+
+    // the rejected value stays in the cell with the reason shown next to it,
+    // the business component writes the warning to the console
+    fn submit_cell_draft(&mut self, row_index: usize, col_index: usize) -> bool {
+        let Some((draft, _)) = self.cell_drafts.remove(&(row_index, col_index)) else {
+            return true;
+        };
+        // clearing a cell that can't hold an empty string makes it NULL
+        let new_value = Some(draft.clone()).filter(|draft| {
+            self.table_inserted_data
+                .as_ref()
+                .is_some_and(|table_inserted_data| {
+                    !table_inserted_data.data_types[col_index].is_null(&Some(draft.clone()))
+                })
+        });
+        if self.set_cell_value(row_index, col_index, new_value.clone()) {
+            return true;
+        }
+        let error = new_value
+            .and_then(|new_value| {
+                self.table_data
+                    .validate_cell_value(col_index, &new_value)
+                    .err()
+            })
+            .unwrap_or_else(|| String::from("Value was rejected, see the console"));
+        self.cell_drafts
+            .insert((row_index, col_index), (draft, Some(error)));
+        false
+    }
+
+    // returns false if a typed value was rejected
+    fn submit_cell_drafts(&mut self) -> bool {
+        let mut cells: Vec<(usize, usize)> = self.cell_drafts.keys().copied().collect();
+        cells.sort();
+        cells
+            .into_iter()
+            .fold(true, |all_submitted, (row_index, col_index)| {
+                self.submit_cell_draft(row_index, col_index) && all_submitted
+            })
+    }
+
+    // in batch mode the edit stays queued until the user commits it
+    fn auto_commit(&self) -> Task<Message> {
+        if !self.table_data.is_auto_commit() {
+            return Task::none();
+        }
+        let table_data = self.table_data.clone();
+        Task::perform(
+            async move {
+                table_data.auto_commit_table_data().await;
+            },
+            |_| <TableDataUI as UIComponent>::EventType::SetTableData.message(),
+        )
+    }
+
+    fn cell_with_id(&self, id: &Id) -> Option<(usize, usize)> {
+        let table_inserted_data = self.table_inserted_data.as_ref()?;
+        let column_count = table_inserted_data.column_names.len();
+        (0..table_inserted_data.rows.len())
+            .flat_map(|row_index| (0..column_count).map(move |col_index| (row_index, col_index)))
+            .find(|(row_index, col_index)| Id::from(cell_input_id(*row_index, *col_index)) == *id)
+    }
+
+    fn focus_moved_cell(
+        &self,
+        cell: (usize, usize),
+        cell_focus_move: CellFocusMove,
+    ) -> Task<Message> {
+        self.table_inserted_data
+            .as_ref()
+            .and_then(|table_inserted_data| {
+                moved_cell(
+                    cell,
+                    cell_focus_move,
+                    table_inserted_data.rows.len(),
+                    &table_inserted_data.read_only_columns,
+                )
+            })
+            .map_or(Task::none(), |(row_index, col_index)| {
+                text_input::focus(cell_input_id(row_index, col_index))
+            })
+    }
+
+    // selected indexes may point at other rows once the rows change
+    fn refresh_displayed_rows(&mut self) {
+        if let Some(table_inserted_data) = self.table_inserted_data.as_mut() {
+            table_inserted_data.rows = self.table_data.get_displayed_rows();
         }
+        self.cell_drafts.clear();
+        self.selected_row_indexes.clear();
+    }
+
+    // typed values that were never submitted are lost too when the table is left
+    pub fn has_pending_changes(&self) -> bool {
+        self.table_data.has_pending_changes() || !self.cell_drafts.is_empty()
+    }
+
+    pub fn pending_change_count(&self) -> usize {
+        self.table_data.pending_change_count()
     }
+
     pub fn content<'a>(&'a self) -> Element<'a, Message> {
         // Combine the picklist, table content, and update button into a single column
         Column::new()
             .spacing(20)
             .push(text("Table Data Viewer").size(32).style(|_| text_style()))
+            .push(self.discard_changes_panel())
+            .push(self.update_summary_panel())
             .push(self.create_picklist())
+            .push(self.row_numbers_checkbox())
+            .push(self.auto_commit_checkbox())
+            .push(self.filter_controls())
+            .push(self.primary_key_lookup())
             .push(self.create_table_content())
+            .push(self.new_row_form())
+            .push(self.undo_redo_buttons())
+            .push(self.import_csv_controls())
+            .push(self.changes_preview_panel())
+            .push(self.pending_diff_panel())
+            .push(self.duplicate_finder())
             .push(self.update_table_data_button()) // Add the button at the bottom
             .push(self.reset_table_data_button())
+            .push(self.discard_changes_button())
             .into()
     }
     fn create_picklist<'a>(&'a self) -> Element<'a, Message> {
@@ -153,12 +799,222 @@ impl TableDataUI {
         PickList::new(
             table_names.clone(),
             self.selected_table_name.clone(),
-            |selected| TableDataMessage::GetTableData(selected.to_string()).message(),
+            // another table opens in its own tab so the edits made here are kept
+            |selected| ComponentsMessage::OpenTableData(selected.to_string()).message(),
         )
         .style(|_, _| picklist_style())
         .into()
     }
 
+    fn row_numbers_checkbox<'a>(&'a self) -> Element<'a, Message> {
+        checkbox("Show Row Numbers", self.show_row_numbers)
+            .on_toggle(|_| <TableDataUI as UIComponent>::EventType::ToggleRowNumbers.message())
+            .into()
+    }
+
+    // switching modes with queued edits would leave them out of the auto commit
+    fn auto_commit_checkbox<'a>(&'a self) -> Element<'a, Message> {
+        checkbox("Auto Commit", self.table_data.is_auto_commit())
+            .on_toggle_maybe(
+                (!self.table_data.has_pending_changes()).then_some(|auto_commit| {
+                    <TableDataUI as UIComponent>::EventType::ToggleAutoCommit(auto_commit).message()
+                }),
+            )
+            .into()
+    }
+
+    fn filter_controls<'a>(&'a self) -> Element<'a, Message> {
+        let Some(table_inserted_data) = self.table_inserted_data.as_ref() else {
+            return Column::new().into();
+        };
+        let column_picklist = PickList::new(
+            table_inserted_data.column_names.clone(),
+            self.filter_column_name.clone(),
+            |column_name| {
+                <TableDataUI as UIComponent>::EventType::SelectFilterColumn(column_name).message()
+            },
+        )
+        .placeholder("Column")
+        .style(|_, _| picklist_style());
+        let operator_picklist = PickList::new(
+            BOperator::ALL.to_vec(),
+            Some(self.filter_operator.clone()),
+            |operator| {
+                <TableDataUI as UIComponent>::EventType::SelectFilterOperator(operator).message()
+            },
+        )
+        .style(|_, _| picklist_style());
+        let value_input = text_input("Value", &self.filter_value)
+            .on_input(|value| {
+                <TableDataUI as UIComponent>::EventType::UpdateFilterValue(value).message()
+            })
+            .padding(5)
+            .width(150)
+            .style(|_, _| text_input_style());
+        let add_filter_button = button(text("Add Filter").size(16).style(|_| text_style()))
+            .on_press_maybe(
+                self.filter_column_name
+                    .as_ref()
+                    .map(|_| <TableDataUI as UIComponent>::EventType::AddFilter.message()),
+            )
+            .padding(5)
+            .style(|_, _| reset_table_data_button_style());
+
+        let active_filters = self.filters.iter().enumerate().fold(
+            Row::new().spacing(10),
+            |active_filters, (filter_index, filter)| {
+                active_filters.push(
+                    button(
+                        text(format!(
+                            "{} {} {} ✖",
+                            filter.column_name, filter.operator, filter.value
+                        ))
+                        .size(14)
+                        .style(|_| text_style()),
+                    )
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::RemoveFilter(filter_index)
+                            .message(),
+                    )
+                    .padding(5)
+                    .style(|_, _| reset_table_data_button_style()),
+                )
+            },
+        );
+
+        Column::new()
+            .spacing(10)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_y(Vertical::Center)
+                    .push(column_picklist)
+                    .push(operator_picklist)
+                    .push(value_input)
+                    .push(add_filter_button),
+            )
+            .push(active_filters)
+            .into()
+    }
+
+    // one input per primary key column so composite keys can be looked up too
+    fn primary_key_lookup<'a>(&'a self) -> Element<'a, Message> {
+        let primary_key_column_names = self.table_data.get_primary_key_column_names();
+        if self.table_inserted_data.is_none() || primary_key_column_names.is_empty() {
+            return Column::new().into();
+        }
+        let is_complete = primary_key_column_names.iter().all(|column_name| {
+            self.primary_key_lookup_values
+                .get(column_name)
+                .is_some_and(|value| !value.is_empty())
+        });
+        let lookup_inputs = primary_key_column_names.into_iter().fold(
+            Row::new().spacing(10).align_y(Vertical::Center),
+            |lookup_inputs, column_name| {
+                let value = self
+                    .primary_key_lookup_values
+                    .get(&column_name)
+                    .cloned()
+                    .unwrap_or_default();
+                lookup_inputs.push(
+                    text_input(&column_name, &value)
+                        .on_input(move |value| {
+                            <TableDataUI as UIComponent>::EventType::UpdatePrimaryKeyLookupValue(
+                                column_name.clone(),
+                                value,
+                            )
+                            .message()
+                        })
+                        .on_submit_maybe(is_complete.then(|| {
+                            <TableDataUI as UIComponent>::EventType::LookUpPrimaryKey.message()
+                        }))
+                        .padding(5)
+                        .width(150)
+                        .style(|_, _| text_input_style()),
+                )
+            },
+        );
+        lookup_inputs
+            .push(
+                button(text("Go to Row").size(16).style(|_| text_style()))
+                    .on_press_maybe(is_complete.then(|| {
+                        <TableDataUI as UIComponent>::EventType::LookUpPrimaryKey.message()
+                    }))
+                    .padding(5)
+                    .style(|_, _| reset_table_data_button_style()),
+            )
+            .into()
+    }
+
+    // serial, identity and computed columns are assigned by the database so they have no input
+    fn new_row_form<'a>(&'a self) -> Element<'a, Message> {
+        let Some(table_inserted_data) = self.table_inserted_data.as_ref() else {
+            return Column::new().into();
+        };
+        let new_row_inputs = zip(
+            &table_inserted_data.column_names,
+            &table_inserted_data.read_only_columns,
+        )
+        .enumerate()
+        .filter(|(_, (_, is_read_only))| !**is_read_only)
+        .fold(
+            Row::new().spacing(10).align_y(Vertical::Center),
+            |new_row_inputs, (col_index, (column_name, _))| {
+                let value = self
+                    .new_row_values
+                    .get(column_name)
+                    .cloned()
+                    .unwrap_or_default();
+                // the row is still rejected on insert, this shows which value is at fault,
+                // empty values are left out of the insert
+                let error = (!value.is_empty())
+                    .then(|| self.table_data.validate_cell_value(col_index, &value).err())
+                    .flatten();
+                let is_invalid = error.is_some();
+                let column_name = column_name.clone();
+                let value_input = text_input(&column_name, &value)
+                    .on_input(move |value| {
+                        <TableDataUI as UIComponent>::EventType::UpdateNewRowValue(
+                            column_name.clone(),
+                            value,
+                        )
+                        .message()
+                    })
+                    .padding(5)
+                    .width(150)
+                    .style(move |_, _| {
+                        if is_invalid {
+                            invalid_text_input_style()
+                        } else {
+                            text_input_style()
+                        }
+                    });
+                match error {
+                    Some(error) => new_row_inputs.push(tooltip(
+                        value_input,
+                        container(text(error).size(12))
+                            .padding(5)
+                            .style(tooltip_style),
+                        tooltip::Position::Bottom,
+                    )),
+                    None => new_row_inputs.push(value_input),
+                }
+            },
+        );
+        new_row_inputs
+            .push(
+                button(text("Insert Row").size(16).style(|_| text_style()))
+                    .on_press_maybe(
+                        (!self.new_row_values.is_empty()).then(|| {
+                            <TableDataUI as UIComponent>::EventType::InsertNewRow.message()
+                        }),
+                    )
+                    .padding(5)
+                    .style(|_, _| add_table_row_button_style()),
+            )
+            .into()
+    }
+
     fn create_table_content<'a>(&'a self) -> Element<'a, Message> {
         if let Some(ref table_inserted_data) = self.table_inserted_data {
             let table_with_header = Column::new()
@@ -167,7 +1023,13 @@ impl TableDataUI {
                     &table_inserted_data.column_names,
                     &table_inserted_data.rows,
                 ))
-                .push(self.add_row_button());
+                .push(self.page_controls(table_inserted_data))
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(self.add_row_button())
+                        .push(self.delete_selected_rows_button()),
+                );
 
             container(table_with_header)
                 .style(|_| table_container_style())
@@ -182,18 +1044,27 @@ impl TableDataUI {
     fn table_column_names_and_rows<'a>(
         &'a self,
         column_names: &Vec<String>,
-        rows: &[Vec<String>],
+        rows: &[Vec<Option<String>>],
     ) -> Scrollable<'a, Message> {
         let mut table_column_names_and_rows = Column::new();
 
-        let column_names = column_names.iter().enumerate().fold(
-            Row::new().spacing(10),
-            |row, (col_index, col_name)| {
-                row.push(
-                    container(text(col_name.clone()).size(16).style(|_| text_style())).width(100), // Ensure each column takes equal space
-                )
-            },
+        let all_rows_selected = !rows.is_empty() && self.selected_row_indexes.len() == rows.len();
+        let mut column_names_row = Row::new().spacing(10).push(
+            container(checkbox("", all_rows_selected).on_toggle(|is_selected| {
+                <TableDataUI as UIComponent>::EventType::ToggleAllRowsSelection(is_selected)
+                    .message()
+            }))
+            .width(30),
         );
+        if self.show_row_numbers {
+            column_names_row = column_names_row
+                .push(container(text("#").size(16).style(|_| text_style())).width(40));
+        }
+        let column_names = column_names.iter().fold(column_names_row, |row, col_name| {
+            row.push(
+                container(self.column_name_header(col_name)).width(100), // Ensure each column takes equal space
+            )
+        });
         table_column_names_and_rows = table_column_names_and_rows.push(column_names);
 
         for (row_index, row) in rows.iter().enumerate() {
@@ -209,16 +1080,267 @@ impl TableDataUI {
             .height(Length::Fill)
     }
 
-    fn create_table_row<'a>(&'a self, row: &[String], row_index: usize) -> Row<'a, Message> {
-        let mut table_row = Row::new().spacing(10).align_y(Vertical::Center);
+    // the comment of a column is shown when hovering its name
+    fn column_name_header<'a>(&'a self, column_name: &str) -> Element<'a, Message> {
+        let sort_button = self.column_name_sort_button(column_name);
+        let references = self.column_foreign_keys(column_name).fold(
+            Column::new().push(sort_button),
+            |references, foreign_key| {
+                references.push(
+                    text(format!(
+                        "→ {}.{}",
+                        foreign_key.referenced_table, foreign_key.referenced_column
+                    ))
+                    .size(12)
+                    .style(|_| text_style()),
+                )
+            },
+        );
+        match self.column_comments.get(column_name) {
+            Some(comment) => tooltip(
+                references,
+                container(text(comment.as_str()).size(14))
+                    .padding(5)
+                    .style(tooltip_style),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+            None => references.into(),
+        }
+    }
+
+    fn column_name_sort_button<'a>(&'a self, column_name: &str) -> Button<'a, Message> {
+        let sort_indicator = match self.order_by.as_ref() {
+            Some((sorted_column_name, BSortDirection::Ascending))
+                if sorted_column_name == column_name =>
+            {
+                " ▲"
+            }
+            Some((sorted_column_name, BSortDirection::Descending))
+                if sorted_column_name == column_name =>
+            {
+                " ▼"
+            }
+            _ => "",
+        };
+        button(
+            text(format!("{}{}", column_name, sort_indicator))
+                .size(16)
+                .style(|_| text_style()),
+        )
+        .on_press(
+            <TableDataUI as UIComponent>::EventType::SortByColumn(column_name.to_string())
+                .message(),
+        )
+        .padding(0)
+        .style(|_, _| column_name_button_style())
+    }
+
+    fn create_table_row<'a>(
+        &'a self,
+        row: &[Option<String>],
+        row_index: usize,
+    ) -> Row<'a, Message> {
+        let is_selected = self.selected_row_indexes.contains(&row_index);
+        let mut table_row = Row::new().spacing(10).align_y(Vertical::Center).push(
+            container(checkbox("", is_selected).on_toggle(move |_| {
+                <TableDataUI as UIComponent>::EventType::ToggleRowSelection(row_index).message()
+            }))
+            .width(30),
+        );
+        if self.show_row_numbers {
+            // rows are displayed in their current order so the row number is 1-based current index
+            let offset = self
+                .table_inserted_data
+                .as_ref()
+                .map(|table_inserted_data| table_inserted_data.page.offset())
+                .unwrap_or_default();
+            table_row = table_row.push(
+                container(
+                    text((offset + row_index + 1).to_string())
+                        .size(16)
+                        .style(|_| text_style()),
+                )
+                .width(40),
+            );
+        }
         for (col_index, value) in row.iter().enumerate() {
+            let cell: Element<'a, Message> = if self.is_read_only_column(col_index) {
+                // serial, identity and computed values are assigned by the database
+                text(value.clone().unwrap_or(String::from("NULL")))
+                    .size(16)
+                    .style(|_| text_style())
+                    .into()
+            } else if self.is_foreign_key_column(col_index) {
+                self.create_foreign_key_cell(row_index, col_index, value)
+            } else {
+                self.create_table_column_value(row_index, col_index, value)
+            };
+            // the displayed value includes edits that are not saved yet
+            let copy_button = button(text("⧉").size(12))
+                .on_press(
+                    <TableDataUI as UIComponent>::EventType::CopyToClipboard(
+                        value.clone().unwrap_or_default(),
+                    )
+                    .message(),
+                )
+                .padding(2)
+                .style(|_, _| reset_table_data_button_style());
             table_row = table_row.push(
-                container(self.create_table_column_value(row_index, col_index, value.as_str()))
-                    .width(100) // Match width with header columns
-                    .align_y(Vertical::Center),
+                container(
+                    Row::new()
+                        .spacing(2)
+                        .align_y(Vertical::Center)
+                        .push(cell)
+                        .push(copy_button),
+                )
+                .width(100) // Match width with header columns
+                .align_y(Vertical::Center),
             );
         }
-        table_row.push(self.delete_row_button(row_index))
+        table_row
+            .push(self.copy_row_button(row))
+            .push(self.duplicate_row_button(row_index))
+            .push(self.delete_row_button(row_index))
+    }
+
+    fn is_read_only_column(&self, col_index: usize) -> bool {
+        self.table_inserted_data
+            .as_ref()
+            .is_some_and(|table_inserted_data| table_inserted_data.read_only_columns[col_index])
+    }
+
+    fn is_foreign_key_column(&self, col_index: usize) -> bool {
+        if let Some(table_inserted_data) = self.table_inserted_data.as_ref() {
+            let column_name = &table_inserted_data.column_names[col_index];
+            self.foreign_keys
+                .iter()
+                .any(|foreign_key| foreign_key.column_name == *column_name)
+        } else {
+            false
+        }
+    }
+
+    // a column can reference more than one table
+    fn column_foreign_keys<'a>(
+        &'a self,
+        column_name: &'a str,
+    ) -> impl Iterator<Item = &'a BColumnForeignKey> {
+        self.foreign_keys
+            .iter()
+            .filter(move |foreign_key| foreign_key.column_name == column_name)
+    }
+
+    fn create_foreign_key_cell<'a>(
+        &'a self,
+        row_index: usize,
+        col_index: usize,
+        value: &Option<String>,
+    ) -> Element<'a, Message> {
+        let preview_button = button(text("🔗").size(12))
+            .on_press(
+                <TableDataUI as UIComponent>::EventType::PreviewForeignKey(row_index, col_index)
+                    .message(),
+            )
+            .padding(2)
+            .style(|_, _| reset_table_data_button_style());
+        let mut cell = Column::new().spacing(5).push(
+            Row::new()
+                .spacing(2)
+                .align_y(Vertical::Center)
+                .push(self.create_table_column_value(row_index, col_index, value))
+                .push(preview_button),
+        );
+        // NULL doesn't reference a row so there is nothing to navigate to
+        if let (Some(value), Some(table_inserted_data)) = (value, &self.table_inserted_data) {
+            let column_name = &table_inserted_data.column_names[col_index];
+            for foreign_key in self.column_foreign_keys(column_name) {
+                cell = cell.push(
+                    button(
+                        text(format!(
+                            "→ {}.{}",
+                            foreign_key.referenced_table, foreign_key.referenced_column
+                        ))
+                        .size(12),
+                    )
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::FollowForeignKey(
+                            foreign_key.referenced_table.clone(),
+                            foreign_key.referenced_column.clone(),
+                            value.clone(),
+                        )
+                        .message(),
+                    )
+                    .padding(2)
+                    .style(|_, _| reset_table_data_button_style()),
+                );
+            }
+        }
+        if let Some((preview_row_index, preview_col_index, referenced_row)) =
+            &self.foreign_key_preview
+        {
+            if *preview_row_index == row_index && *preview_col_index == col_index {
+                cell = cell.push(self.foreign_key_preview_popover(referenced_row));
+            }
+        }
+        cell.into()
+    }
+
+    fn foreign_key_preview_popover<'a>(
+        &'a self,
+        referenced_row: &Option<Vec<(String, String)>>,
+    ) -> Element<'a, Message> {
+        let preview = if let Some(referenced_row) = referenced_row {
+            referenced_row
+                .iter()
+                .fold(Column::new().spacing(2), |preview, (column_name, value)| {
+                    preview.push(
+                        text(format!("{}: {}", column_name, value))
+                            .size(12)
+                            .style(|_| text_style()),
+                    )
+                })
+        } else {
+            Column::new().push(text("(not found)").size(12).style(|_| text_style()))
+        };
+        container(preview)
+            .padding(5)
+            .style(|_| table_container_style())
+            .into()
+    }
+
+    fn page_controls<'a>(&'a self, table_inserted_data: &BTableInsertedData) -> Row<'a, Message> {
+        let page_number = table_inserted_data.page.number;
+        let page_count = table_inserted_data
+            .page
+            .page_count(table_inserted_data.total_row_count);
+        let previous_button = button(text("◀ Previous").size(16).style(|_| text_style()))
+            .on_press_maybe((page_number > 0).then(|| {
+                <TableDataUI as UIComponent>::EventType::ChangePage(page_number - 1).message()
+            }))
+            .padding(10)
+            .style(|_, _| reset_table_data_button_style());
+        let next_button = button(text("Next ▶").size(16).style(|_| text_style()))
+            .on_press_maybe((page_number + 1 < page_count).then(|| {
+                <TableDataUI as UIComponent>::EventType::ChangePage(page_number + 1).message()
+            }))
+            .padding(10)
+            .style(|_, _| reset_table_data_button_style());
+        Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(previous_button)
+            .push(
+                text(format!(
+                    "Page {} of {} ({} rows)",
+                    page_number + 1,
+                    page_count,
+                    table_inserted_data.total_row_count
+                ))
+                .size(16)
+                .style(|_| text_style()),
+            )
+            .push(next_button)
     }
 
     fn delete_row_button<'a>(&'a self, row_index: usize) -> Button<'a, Message> {
@@ -230,6 +1352,40 @@ impl TableDataUI {
         .style(|_, _| delete_table_row_button_style()) // App
     }
 
+    fn delete_selected_rows_button<'a>(&'a self) -> Button<'a, Message> {
+        button(
+            text(format!(
+                "Delete Selected ({})",
+                self.selected_row_indexes.len()
+            ))
+            .size(16)
+            .style(|_| text_style()),
+        )
+        .on_press_maybe(
+            (!self.selected_row_indexes.is_empty())
+                .then(|| <TableDataUI as UIComponent>::EventType::DeleteSelectedRows.message()),
+        )
+        .padding(10)
+        .style(|_, _| delete_table_row_button_style())
+    }
+
+    fn copy_row_button<'a>(&'a self, row: &[Option<String>]) -> Button<'a, Message> {
+        button(text("Copy").size(16).style(|_| text_style()))
+            .on_press(
+                <TableDataUI as UIComponent>::EventType::CopyToClipboard(tab_separated_row(row))
+                    .message(),
+            )
+            .padding(10)
+            .style(|_, _| add_table_row_button_style())
+    }
+
+    fn duplicate_row_button<'a>(&'a self, row_index: usize) -> Button<'a, Message> {
+        button(text("Duplicate").size(16).style(|_| text_style()))
+            .on_press(<TableDataUI as UIComponent>::EventType::DuplicateRow(row_index).message())
+            .padding(10)
+            .style(|_, _| add_table_row_button_style())
+    }
+
     fn add_row_button<'a>(&'a self) -> Button<'a, Message> {
         button(
             text("Add Row").size(16).style(|_| text_style()), // Style the button text
@@ -239,6 +1395,362 @@ impl TableDataUI {
         .style(|_, _| add_table_row_button_style()) // App
     }
 
+    fn import_csv_controls<'a>(&'a self) -> Row<'a, Message> {
+        Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(
+                text_input("Path to CSV file", &self.import_path)
+                    .on_input(|import_path| {
+                        <TableDataUI as UIComponent>::EventType::UpdateImportPath(import_path)
+                            .message()
+                    })
+                    .padding(5)
+                    .width(300)
+                    .style(|_, _| text_input_style()),
+            )
+            .push(
+                button(text("Import CSV").size(16).style(|_| text_style()))
+                    .on_press_maybe(
+                        (self.table_inserted_data.is_some() && !self.import_path.is_empty())
+                            .then(|| <TableDataUI as UIComponent>::EventType::ImportCsv.message()),
+                    )
+                    .padding(10)
+                    .style(|_, _| reset_table_data_button_style()),
+            )
+    }
+
+    fn discard_changes_panel<'a>(&'a self) -> Element<'a, Message> {
+        if self.message_awaiting_discard.is_none() {
+            return Column::new().into();
+        }
+        let buttons = Row::new()
+            .spacing(10)
+            .push(
+                button(text("Discard").size(14).style(|_| text_style()))
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::ConfirmDiscardChanges.message(),
+                    )
+                    .padding(5)
+                    .style(|_, _| delete_table_row_button_style()),
+            )
+            .push(
+                button(text("Cancel").size(14).style(|_| text_style()))
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::CancelDiscardChanges.message(),
+                    )
+                    .padding(5)
+                    .style(|_, _| reset_table_data_button_style()),
+            );
+        container(
+            Column::new()
+                .spacing(10)
+                .push(
+                    text("There are unsaved changes, discard them?")
+                        .size(16)
+                        .style(|_| text_style()),
+                )
+                .push(buttons),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(|_| table_container_style())
+        .into()
+    }
+
+    // the details are the statements shown by the changes preview
+    fn update_summary_panel<'a>(&'a self) -> Element<'a, Message> {
+        let Some(update_summary) = self.update_summary.as_ref() else {
+            return Column::new().into();
+        };
+        let buttons = Row::new()
+            .spacing(10)
+            .push(
+                button(text("Commit").size(14).style(|_| text_style()))
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::ConfirmUpdateTableData.message(),
+                    )
+                    .padding(5)
+                    .style(|_, _| update_table_data_button_style()),
+            )
+            .push(
+                button(text("Show details").size(14).style(|_| text_style()))
+                    .on_press_maybe(
+                        self.changes_preview.is_none().then(|| {
+                            <TableDataUI as UIComponent>::EventType::PreviewChanges.message()
+                        }),
+                    )
+                    .padding(5)
+                    .style(|_, _| reset_table_data_button_style()),
+            )
+            .push(
+                button(text("Cancel").size(14).style(|_| text_style()))
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::CancelUpdateTableData.message(),
+                    )
+                    .padding(5)
+                    .style(|_, _| reset_table_data_button_style()),
+            );
+        let mut summary = Column::new().spacing(10).push(
+            text(format!("Commit {}?", update_summary))
+                .size(16)
+                .style(|_| text_style()),
+        );
+        // deleted rows can't be brought back after the commit
+        if update_summary.deletes > 0 {
+            summary = summary.push(
+                text(format!(
+                    "{} row(s) will be deleted from the database",
+                    update_summary.deletes
+                ))
+                .size(14)
+                .color(Color::from_rgb(1.0, 0.4, 0.4)),
+            );
+        }
+        container(summary.push(buttons))
+            .padding(10)
+            .width(Length::Fill)
+            .style(|_| table_container_style())
+            .into()
+    }
+
+    fn changes_preview_panel<'a>(&'a self) -> Element<'a, Message> {
+        let Some(queries) = self.changes_preview.as_ref() else {
+            return Column::new().into();
+        };
+        let header = Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(text("Pending SQL").size(20).style(|_| text_style()))
+            .push(
+                button(text("Close").size(14).style(|_| text_style()))
+                    .on_press(
+                        <TableDataUI as UIComponent>::EventType::CloseChangesPreview.message(),
+                    )
+                    .padding(5)
+                    .style(|_, _| reset_table_data_button_style()),
+            );
+        let statements = if queries.is_empty() {
+            Column::new().push(text("No pending changes").size(14).style(|_| text_style()))
+        } else {
+            queries
+                .iter()
+                .fold(Column::new().spacing(5), |statements, query| {
+                    statements.push(text(format!("{};", query)).size(14).style(|_| text_style()))
+                })
+        };
+        container(Column::new().spacing(10).push(header).push(statements))
+            .padding(10)
+            .width(Length::Fill)
+            .style(|_| table_container_style())
+            .into()
+    }
+
+    // inserted rows are green, deleted rows red and modified cells show the committed value
+    fn pending_diff_panel<'a>(&'a self) -> Element<'a, Message> {
+        if !self.show_pending_diff {
+            return Column::new().into();
+        }
+        let format_value = |value: &Option<String>| value.clone().unwrap_or(String::from("NULL"));
+        let format_row = |row: &Vec<(String, Option<String>)>| {
+            row.iter()
+                .map(|(column_name, value)| format!("{} = {}", column_name, format_value(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let pending_diff = self.table_data.pending_diff();
+        let row_diffs = if pending_diff.is_empty() {
+            Column::new().push(text("No pending changes").size(14).style(|_| text_style()))
+        } else {
+            pending_diff
+                .iter()
+                .fold(Column::new().spacing(5), |row_diffs, row_diff| {
+                    let (line, color) = match row_diff {
+                        BRowDiff::Inserted(row) => (
+                            format!("+ {}", format_row(row)),
+                            Color::from_rgb(0.3, 0.8, 0.3),
+                        ),
+                        BRowDiff::Deleted(row) => (
+                            format!("- {}", format_row(row)),
+                            Color::from_rgb(0.9, 0.3, 0.3),
+                        ),
+                        BRowDiff::Modified {
+                            primary_key,
+                            changed_cells,
+                        } => (
+                            format!(
+                                "~ {}: {}",
+                                primary_key
+                                    .iter()
+                                    .map(|(column_name, value)| format!(
+                                        "{} = {}",
+                                        column_name, value
+                                    ))
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                                changed_cells
+                                    .iter()
+                                    .map(|(column_name, old_value, new_value)| format!(
+                                        "{} {} → {}",
+                                        column_name,
+                                        format_value(old_value),
+                                        format_value(new_value)
+                                    ))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ),
+                            Color::from_rgb(0.9, 0.75, 0.3),
+                        ),
+                    };
+                    row_diffs.push(text(line).size(14).color(color))
+                })
+        };
+        container(
+            Column::new()
+                .spacing(10)
+                .push(text("Pending diff").size(20).style(|_| text_style()))
+                .push(row_diffs),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(|_| table_container_style())
+        .into()
+    }
+
+    // the columns are listed in the order they were checked, which is the order of the values
+    fn duplicate_finder<'a>(&'a self) -> Element<'a, Message> {
+        let Some(table_inserted_data) = self.table_inserted_data.as_ref() else {
+            return Column::new().into();
+        };
+        let column_checkboxes = table_inserted_data.column_names.iter().fold(
+            Row::new().spacing(10),
+            |column_checkboxes, column_name| {
+                let toggled_column_name = column_name.clone();
+                column_checkboxes.push(
+                    checkbox(
+                        column_name.clone(),
+                        self.duplicate_column_names.contains(column_name),
+                    )
+                    .on_toggle(move |_| {
+                        <TableDataUI as UIComponent>::EventType::ToggleDuplicateColumn(
+                            toggled_column_name.clone(),
+                        )
+                        .message()
+                    }),
+                )
+            },
+        );
+        let find_button = button(text("Find duplicates").size(16).style(|_| text_style()))
+            .on_press_maybe(
+                (!self.duplicate_column_names.is_empty())
+                    .then(|| <TableDataUI as UIComponent>::EventType::FindDuplicates.message()),
+            )
+            .padding(10)
+            .style(|_, _| reset_table_data_button_style());
+        let mut duplicate_finder = Column::new().spacing(10).push(
+            Row::new()
+                .spacing(10)
+                .align_y(Vertical::Center)
+                .push(column_checkboxes)
+                .push(find_button),
+        );
+
+        if let Some(duplicate_groups) = self.duplicate_groups.as_ref() {
+            let header = Row::new()
+                .spacing(10)
+                .align_y(Vertical::Center)
+                .push(
+                    text(format!(
+                        "Duplicates of {}",
+                        self.duplicate_column_names.join(", ")
+                    ))
+                    .size(20)
+                    .style(|_| text_style()),
+                )
+                .push(
+                    button(text("Close").size(14).style(|_| text_style()))
+                        .on_press(
+                            <TableDataUI as UIComponent>::EventType::CloseDuplicateGroups.message(),
+                        )
+                        .padding(5)
+                        .style(|_, _| reset_table_data_button_style()),
+                );
+            let groups = if duplicate_groups.is_empty() {
+                Column::new().push(text("No duplicate rows").size(14).style(|_| text_style()))
+            } else {
+                duplicate_groups.iter().fold(
+                    Column::new().spacing(5),
+                    |groups, (values, row_count)| {
+                        let values: Vec<&str> = values
+                            .iter()
+                            .map(|value| value.as_deref().unwrap_or("NULL"))
+                            .collect();
+                        groups.push(
+                            text(format!("{} ({} rows)", values.join(" | "), row_count))
+                                .size(14)
+                                .style(|_| text_style()),
+                        )
+                    },
+                )
+            };
+            duplicate_finder = duplicate_finder.push(
+                container(Column::new().spacing(10).push(header).push(groups))
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(|_| table_container_style()),
+            );
+        }
+        duplicate_finder.into()
+    }
+
+    fn undo_redo_buttons<'a>(&'a self) -> Row<'a, Message> {
+        Row::new()
+            .spacing(10)
+            .push(
+                button(text("↶ Undo").size(16).style(|_| text_style()))
+                    .on_press(<TableDataUI as UIComponent>::EventType::UndoEvent.message())
+                    .padding(10)
+                    .style(|_, _| reset_table_data_button_style()),
+            )
+            .push(
+                button(text("↷ Redo").size(16).style(|_| text_style()))
+                    .on_press(<TableDataUI as UIComponent>::EventType::RedoEvent.message())
+                    .padding(10)
+                    .style(|_, _| reset_table_data_button_style()),
+            )
+            .push(
+                button(text("Preview changes").size(16).style(|_| text_style()))
+                    .on_press(<TableDataUI as UIComponent>::EventType::PreviewChanges.message())
+                    .padding(10)
+                    .style(|_, _| reset_table_data_button_style()),
+            )
+            .push(
+                button(
+                    text(if self.show_pending_diff {
+                        "Hide diff"
+                    } else {
+                        "Show diff"
+                    })
+                    .size(16)
+                    .style(|_| text_style()),
+                )
+                .on_press(<TableDataUI as UIComponent>::EventType::TogglePendingDiff.message())
+                .padding(10)
+                .style(|_, _| reset_table_data_button_style()),
+            )
+    }
+
+    fn discard_changes_button<'a>(&'a self) -> Button<'a, Message> {
+        button(text("Discard changes").size(16).style(|_| text_style()))
+            .on_press_maybe(
+                self.table_data
+                    .has_pending_changes()
+                    .then(|| <TableDataUI as UIComponent>::EventType::DiscardChanges.message()),
+            )
+            .padding(10)
+            .style(|_, _| reset_table_data_button_style())
+    }
+
     fn reset_table_data_button<'a>(&'a self) -> Button<'a, Message> {
         button(
             text("Reset Table Data").size(16).style(|_| text_style()), // Style the button text
@@ -261,15 +1773,68 @@ impl TableDataUI {
         &'a self,
         row_index: usize,
         col_index: usize,
-        value: &str,
-    ) -> TextInput<'a, Message> {
-        text_input("", value)
+        value: &Option<String>,
+    ) -> Element<'a, Message> {
+        let cell_draft = self.cell_drafts.get(&(row_index, col_index));
+        let displayed_value = match cell_draft {
+            Some((draft, _)) => draft.as_str(),
+            None => value.as_deref().unwrap_or_default(),
+        };
+        let error = cell_draft.and_then(|(_, error)| error.as_ref());
+        // the placeholder tells a NULL apart from an empty string
+        let value_input = text_input(if value.is_none() { "NULL" } else { "" }, displayed_value)
             .on_input(move |new_value| {
                 <TableDataUI as UIComponent>::EventType::UpdateCell(row_index, col_index, new_value)
                     .message()
             })
+            .on_submit(
+                <TableDataUI as UIComponent>::EventType::SubmitCell(row_index, col_index).message(),
+            )
+            .id(cell_input_id(row_index, col_index))
             .padding(5)
-            .style(|_, _| text_input_style())
+            .style(move |_, _| {
+                if error.is_some() {
+                    invalid_text_input_style()
+                } else {
+                    text_input_style()
+                }
+            });
+        let value_input: Element<'a, Message> = match error {
+            Some(error) => tooltip(
+                value_input,
+                container(text(error.clone()).size(12))
+                    .padding(5)
+                    .style(tooltip_style),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+            None => value_input.into(),
+        };
+        // other types become NULL when cleared, only text needs a way to set it
+        let is_text_column = self
+            .table_inserted_data
+            .as_ref()
+            .is_some_and(|table_inserted_data| {
+                matches!(
+                    table_inserted_data.data_types[col_index],
+                    BDataType::TEXT | BDataType::VARCHAR(_)
+                )
+            });
+        if !is_text_column {
+            return value_input;
+        }
+        let null_button = button(text("∅").size(12))
+            .on_press_maybe(value.is_some().then(|| {
+                <TableDataUI as UIComponent>::EventType::SetCellNull(row_index, col_index).message()
+            }))
+            .padding(2)
+            .style(|_, _| reset_table_data_button_style());
+        Row::new()
+            .spacing(2)
+            .align_y(Vertical::Center)
+            .push(value_input)
+            .push(null_button)
+            .into()
     }
 
     fn create_no_data_message<'a>(&'a self) -> Element<'a, Message> {
@@ -279,6 +1844,43 @@ impl TableDataUI {
     }
 }
 
+// the cells are found again by position when moving the focus with the keyboard
+fn cell_input_id(row_index: usize, col_index: usize) -> text_input::Id {
+    text_input::Id::new(format!("table_data_cell_{}_{}", row_index, col_index))
+}
+
+// read only columns are shown as text so they can't take the focus and are skipped,
+// there is no cell to move to past the first or last row
+fn moved_cell(
+    (row_index, col_index): (usize, usize),
+    cell_focus_move: CellFocusMove,
+    row_count: usize,
+    read_only_columns: &[bool],
+) -> Option<(usize, usize)> {
+    let column_count = read_only_columns.len();
+    let cell_index = row_index * column_count + col_index;
+    let cell_count = row_count * column_count;
+    let cell_indexes: Vec<usize> = match cell_focus_move {
+        CellFocusMove::Next => (cell_index + 1..cell_count).collect(),
+        CellFocusMove::Previous => (0..cell_index).rev().collect(),
+        CellFocusMove::Down => vec![cell_index + column_count],
+        CellFocusMove::Up => cell_index.checked_sub(column_count).into_iter().collect(),
+    };
+    cell_indexes
+        .into_iter()
+        .filter(|cell_index| *cell_index < cell_count)
+        .find(|cell_index| !read_only_columns[cell_index % column_count])
+        .map(|cell_index| (cell_index / column_count, cell_index % column_count))
+}
+
+// NULL values are copied as empty fields
+fn tab_separated_row(row: &[Option<String>]) -> String {
+    row.iter()
+        .map(|value| value.as_deref().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
 // Style function for the table container using ::Style
 fn table_container_style() -> container::Style {
     container::Style {
@@ -347,6 +1949,16 @@ fn update_table_data_button_style() -> button::Style {
     }
 }
 
+// column headers are buttons that look like plain text
+fn column_name_button_style() -> button::Style {
+    button::Style {
+        background: None,
+        border: Border::default(),
+        text_color: Color::from_rgb(0.9, 0.9, 1.0),
+        shadow: Shadow::default(),
+    }
+}
+
 fn add_table_row_button_style() -> button::Style {
     button::Style {
         background: Some(Background::Color(Color::from_rgb(0.0, 0.8, 1.0))), // Vibrant turquoise-blue background
@@ -402,3 +2014,60 @@ fn text_input_style() -> text_input::Style {
         selection: Color::from_rgba(0.0, 0.7, 1.0, 0.5), // Highlighted selection color
     }
 }
+
+// marks a cell holding a value its column type won't accept
+fn invalid_text_input_style() -> text_input::Style {
+    text_input::Style {
+        border: Border {
+            color: Color::from_rgb(0.9, 0.2, 0.2),
+            width: 2.0,
+            radius: Radius::from(8.0),
+        },
+        ..text_input_style()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_separated_row() {
+        let row = vec![Some(String::from("1")), None, Some(String::from("Alice"))];
+        assert_eq!(tab_separated_row(&row), "1\t\tAlice");
+    }
+
+    #[test]
+    fn test_moved_cell() {
+        // the first column is a serial id that can't be edited
+        let read_only_columns = [true, false, false];
+        assert_eq!(
+            moved_cell((0, 1), CellFocusMove::Next, 2, &read_only_columns),
+            Some((0, 2))
+        );
+        assert_eq!(
+            moved_cell((0, 2), CellFocusMove::Next, 2, &read_only_columns),
+            Some((1, 1))
+        );
+        assert_eq!(
+            moved_cell((1, 1), CellFocusMove::Previous, 2, &read_only_columns),
+            Some((0, 2))
+        );
+        assert_eq!(
+            moved_cell((0, 2), CellFocusMove::Down, 2, &read_only_columns),
+            Some((1, 2))
+        );
+        assert_eq!(
+            moved_cell((1, 2), CellFocusMove::Next, 2, &read_only_columns),
+            None
+        );
+        assert_eq!(
+            moved_cell((1, 2), CellFocusMove::Down, 2, &read_only_columns),
+            None
+        );
+        assert_eq!(
+            moved_cell((0, 1), CellFocusMove::Up, 2, &read_only_columns),
+            None
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::components::business_components::component::{
-    BDataType, BTableChangeEvents, BTableGeneral, BTableIn, BTableInfo,
+    BColumnStats, BDataType, BTableChangeEvents, BTableGeneral, BTableIn, BTableInfo,
 };
 use crate::components::ui_components::{
     component::Event, events::Message, tables::events::TablesMessage,
@@ -13,6 +13,7 @@ pub enum TableInfoMessage {
     UpdateColumnType(usize, BDataType), // Event to update the type of a specific column
     UpdateTableName(String),
     SubmitUpdateTable,
+    TableUpdated(bool),
     UpdateTableInfoUI,
     ResetTableInfo,
     SetTableInfo,
@@ -23,6 +24,20 @@ pub enum TableInfoMessage {
     SetOrRemovePrimaryKey(usize),
     AddTableChangeEvent(BTableChangeEvents),
     TableChangeEventDone,
+    ToggleIndexColumn(String),
+    ToggleIndexUnique,
+    AddIndex,
+    DropIndex(String),
+    SetIndexes,
+    SelectTypeChangeColumn(String),
+    SelectTypeChangeDataType(BDataType),
+    UpdateTypeChangeUsingExpression(String),
+    ChangeColumnType,
+    ColumnTypeChanged(bool),
+    CopyTableSchema,
+    TableSchemaLoaded(Option<String>),
+    ToggleColumnStats(String),
+    SetColumnStats(String, Option<BColumnStats>),
 }
 
 impl Event for TableInfoMessage {
@@ -1,7 +1,8 @@
 use crate::components::business_components::{
     component::{
-        BColumn, BColumnForeignKey, BConstraint, BDataType, BTableChangeEvents, BTableGeneral,
-        BTableIn, BTableInfo, BusinessComponent,
+        BColumn, BColumnForeignKey, BColumnStats, BConstraint, BDataType, BIndex,
+        BReferentialAction, BTableChangeEvents, BTableGeneral, BTableIn, BTableInfo,
+        BTableMetadata, BusinessComponent,
     },
     components::BusinessTables,
 };
@@ -9,6 +10,7 @@ use crate::components::ui_components::console::events::ConsoleMessage;
 use crate::components::ui_components::{
     component::{Event, UIComponent},
     events::Message,
+    tables::events::TablesMessage,
     tables::foreign_key_dropdown::{ForeignKeyDropDownUI, ForeignKeyDropdownEvents},
     tables::table_data::events::TableDataMessage,
     tables::table_info::events::TableInfoMessage,
@@ -24,6 +26,7 @@ use iced::{
     },
     Alignment, Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector,
 };
+use std::collections::HashMap;
 use std::iter::zip;
 use std::sync::Arc;
 use tokio::sync::Mutex as AsyncMutex;
@@ -55,6 +58,20 @@ pub struct TableInfoUI {
     table_name_display: String,
     columns_display: Vec<BColumn>,
     active_foreign_key_dropdown: Option<ForeignKeyDropDownUI<TableInfoForeignKeyDropdown>>,
+    indexes_display: Vec<BIndex>,
+    // only columns that already exist in the database can be indexed
+    indexable_column_names: Vec<String>,
+    // columns of the index being added, in the order they were picked
+    index_column_names: Vec<String>,
+    index_unique: bool,
+    metadata_display: Option<BTableMetadata>,
+    // the column type change is applied right away, not with the table update
+    type_change_column_name: Option<String>,
+    type_change_data_type: Option<BDataType>,
+    // asked for once the database rejects the plain conversion
+    type_change_using_expression: Option<String>,
+    // expanded columns, the stats are None while they are loading
+    column_stats: HashMap<String, Option<BColumnStats>>,
 }
 
 impl UIComponent for TableInfoUI {
@@ -131,15 +148,22 @@ impl UIComponent for TableInfoUI {
             Self::EventType::SubmitUpdateTable => {
                 let table_info = self.table_info.clone();
                 Task::perform(
-                    async move {
-                        table_info.update_table().await;
-                    },
-                    |_| Self::EventType::UpdateTableInfoUI.message(),
+                    async move { table_info.update_table().await },
+                    |is_updated| Self::EventType::TableUpdated(is_updated).message(),
                 )
-                .chain(Task::done(TableDataMessage::SetTableData.message()))
             }
+            Self::EventType::TableUpdated(true) => {
+                Task::done(Self::EventType::UpdateTableInfoUI.message()).chain(Task::done(
+                    TablesMessage::TableData(self.get_table_name(), TableDataMessage::SetTableData)
+                        .message(),
+                ))
+            }
+            // in a dry run the edits stay on screen together with their pending changes
+            Self::EventType::TableUpdated(false) => Task::none(),
             Self::EventType::UpdateTableInfoUI => {
                 self.columns_display = self.table_info.columns_info.blocking_lock().clone();
+                self.set_indexes_display();
+                self.metadata_display = self.table_info.metadata.blocking_lock().clone();
                 self.table_name_display = self
                     .table_info
                     .table_name
@@ -169,6 +193,10 @@ impl UIComponent for TableInfoUI {
                     .unwrap()
                     .clone();
                 self.columns_display = self.table_info.columns_info.blocking_lock().clone();
+                self.set_indexes_display();
+                self.metadata_display = self.table_info.metadata.blocking_lock().clone();
+                // the data may have changed along with the table
+                self.column_stats.clear();
                 Task::none()
             }
             Self::EventType::AddForeignKey(
@@ -177,25 +205,34 @@ impl UIComponent for TableInfoUI {
                 referenced_column_name,
             ) => {
                 if let Some(column) = self.columns_display.get_mut(index) {
-                    if let Some(existing_index) = column.constraints.iter().position(|constraint| {
-                        matches!(
-                            constraint,
-                            BConstraint::ForeignKey(existing_table_name, existing_column_name)
-                        )
-                    }) {
+                    // a replaced foreign key keeps its delete action, a new one gets the default
+                    let mut on_delete = BReferentialAction::default();
+                    if let Some(existing_index) = column
+                        .constraints
+                        .iter()
+                        .position(|constraint| matches!(constraint, BConstraint::ForeignKey { .. }))
+                    {
                         // Replace the foreign key constraint if it exists
-                        column.constraints.remove(existing_index);
+                        if let BConstraint::ForeignKey {
+                            on_delete: existing_on_delete,
+                            ..
+                        } = column.constraints.remove(existing_index)
+                        {
+                            on_delete = existing_on_delete;
+                        }
                     }
-                    column.constraints.push(BConstraint::ForeignKey(
-                        referenced_table_name.clone(),
-                        referenced_column_name.clone(),
-                    ));
+                    column.constraints.push(BConstraint::ForeignKey {
+                        table: referenced_table_name.clone(),
+                        column: referenced_column_name.clone(),
+                        on_delete: on_delete.clone(),
+                    });
                     Task::done(
                         Self::EventType::AddTableChangeEvent(BTableChangeEvents::AddForeignKey(
                             BColumnForeignKey {
                                 column_name: column.name.clone(),
                                 referenced_table: referenced_table_name,
                                 referenced_column: referenced_column_name,
+                                on_delete,
                             },
                         ))
                         .message(),
@@ -206,12 +243,11 @@ impl UIComponent for TableInfoUI {
             }
             Self::EventType::RemoveForeignKey(index) => {
                 if let Some(column) = self.columns_display.get_mut(index) {
-                    if let Some(existing_index) = column.constraints.iter().position(|constraint| {
-                        matches!(
-                            constraint,
-                            BConstraint::ForeignKey(existing_table_name, existing_column_name)
-                        )
-                    }) {
+                    if let Some(existing_index) = column
+                        .constraints
+                        .iter()
+                        .position(|constraint| matches!(constraint, BConstraint::ForeignKey { .. }))
+                    {
                         column.constraints.remove(existing_index);
                     }
                     Task::done(
@@ -289,6 +325,145 @@ impl UIComponent for TableInfoUI {
                 Task::none()
             }
             Self::EventType::TableChangeEventDone => Task::none(),
+            Self::EventType::ToggleIndexColumn(column_name) => {
+                if let Some(existing_index) = self
+                    .index_column_names
+                    .iter()
+                    .position(|index_column_name| *index_column_name == column_name)
+                {
+                    self.index_column_names.remove(existing_index);
+                } else {
+                    self.index_column_names.push(column_name);
+                }
+                Task::none()
+            }
+            Self::EventType::ToggleIndexUnique => {
+                self.index_unique = !self.index_unique;
+                Task::none()
+            }
+            Self::EventType::AddIndex => {
+                let table_info = self.table_info.clone();
+                let index_column_names = std::mem::take(&mut self.index_column_names);
+                let index_unique = std::mem::take(&mut self.index_unique);
+                Task::perform(
+                    async move {
+                        table_info.add_index(index_column_names, index_unique).await;
+                    },
+                    |_| Self::EventType::SetIndexes.message(),
+                )
+            }
+            Self::EventType::DropIndex(index_name) => {
+                let table_info = self.table_info.clone();
+                Task::perform(
+                    async move {
+                        table_info.drop_index(index_name).await;
+                    },
+                    |_| Self::EventType::SetIndexes.message(),
+                )
+            }
+            Self::EventType::SetIndexes => {
+                self.set_indexes_display();
+                Task::none()
+            }
+            Self::EventType::SelectTypeChangeColumn(column_name) => {
+                self.type_change_column_name = Some(column_name);
+                self.type_change_using_expression = None;
+                Task::none()
+            }
+            Self::EventType::SelectTypeChangeDataType(data_type) => {
+                self.type_change_data_type = Some(data_type);
+                self.type_change_using_expression = None;
+                Task::none()
+            }
+            Self::EventType::UpdateTypeChangeUsingExpression(using_expression) => {
+                self.type_change_using_expression = Some(using_expression);
+                Task::none()
+            }
+            Self::EventType::ChangeColumnType => {
+                let (Some(column_name), Some(data_type)) = (
+                    self.type_change_column_name.clone(),
+                    self.type_change_data_type.clone(),
+                ) else {
+                    return Task::none();
+                };
+                let table_info = self.table_info.clone();
+                let using_expression = self
+                    .type_change_using_expression
+                    .clone()
+                    .filter(|using_expression| !using_expression.trim().is_empty());
+                Task::perform(
+                    async move {
+                        table_info
+                            .change_column_type(column_name, data_type, using_expression)
+                            .await
+                    },
+                    |is_changed| Self::EventType::ColumnTypeChanged(is_changed).message(),
+                )
+            }
+            Self::EventType::ColumnTypeChanged(true) => {
+                self.type_change_column_name = None;
+                self.type_change_data_type = None;
+                self.type_change_using_expression = None;
+                Task::done(Self::EventType::SetTableInfo.message()).chain(Task::done(
+                    TablesMessage::TableData(self.get_table_name(), TableDataMessage::SetTableData)
+                        .message(),
+                ))
+            }
+            Self::EventType::CopyTableSchema => {
+                let table_info = self.table_info.clone();
+                Task::perform(
+                    async move { table_info.get_table_schema().await },
+                    |table_schema| Self::EventType::TableSchemaLoaded(table_schema).message(),
+                )
+            }
+            // a schema that could not be built was already reported in the console
+            Self::EventType::TableSchemaLoaded(Some(table_schema)) => {
+                iced::clipboard::write(table_schema)
+            }
+            Self::EventType::TableSchemaLoaded(None) => Task::none(),
+            Self::EventType::ToggleColumnStats(column_name) => {
+                if self.column_stats.remove(&column_name).is_some() {
+                    return Task::none();
+                }
+                self.column_stats.insert(column_name.clone(), None);
+                let table_info = self.table_info.clone();
+                Task::perform(
+                    async move {
+                        let column_stats = table_info.get_column_stats(column_name.clone()).await;
+                        (column_name, column_stats)
+                    },
+                    |(column_name, column_stats)| {
+                        Self::EventType::SetColumnStats(column_name, column_stats).message()
+                    },
+                )
+            }
+            // a failed query is shown in the console, the column collapses again
+            Self::EventType::SetColumnStats(column_name, column_stats) => {
+                match column_stats {
+                    Some(column_stats) => {
+                        if let Some(expanded_column_stats) = self.column_stats.get_mut(&column_name)
+                        {
+                            *expanded_column_stats = Some(column_stats);
+                        }
+                    }
+                    None => {
+                        self.column_stats.remove(&column_name);
+                    }
+                }
+                Task::none()
+            }
+            Self::EventType::ColumnTypeChanged(false) => {
+                // suggest the cast the plain conversion was missing
+                if self.type_change_using_expression.is_none() {
+                    if let (Some(column_name), Some(data_type)) =
+                        (&self.type_change_column_name, &self.type_change_data_type)
+                    {
+                        self.type_change_using_expression =
+                            Some(format!("\"{}\"::{}", column_name, data_type));
+                    }
+                }
+                Task::none()
+            }
         }
     }
 }
@@ -305,9 +480,25 @@ impl TableInfoUI {
                 .clone(),
             columns_display: table_info.columns_info.blocking_lock().clone(),
             active_foreign_key_dropdown: None,
+            indexes_display: table_info.indexes.blocking_lock().clone(),
+            indexable_column_names: column_names(&table_info.columns_info.blocking_lock()),
+            index_column_names: vec![],
+            index_unique: false,
+            metadata_display: table_info.metadata.blocking_lock().clone(),
+            type_change_column_name: None,
+            type_change_data_type: None,
+            type_change_using_expression: None,
+            column_stats: HashMap::new(),
         }
     }
 
+    fn set_indexes_display(&mut self) {
+        self.indexes_display = self.table_info.indexes.blocking_lock().clone();
+        self.indexable_column_names = column_names(&self.table_info.columns_info.blocking_lock());
+        self.index_column_names
+            .retain(|column_name| self.indexable_column_names.contains(column_name));
+    }
+
     pub fn get_table_name(&self) -> String {
         self.table_info
             .table_name
@@ -326,14 +517,63 @@ impl TableInfoUI {
             .push(self.separator_line())
             .push(self.scrollable_columns_info())
             .push(self.add_column_button())
+            .push(self.indexes_section())
+            .push(self.column_type_change_section())
             .push(self.update_table_button())
+            .push(self.copy_table_schema_button())
+            .push(self.truncate_table_button())
             .push(self.reset_table_info_button());
 
-        container(table_info_column)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(20)
-            .style(|_| container_style())
+        container(
+            Row::new()
+                .spacing(20)
+                .push(table_info_column.width(Length::FillPortion(3)))
+                .push(self.metadata_panel()),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(20)
+        .style(|_| container_style())
+        .into()
+    }
+
+    fn metadata_panel(&self) -> Element<'_, Message> {
+        let Some(metadata) = &self.metadata_display else {
+            return Column::new().into();
+        };
+        let primary_key = if metadata.primary_key_column_names.is_empty() {
+            String::from("none")
+        } else {
+            metadata.primary_key_column_names.join(", ")
+        };
+        let foreign_keys = metadata.foreign_keys.iter().fold(
+            Column::new().spacing(5),
+            |foreign_keys, foreign_key| {
+                foreign_keys.push(text(format!(
+                    "{} → {} ({})",
+                    foreign_key.column_names.join(", "),
+                    foreign_key.referenced_table,
+                    foreign_key.referenced_column_names.join(", ")
+                )))
+            },
+        );
+        let mut panel = Column::new()
+            .spacing(10)
+            .padding(10)
+            .push(text("Overview").size(20).color(Color::WHITE))
+            .push(text(format!("Columns: {}", metadata.column_count)))
+            .push(text(format!("Primary key: {}", primary_key)))
+            .push(text(format!(
+                "Foreign keys: {}",
+                metadata.foreign_keys.len()
+            )))
+            .push(foreign_keys);
+        if let Some(total_size_bytes) = metadata.total_size_bytes {
+            panel = panel.push(text(format!("Size: {}", format_size(total_size_bytes))));
+        }
+        container(panel)
+            .width(Length::FillPortion(1))
+            .style(|_| metadata_panel_style())
             .into()
     }
 
@@ -390,7 +630,14 @@ impl TableInfoUI {
             .fold(
                 Column::new().spacing(10),
                 |columns_info_column, (index, column_info)| {
-                    columns_info_column.push(self.build_column_row(index, column_info))
+                    let columns_info_column =
+                        columns_info_column.push(self.build_column_row(index, column_info));
+                    match self.column_stats.get(&column_info.name) {
+                        Some(column_stats) => {
+                            columns_info_column.push(self.column_stats_line(column_stats.as_ref()))
+                        }
+                        None => columns_info_column,
+                    }
                 },
             )
             .into()
@@ -403,10 +650,42 @@ impl TableInfoUI {
             .push(self.data_type_picker(index, &column_info.datatype))
             .push(self.primary_key_checkbox(index, &column_info))
             .push(self.render_foreign_key_button(index, &column_info))
+            .push(self.column_stats_button(&column_info.name))
             .push(self.remove_column_button(index))
             .align_y(Vertical::Center)
     }
 
+    // only columns that exist in the database have data to count
+    fn column_stats_button<'a>(&'a self, column_name: &str) -> Button<'a, Message> {
+        let button_text = if self.column_stats.contains_key(column_name) {
+            "📊 Hide Stats"
+        } else {
+            "📊 Stats"
+        };
+        button(button_text)
+            .style(|_, _| toggle_foreign_key_dropdown_button_style())
+            .padding(10)
+            .on_press_maybe(
+                self.indexable_column_names
+                    .iter()
+                    .any(|indexable_column_name| indexable_column_name == column_name)
+                    .then(|| {
+                        TableInfoMessage::ToggleColumnStats(column_name.to_string()).message()
+                    }),
+            )
+    }
+
+    fn column_stats_line<'a>(&'a self, column_stats: Option<&BColumnStats>) -> Text<'a> {
+        let stats = match column_stats {
+            Some(column_stats) => format!(
+                "{} NULL values, {} distinct values",
+                column_stats.null_count, column_stats.distinct_count
+            ),
+            None => String::from("Counting..."),
+        };
+        text(stats).size(14).color(Color::from_rgb(0.7, 0.7, 0.7))
+    }
+
     fn column_name_input<'a>(&'a self, index: usize, name: &str) -> TextInput<'a, Message> {
         text_input("Column Name", name)
             .on_input(move |value| TableInfoMessage::UpdateColumnName(index, value).message())
@@ -417,7 +696,17 @@ impl TableInfoUI {
 
     fn data_type_picker<'a>(&'a self, index: usize, datatype: &BDataType) -> Element<'a, Message> {
         PickList::new(
-            vec![BDataType::TEXT, BDataType::INTEGER, BDataType::TIMESTAMP],
+            vec![
+                BDataType::TEXT,
+                BDataType::VARCHAR(BDataType::DEFAULT_VARCHAR_LENGTH),
+                BDataType::SMALLINT,
+                BDataType::INTEGER,
+                BDataType::SERIAL,
+                BDataType::BIGINT,
+                BDataType::DOUBLE,
+                BDataType::TIMESTAMP,
+                BDataType::UUID,
+            ],
             Some(datatype.clone()),
             move |value| {
                 <TableInfoUI as UIComponent>::EventType::UpdateColumnType(index, value).message()
@@ -450,10 +739,13 @@ impl TableInfoUI {
         let button_text = if let Some(foreign_key_constraint) = column
             .constraints
             .iter()
-            .find(|constraint| matches!(constraint, BConstraint::ForeignKey(_, _)))
+            .find(|constraint| matches!(constraint, BConstraint::ForeignKey { .. }))
         {
-            if let BConstraint::ForeignKey(referenced_table_name, referenced_column_name) =
-                foreign_key_constraint
+            if let BConstraint::ForeignKey {
+                table: referenced_table_name,
+                column: referenced_column_name,
+                ..
+            } = foreign_key_constraint
             {
                 text(format!(
                     "{}.{}",
@@ -503,6 +795,135 @@ impl TableInfoUI {
             .on_press(TableInfoMessage::RemoveColumn(index).message())
     }
 
+    // indexes are created and dropped right away, they are not part of the table update
+    fn indexes_section(&self) -> Column<'_, Message> {
+        let indexes_list =
+            self.indexes_display
+                .iter()
+                .fold(Column::new().spacing(5), |indexes_list, index| {
+                    let index_row = Row::new()
+                        .spacing(10)
+                        .align_y(Vertical::Center)
+                        .push(text(&index.definition).width(Length::Fill));
+                    // the index of a primary key or unique constraint goes away with it
+                    let index_row = if index.is_constraint {
+                        index_row.push(text("constraint"))
+                    } else {
+                        index_row.push(
+                            button("🗑️ Drop")
+                                .style(|_, _| delete_button_style())
+                                .padding(5)
+                                .on_press(
+                                    TableInfoMessage::DropIndex(index.name.clone()).message(),
+                                ),
+                        )
+                    };
+                    indexes_list.push(index_row)
+                });
+        let index_columns = self.indexable_column_names.iter().fold(
+            Row::new().spacing(10),
+            |index_columns, column_name| {
+                index_columns.push(
+                    checkbox(
+                        column_name.as_str(),
+                        self.index_column_names.contains(column_name),
+                    )
+                    .on_toggle(move |_| {
+                        TableInfoMessage::ToggleIndexColumn(column_name.clone()).message()
+                    }),
+                )
+            },
+        );
+        let add_index_controls = Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(index_columns)
+            .push(
+                checkbox("Unique", self.index_unique)
+                    .on_toggle(|_| TableInfoMessage::ToggleIndexUnique.message()),
+            )
+            .push(
+                button("➕ Add Index")
+                    .style(|_, _| add_button_style())
+                    .padding(10)
+                    .on_press_maybe(
+                        (!self.index_column_names.is_empty())
+                            .then(|| TableInfoMessage::AddIndex.message()),
+                    ),
+            );
+        Column::new()
+            .spacing(10)
+            .push(text("Indexes").size(20).color(Color::WHITE))
+            .push(indexes_list)
+            .push(add_index_controls)
+    }
+
+    fn column_type_change_section(&self) -> Column<'_, Message> {
+        let mut controls = Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(
+                PickList::new(
+                    self.indexable_column_names.clone(),
+                    self.type_change_column_name.clone(),
+                    |column_name| TableInfoMessage::SelectTypeChangeColumn(column_name).message(),
+                )
+                .placeholder("Column")
+                .width(150)
+                .padding(5),
+            )
+            .push(
+                PickList::new(
+                    vec![
+                        BDataType::TEXT,
+                        BDataType::VARCHAR(BDataType::DEFAULT_VARCHAR_LENGTH),
+                        BDataType::SMALLINT,
+                        BDataType::INTEGER,
+                        BDataType::BIGINT,
+                        BDataType::DOUBLE,
+                        BDataType::TIMESTAMP,
+                        BDataType::BOOLEAN,
+                        BDataType::UUID,
+                    ],
+                    self.type_change_data_type.clone(),
+                    |data_type| TableInfoMessage::SelectTypeChangeDataType(data_type).message(),
+                )
+                .placeholder("New Type")
+                .width(150)
+                .padding(5),
+            );
+        if let Some(using_expression) = &self.type_change_using_expression {
+            controls = controls.push(
+                text_input("USING expression", using_expression)
+                    .on_input(|value| {
+                        TableInfoMessage::UpdateTypeChangeUsingExpression(value).message()
+                    })
+                    .width(250)
+                    .style(|_, _| text_input_style()),
+            );
+        }
+        controls = controls.push(
+            button("🔁 Change Type")
+                .style(|_, _| update_button_style())
+                .padding(10)
+                .on_press_maybe(
+                    (self.type_change_column_name.is_some()
+                        && self.type_change_data_type.is_some())
+                    .then(|| TableInfoMessage::ChangeColumnType.message()),
+                ),
+        );
+        let mut section = Column::new()
+            .spacing(10)
+            .push(text("Change Column Type").size(20).color(Color::WHITE));
+        if self.type_change_using_expression.is_some() {
+            section = section.push(
+                text("The database could not convert the values, enter a cast to use instead")
+                    .color(Color::from_rgb(1.0, 0.85, 0.3)),
+            );
+        }
+        section.push(controls)
+    }
+
     fn update_table_button(&self) -> Button<'_, Message> {
         button("🛠️ Update Table")
             .style(|_, _| update_button_style())
@@ -510,6 +931,22 @@ impl TableInfoUI {
             .on_press(TableInfoMessage::SubmitUpdateTable.message())
     }
 
+    // the schema of the table as it is in the database, without the unsaved edits
+    fn copy_table_schema_button(&self) -> Button<'_, Message> {
+        button("📋 Copy DDL")
+            .style(|_, _| add_button_style())
+            .padding(10)
+            .on_press(TableInfoMessage::CopyTableSchema.message())
+    }
+
+    // the confirmation is asked for by the tables view
+    fn truncate_table_button(&self) -> Button<'_, Message> {
+        button("🧹 Truncate Table")
+            .style(|_, _| delete_button_style())
+            .padding(10)
+            .on_press(TablesMessage::RequestTruncateTable(self.get_table_name()).message())
+    }
+
     fn reset_table_info_button(&self) -> Button<'_, Message> {
         button("Reset Table Info")
             .style(|_, _| reset_button_style())
@@ -518,6 +955,25 @@ impl TableInfoUI {
     }
 }
 
+fn column_names(columns: &[BColumn]) -> Vec<String> {
+    columns.iter().map(|column| column.name.clone()).collect()
+}
+
+fn format_size(size_bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = size_bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size_bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
 fn container_style() -> container::Style {
     container::Style {
         background: Some(Background::Color(Color::from_rgb(0.12, 0.15, 0.20))), // Darker background for a CRM feel
@@ -618,6 +1074,19 @@ fn text_input_style() -> text_input::Style {
     }
 }
 
+fn metadata_panel_style() -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color::from_rgb(0.15, 0.15, 0.15))),
+        border: Border {
+            color: Color::from_rgb(0.0, 0.6, 0.6),
+            width: 1.0,
+            radius: Radius::from(5.0),
+        },
+        text_color: Some(Color::WHITE),
+        shadow: Shadow::default(),
+    }
+}
+
 fn constraints_container_style() -> container::Style {
     container::Style {
         background: Some(Background::Color(Color::from_rgb(0.95, 0.95, 0.95))),
@@ -4,11 +4,13 @@ use crate::components::business_components::{
 };
 use crate::components::ui_components::{
     component::{Event, UIComponent},
+    components::ComponentsMessage,
     events::Message,
+    styles::{self, ButtonKind},
     tables::{
         create_table_form::CreateTableFormUI,
-        events::{CreateTableFormMessage, TablesMessage},
-        table_data::table_data::TableDataUI,
+        events::{CreateTableFormMessage, TableFilterMode, TableSort, TablesMessage},
+        table_data::{events::TableDataMessage, table_data::TableDataUI},
         table_info::table_info::TableInfoUI,
     },
 };
@@ -16,29 +18,71 @@ use iced::{
     alignment,
     alignment::{Alignment, Vertical},
     border::Radius,
+    font::{Font, Weight},
     futures::join,
     widget::{
-        button, checkbox, column, container, row, scrollable, text, text_input, Button, Checkbox,
-        Column, Container, PickList, Row, Text,
+        button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Button,
+        Checkbox, Column, Container, PickList, Row, Text,
     },
     Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector,
 };
 use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::iter::zip;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
 
+const TABLE_FILTER_INPUT_ID: &str = "table_filter";
+
 #[derive(Debug, Clone)]
 pub struct TablesUI {
     table_filter: String,
+    table_filter_case_sensitive: bool,
+    table_filter_mode: TableFilterMode,
+    // applied to the tables matching the filter
+    table_sort: TableSort,
+    schema: String,
+    schema_names: Vec<String>,
     show_create_table_form: bool,
     create_table_form: CreateTableFormUI,
     tables: Arc<BusinessTables>,
     single_table_info: Option<TableInfoUI>,
     show_single_table_data: bool,
-    single_table_data: TableDataUI,
+    // every open table keeps its rows and pending changes while another tab is shown
+    open_table_data: HashMap<String, TableDataUI>,
+    active_table_data: Option<String>,
+    column_search: String,
+    // (table, column) of the columns whose name matched the last search
+    column_search_results: Vec<(String, String)>,
     table_to_delete: Option<String>,
+    // tables with foreign keys to the table being deleted, it can only be dropped by cascading
+    table_to_delete_dependents: Vec<String>,
+    delete_table_cascade: bool,
+    selected_tables: Vec<String>,
+    // total row count of the selected tables, shown while confirming the deletion
+    selected_tables_row_count: Option<i64>,
+    table_to_clone: Option<String>,
+    clone_table_name: String,
+    clone_table_constraints: bool,
+    // the clone is created with CREATE TABLE LIKE, copying the rows is optional
+    clone_table_structure: bool,
+    clone_table_rows: bool,
+    table_to_rename: Option<String>,
+    rename_table_name: String,
+    table_to_truncate: Option<String>,
+    truncate_table_cascade: bool,
     show_tables: bool,
+    // false until the first table list arrives, an empty list before that is still loading
+    tables_loaded: bool,
+    schema_export_path: String,
+    // set while asking whether uncommitted table data edits may be discarded
+    confirm_refresh: bool,
+    // the tab being closed while asking whether its uncommitted edits may be discarded
+    table_data_to_close: Option<String>,
+    // the schema switched to once the uncommitted table data edits may be discarded
+    schema_to_select: Option<String>,
 }
 
 impl UIComponent for TablesUI {
@@ -50,11 +94,88 @@ impl UIComponent for TablesUI {
                 self.table_filter = input;
                 Task::none()
             }
+            Self::EventType::ToggleTableFilterCaseSensitive => {
+                self.table_filter_case_sensitive = !self.table_filter_case_sensitive;
+                Task::none()
+            }
+            Self::EventType::SelectTableFilterMode(table_filter_mode) => {
+                self.table_filter_mode = table_filter_mode;
+                Task::none()
+            }
+            Self::EventType::SetTableSort(table_sort) => {
+                self.table_sort = table_sort;
+                Task::none()
+            }
+            Self::EventType::UpdateColumnSearch(input) => {
+                self.column_search = input;
+                Task::none()
+            }
+            Self::EventType::SearchColumns => {
+                let tables = self.tables.clone();
+                let pattern = self.column_search.trim().to_string();
+                Task::perform(
+                    async move { tables.search_columns(pattern).await },
+                    |results| Self::EventType::SetColumnSearchResults(results).message(),
+                )
+            }
+            Self::EventType::SetColumnSearchResults(results) => {
+                self.column_search_results = results;
+                Task::none()
+            }
+            Self::EventType::SelectSchema(schema) => {
+                let has_pending_changes = self
+                    .open_table_data
+                    .values()
+                    .any(|table_data| table_data.has_pending_changes());
+                self.schema_to_select = Some(schema);
+                if has_pending_changes {
+                    Task::none()
+                } else {
+                    Task::done(Self::EventType::ConfirmSelectSchema.message())
+                }
+            }
+            Self::EventType::CancelSelectSchema => {
+                self.schema_to_select = None;
+                Task::none()
+            }
+            Self::EventType::ConfirmSelectSchema => {
+                let Some(schema) = self.schema_to_select.take() else {
+                    return Task::none();
+                };
+                // the open table and the column search results belong to the previous schema,
+                // the business component drops their queued changes
+                self.column_search_results.clear();
+                self.single_table_info = None;
+                self.open_table_data.clear();
+                self.active_table_data = None;
+                self.selected_tables.clear();
+                self.schema = schema.clone();
+                let tables = self.tables.clone();
+                Task::perform(
+                    async move {
+                        tables.set_schema(schema).await;
+                    },
+                    |_| Self::EventType::SetTables.message(),
+                )
+            }
             Self::EventType::ShowOrRemoveCreateTableForm => {
                 self.show_create_table_form = !self.show_create_table_form;
                 self.create_table_form
                     .update(CreateTableFormMessage::ShowOrRemoveCreateTableForm)
             }
+            // the keyboard shortcuts only open or close, they never toggle
+            Self::EventType::OpenCreateTableForm if !self.show_create_table_form => {
+                self.update(Self::EventType::ShowOrRemoveCreateTableForm)
+            }
+            Self::EventType::CloseCreateTableForm if self.show_create_table_form => {
+                self.update(Self::EventType::ShowOrRemoveCreateTableForm)
+            }
+            Self::EventType::OpenCreateTableForm | Self::EventType::CloseCreateTableForm => {
+                Task::none()
+            }
+            Self::EventType::FocusTableFilter => {
+                text_input::focus(text_input::Id::new(TABLE_FILTER_INPUT_ID))
+            }
             Self::EventType::ShowOrRemoveTableData => {
                 if self.show_single_table_data {
                     self.show_single_table_data = false;
@@ -103,7 +224,115 @@ impl UIComponent for TablesUI {
                 }
             }
             Self::EventType::SingleTableData(table_data_message) => {
-                self.single_table_data.update(table_data_message)
+                if let Some(table_name) = self.active_table_data.clone() {
+                    self.update(Self::EventType::TableData(table_name, table_data_message))
+                } else {
+                    Task::none()
+                }
+            }
+            // the referenced rows are shown in the tab of the referenced table
+            Self::EventType::TableData(
+                table_name,
+                TableDataMessage::FollowForeignKey(referenced_table, referenced_column, value),
+            ) if table_name != referenced_table => {
+                self.open_table_data
+                    .entry(referenced_table.clone())
+                    .or_insert_with(|| {
+                        TableDataUI::new(self.tables.open_table_data(&referenced_table))
+                    });
+                Task::done(
+                    Self::EventType::TableData(
+                        referenced_table.clone(),
+                        TableDataMessage::FollowForeignKey(
+                            referenced_table.clone(),
+                            referenced_column,
+                            value,
+                        ),
+                    )
+                    .message(),
+                )
+                .chain(Task::done(
+                    ComponentsMessage::OpenTableData(referenced_table).message(),
+                ))
+            }
+            Self::EventType::TableData(table_name, table_data_message) => {
+                let Some(table_data) = self.open_table_data.get_mut(&table_name) else {
+                    return Task::none();
+                };
+                table_data
+                    .update(table_data_message)
+                    .map(move |message| match message {
+                        Message::Tables(Self::EventType::SingleTableData(table_data_message)) => {
+                            Self::EventType::TableData(table_name.clone(), table_data_message)
+                                .message()
+                        }
+                        message => message,
+                    })
+            }
+            Self::EventType::OpenTableData(table_name) => {
+                self.active_table_data = Some(table_name.clone());
+                if self.open_table_data.contains_key(&table_name) {
+                    return Task::none();
+                }
+                self.open_table_data.insert(
+                    table_name.clone(),
+                    TableDataUI::new(self.tables.open_table_data(&table_name)),
+                );
+                Task::done(
+                    Self::EventType::TableData(
+                        table_name.clone(),
+                        TableDataMessage::GetTableData(table_name),
+                    )
+                    .message(),
+                )
+            }
+            Self::EventType::SelectTableDataTab(table_name) => {
+                if self.open_table_data.contains_key(&table_name) {
+                    self.active_table_data = Some(table_name);
+                }
+                Task::none()
+            }
+            Self::EventType::CloseTableData(table_name) => {
+                // the tab already waiting for an answer keeps its confirmation
+                if self.table_data_to_close.is_some() {
+                    return Task::none();
+                }
+                let has_pending_changes = self
+                    .open_table_data
+                    .get(&table_name)
+                    .is_some_and(|table_data| table_data.has_pending_changes());
+                self.table_data_to_close = Some(table_name);
+                if has_pending_changes {
+                    Task::none()
+                } else {
+                    Task::done(Self::EventType::ConfirmCloseTableData.message())
+                }
+            }
+            Self::EventType::ConfirmCloseTableData => {
+                let Some(table_name) = self.table_data_to_close.take() else {
+                    return Task::none();
+                };
+                let closed_table_data = self.tables.close_table_data(&table_name);
+                self.remove_open_table_data(&table_name);
+                Task::perform(
+                    async move {
+                        if let Some(table_data) = closed_table_data {
+                            table_data.teardown_component().await;
+                        }
+                    },
+                    |_| Self::EventType::TableDataClosed.message(),
+                )
+            }
+            Self::EventType::TableDataClosed => {
+                if self.open_table_data.is_empty() {
+                    Task::done(ComponentsMessage::ShowHome.message())
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::CancelCloseTableData => {
+                self.table_data_to_close = None;
+                Task::none()
             }
             Self::EventType::ShowOrRemoveTables => {
                 self.show_tables = !self.show_tables;
@@ -111,7 +340,21 @@ impl UIComponent for TablesUI {
             }
 
             Self::EventType::RequestDeleteTable(table_name) => {
-                self.table_to_delete = Some(table_name);
+                self.table_to_delete = Some(table_name.clone());
+                self.table_to_delete_dependents = vec![];
+                self.delete_table_cascade = false;
+                let tables = self.tables.clone();
+                Task::perform(
+                    async move { tables.get_dependent_table_names(&table_name).await },
+                    |dependents| Self::EventType::SetDeleteTableDependents(dependents).message(),
+                )
+            }
+            Self::EventType::SetDeleteTableDependents(dependents) => {
+                self.table_to_delete_dependents = dependents;
+                Task::none()
+            }
+            Self::EventType::ToggleDeleteTableCascade => {
+                self.delete_table_cascade = !self.delete_table_cascade;
                 Task::none()
             }
             Self::EventType::InitializeComponent => {
@@ -128,23 +371,25 @@ impl UIComponent for TablesUI {
             }
             Self::EventType::ConfirmDeleteTable => {
                 if let Some(table_to_delete) = self.table_to_delete.clone() {
+                    let cascade = self.delete_table_cascade;
+                    let dependents = std::mem::take(&mut self.table_to_delete_dependents);
                     if let Some(single_table_info) = &self.single_table_info {
-                        if single_table_info.get_table_name() == table_to_delete {
+                        let single_table_name = single_table_info.get_table_name();
+                        // cascading drops the foreign keys shown in a dependent's table info
+                        if single_table_name == table_to_delete
+                            || (cascade && dependents.contains(&single_table_name))
+                        {
                             self.single_table_info = None;
                         }
                     }
-                    if let Some(table_name) = self.single_table_data.get_table_name() {
-                        if table_name == table_to_delete {
-                            self.single_table_data =
-                                TableDataUI::new(self.tables.table_data.clone());
-                        }
-                    }
+                    self.remove_open_table_data(&table_to_delete);
                     self.table_to_delete = None;
+                    self.delete_table_cascade = false;
                     let tables = self.tables.clone();
 
                     Task::perform(
                         async move {
-                            tables.delete_table(table_to_delete).await;
+                            tables.delete_table(table_to_delete, cascade).await;
                         },
                         |_| Self::EventType::SetTables.message(),
                     )
@@ -154,9 +399,304 @@ impl UIComponent for TablesUI {
             }
             Self::EventType::CancelDeleteTable => {
                 self.table_to_delete = None;
+                self.table_to_delete_dependents = vec![];
+                self.delete_table_cascade = false;
+                Task::none()
+            }
+            Self::EventType::ToggleTableSelection(table_name) => {
+                if let Some(existing_index) = self
+                    .selected_tables
+                    .iter()
+                    .position(|selected_table| *selected_table == table_name)
+                {
+                    self.selected_tables.remove(existing_index);
+                } else {
+                    self.selected_tables.push(table_name);
+                }
+                Task::none()
+            }
+            Self::EventType::RequestDeleteSelectedTables => {
+                let tables = self.tables.clone();
+                let selected_tables = self.selected_tables.clone();
+                Task::perform(
+                    async move { tables.get_total_row_count(&selected_tables).await },
+                    |row_count| Self::EventType::SetSelectedTablesRowCount(row_count).message(),
+                )
+            }
+            Self::EventType::SetSelectedTablesRowCount(row_count) => {
+                self.selected_tables_row_count = Some(row_count);
+                Task::none()
+            }
+            Self::EventType::ConfirmDeleteSelectedTables => {
+                let selected_tables = std::mem::take(&mut self.selected_tables);
+                self.selected_tables_row_count = None;
+                if let Some(single_table_info) = &self.single_table_info {
+                    if selected_tables.contains(&single_table_info.get_table_name()) {
+                        self.single_table_info = None;
+                    }
+                }
+                for table_name in &selected_tables {
+                    self.remove_open_table_data(table_name);
+                }
+                let tables = self.tables.clone();
+                Task::perform(
+                    async move {
+                        tables.delete_tables(selected_tables).await;
+                    },
+                    |_| Self::EventType::SetTables.message(),
+                )
+            }
+            Self::EventType::CancelDeleteSelectedTables => {
+                self.selected_tables_row_count = None;
+                Task::none()
+            }
+            Self::EventType::RequestCloneTable(table_name) => {
+                self.clone_table_name = format!("{}_copy", table_name);
+                self.clone_table_constraints = false;
+                self.clone_table_structure = false;
+                self.table_to_clone = Some(table_name);
+                Task::none()
+            }
+            Self::EventType::RequestCloneTableStructure(table_name) => {
+                self.clone_table_name = format!("{}_copy", table_name);
+                self.clone_table_rows = false;
+                self.clone_table_structure = true;
+                self.table_to_clone = Some(table_name);
+                Task::none()
+            }
+            Self::EventType::UpdateCloneTableName(input) => {
+                self.clone_table_name = input;
+                Task::none()
+            }
+            Self::EventType::ToggleCloneTableConstraints => {
+                self.clone_table_constraints = !self.clone_table_constraints;
+                Task::none()
+            }
+            Self::EventType::ToggleCloneTableRows => {
+                self.clone_table_rows = !self.clone_table_rows;
+                Task::none()
+            }
+            Self::EventType::ConfirmCloneTable => {
+                if let Some(table_to_clone) = self.table_to_clone.take() {
+                    let tables = self.tables.clone();
+                    let clone_table_name = self.clone_table_name.clone();
+                    let clone_table_constraints = self.clone_table_constraints;
+                    let (clone_table_structure, clone_table_rows) =
+                        (self.clone_table_structure, self.clone_table_rows);
+                    Task::perform(
+                        async move {
+                            if clone_table_structure {
+                                tables
+                                    .clone_table_structure(
+                                        table_to_clone,
+                                        clone_table_name,
+                                        clone_table_rows,
+                                    )
+                                    .await;
+                            } else {
+                                tables
+                                    .clone_table(
+                                        table_to_clone,
+                                        clone_table_name,
+                                        clone_table_constraints,
+                                    )
+                                    .await;
+                            }
+                        },
+                        |_| Self::EventType::SetTables.message(),
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::CancelCloneTable => {
+                self.table_to_clone = None;
+                Task::none()
+            }
+            Self::EventType::RequestRenameTable(table_name) => {
+                self.rename_table_name = table_name.clone();
+                self.table_to_rename = Some(table_name);
+                Task::none()
+            }
+            Self::EventType::UpdateRenameTableName(input) => {
+                self.rename_table_name = input;
+                Task::none()
+            }
+            Self::EventType::ConfirmRenameTable => {
+                if let Some(table_to_rename) = self.table_to_rename.take() {
+                    let tables = self.tables.clone();
+                    let rename_table_name = self.rename_table_name.clone();
+                    Task::perform(
+                        async move {
+                            let renamed = tables
+                                .rename_table(table_to_rename.clone(), rename_table_name.clone())
+                                .await;
+                            (renamed, table_to_rename, rename_table_name)
+                        },
+                        |(renamed, table_to_rename, rename_table_name)| {
+                            if renamed {
+                                Self::EventType::TableRenamed(table_to_rename, rename_table_name)
+                                    .message()
+                            } else {
+                                Self::EventType::SetTables.message()
+                            }
+                        },
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Self::EventType::TableRenamed(old_name, new_name) => {
+                // the open table data moves to the new name with its pending changes
+                let mut task = Task::none();
+                if let Some(table_data) = self.open_table_data.remove(&old_name) {
+                    self.open_table_data.insert(new_name.clone(), table_data);
+                    if self.active_table_data.as_ref() == Some(&old_name) {
+                        self.active_table_data = Some(new_name.clone());
+                    }
+                    task = Task::done(
+                        Self::EventType::TableData(
+                            new_name.clone(),
+                            TableDataMessage::SetTableData,
+                        )
+                        .message(),
+                    );
+                }
+                // the displayed table info is reloaded under its new name
+                let reload_table_info = self
+                    .single_table_info
+                    .as_ref()
+                    .is_some_and(|table_info| table_info.get_table_name() == new_name);
+                task.chain(Task::done(if reload_table_info {
+                    Self::EventType::GetSingleTableInfo(new_name).message()
+                } else {
+                    Self::EventType::SetTables.message()
+                }))
+            }
+            Self::EventType::CancelRenameTable => {
+                self.table_to_rename = None;
+                Task::none()
+            }
+            Self::EventType::RequestTruncateTable(table_name) => {
+                self.table_to_truncate = Some(table_name);
+                self.truncate_table_cascade = false;
+                Task::none()
+            }
+            Self::EventType::ToggleTruncateTableCascade => {
+                self.truncate_table_cascade = !self.truncate_table_cascade;
+                Task::none()
+            }
+            Self::EventType::ConfirmTruncateTable => {
+                let Some(table_to_truncate) = self.table_to_truncate.take() else {
+                    return Task::none();
+                };
+                let cascade = std::mem::take(&mut self.truncate_table_cascade);
+                let tables = self.tables.clone();
+                Task::perform(
+                    async move { tables.truncate_table(table_to_truncate, cascade).await },
+                    |truncated| Self::EventType::TableTruncated(truncated).message(),
+                )
+            }
+            // cascading may have emptied other open tables too, so every open tab is reloaded
+            Self::EventType::TableTruncated(true) => Self::EventType::batch(
+                self.open_table_data
+                    .keys()
+                    .map(|table_name| {
+                        Self::EventType::TableData(
+                            table_name.clone(),
+                            TableDataMessage::RefreshTableData,
+                        )
+                    })
+                    .collect(),
+            ),
+            Self::EventType::TableTruncated(false) => Task::none(),
+            Self::EventType::CancelTruncateTable => {
+                self.table_to_truncate = None;
+                self.truncate_table_cascade = false;
+                Task::none()
+            }
+            Self::EventType::ToggleDryRun(dry_run) => {
+                self.tables.set_dry_run(dry_run);
+                Task::none()
+            }
+            Self::EventType::UpdateSchemaExportPath(input) => {
+                self.schema_export_path = input;
+                Task::none()
+            }
+            Self::EventType::ExportSchema => {
+                let tables = self.tables.clone();
+                let path = PathBuf::from(&self.schema_export_path);
+                Task::perform(
+                    async move {
+                        tables.export_schema(path).await;
+                    },
+                    |_| Self::EventType::SetTables.message(),
+                )
+            }
+            Self::EventType::SetTables => {
+                self.tables_loaded = true;
+                self.schema = self.tables.schema();
+                self.schema_names = self.tables.schema_names.blocking_lock().clone();
+                // drop selections of tables that no longer exist
+                let locked_tables_general_info = self.tables.tables_general_info.blocking_lock();
+                self.selected_tables.retain(|selected_table| {
+                    locked_tables_general_info
+                        .iter()
+                        .any(|table| table.table_name == *selected_table)
+                });
+                // counting every table can be slow, so it runs in the background
+                let tables = self.tables.clone();
+                Task::perform(
+                    async move {
+                        tables.set_row_counts().await;
+                    },
+                    |_| Self::EventType::RowCountsLoaded.message(),
+                )
+            }
+            Self::EventType::RowCountsLoaded => Task::none(),
+            Self::EventType::Refresh => {
+                if self
+                    .open_table_data
+                    .values()
+                    .any(|table_data| table_data.has_pending_changes())
+                {
+                    self.confirm_refresh = true;
+                    Task::none()
+                } else {
+                    Task::done(Self::EventType::ConfirmRefresh.message())
+                }
+            }
+            Self::EventType::ConfirmRefresh => {
+                self.confirm_refresh = false;
+                let tables = self.tables.clone();
+                // the open table info and data are reloaded once the columns are known
+                let mut refresh_open_tables: Vec<Self::EventType> = self
+                    .open_table_data
+                    .keys()
+                    .map(|table_name| {
+                        Self::EventType::TableData(
+                            table_name.clone(),
+                            TableDataMessage::RefreshTableData,
+                        )
+                    })
+                    .collect();
+                if let Some(table_info) = &self.single_table_info {
+                    refresh_open_tables.push(Self::EventType::GetSingleTableInfo(
+                        table_info.get_table_name(),
+                    ));
+                }
+                Task::perform(
+                    async move {
+                        tables.initialize_component().await;
+                    },
+                    |_| Self::EventType::SetTables.message(),
+                )
+                .chain(Self::EventType::batch(refresh_open_tables))
+            }
+            Self::EventType::CancelRefresh => {
+                self.confirm_refresh = false;
                 Task::none()
             }
-            Self::EventType::SetTables => Task::none(),
         }
     }
 }
@@ -165,18 +705,81 @@ impl TablesUI {
     pub fn new(tables: Arc<BusinessTables>) -> Self {
         Self {
             table_filter: String::default(),
+            table_filter_case_sensitive: false,
+            table_filter_mode: TableFilterMode::default(),
+            table_sort: TableSort::default(),
+            schema: tables.schema(),
+            schema_names: vec![],
             show_create_table_form: false,
             show_single_table_data: false,
             create_table_form: CreateTableFormUI::new(tables.clone()),
-            single_table_data: TableDataUI::new(tables.table_data.clone()),
+            open_table_data: HashMap::new(),
+            active_table_data: None,
             tables,
             single_table_info: None,
+            column_search: String::default(),
+            column_search_results: vec![],
             table_to_delete: None,
+            table_to_delete_dependents: vec![],
+            delete_table_cascade: false,
+            selected_tables: vec![],
+            selected_tables_row_count: None,
+            table_to_clone: None,
+            clone_table_name: String::default(),
+            clone_table_constraints: false,
+            clone_table_structure: false,
+            clone_table_rows: false,
+            table_to_rename: None,
+            rename_table_name: String::default(),
+            table_to_truncate: None,
+            truncate_table_cascade: false,
             show_tables: true,
+            tables_loaded: false,
+            schema_export_path: String::default(),
+            confirm_refresh: false,
+            table_data_to_close: None,
+            schema_to_select: None,
+        }
+    }
+
+    pub fn active_table_data_name(&self) -> Option<String> {
+        self.active_table_data.clone()
+    }
+
+    pub fn active_pending_change_count(&self) -> usize {
+        self.active_table_data
+            .as_ref()
+            .and_then(|table_name| self.open_table_data.get(table_name))
+            .map_or(0, |table_data| table_data.pending_change_count())
+    }
+
+    // another open table is shown when the active one goes away
+    fn remove_open_table_data(&mut self, table_name: &str) {
+        self.open_table_data.remove(table_name);
+        if self.active_table_data.as_deref() == Some(table_name) {
+            self.active_table_data = self.open_table_data.keys().min().cloned();
         }
     }
 
+    pub fn has_table(&self, table_name: &str) -> bool {
+        self.tables
+            .tables_general_info
+            .blocking_lock()
+            .iter()
+            .any(|table| table.table_name == table_name)
+    }
+
     pub fn content<'a>(&'a self) -> Element<'a, Message> {
+        if self.confirm_refresh {
+            return self.refresh_confirmation_modal();
+        }
+        if let Some(schema) = &self.schema_to_select {
+            return self.select_schema_confirmation_modal(schema);
+        }
+        if let Some(table_to_truncate) = &self.table_to_truncate {
+            return self.truncate_table_confirmation_modal(table_to_truncate);
+        }
+
         let mut row = Row::new()
             .height(Length::Fill)
             .width(Length::Fill)
@@ -210,7 +813,7 @@ impl TablesUI {
 
         if self.show_single_table_data {
             row = row.push(self.tables_component_section(
-                self.single_table_data.content(),
+                self.active_table_data_content(),
                 <TablesUI as UIComponent>::EventType::ShowOrRemoveTableData.message(),
             ));
         }
@@ -223,6 +826,78 @@ impl TablesUI {
             .into()
     }
 
+    pub fn table_data_content<'a>(&'a self) -> Element<'a, Message> {
+        let mut table_data_column = Column::new().spacing(10).push(self.table_data_tabs());
+        if let Some(table_name) = &self.table_data_to_close {
+            table_data_column =
+                table_data_column.push(self.close_table_data_confirmation_modal(table_name));
+        }
+        container(table_data_column.push(self.active_table_data_content()))
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .padding(20)
+            .style(|_| container_style())
+            .into()
+    }
+
+    fn active_table_data_content<'a>(&'a self) -> Element<'a, Message> {
+        match self
+            .active_table_data
+            .as_ref()
+            .and_then(|table_name| self.open_table_data.get(table_name))
+        {
+            Some(table_data) => table_data.content(),
+            None => text("Open a table to see its rows").into(),
+        }
+    }
+
+    // tables with pending changes are marked, closing one asks before discarding them
+    fn table_data_tabs<'a>(&'a self) -> Element<'a, Message> {
+        let mut table_names: Vec<&String> = self.open_table_data.keys().collect();
+        table_names.sort();
+        let tabs = table_names.into_iter().map(|table_name| {
+            let has_pending_changes = self.open_table_data[table_name].has_pending_changes();
+            let is_active = self.active_table_data.as_ref() == Some(table_name);
+            let label = if has_pending_changes {
+                format!("● {}", table_name)
+            } else {
+                table_name.clone()
+            };
+            Row::new()
+                .spacing(2)
+                .push(
+                    button(text(label))
+                        .style(move |theme, _| {
+                            styles::button_style(theme, ButtonKind::tab(is_active))
+                        })
+                        .on_press(
+                            <TablesUI as UIComponent>::EventType::SelectTableDataTab(
+                                table_name.clone(),
+                            )
+                            .message(),
+                        )
+                        .padding(8),
+                )
+                .push(
+                    button(text("✖"))
+                        .style(|_, _| delete_button_style())
+                        .on_press(
+                            <TablesUI as UIComponent>::EventType::CloseTableData(
+                                table_name.clone(),
+                            )
+                            .message(),
+                        )
+                        .padding(8),
+                )
+                .into()
+        });
+        scrollable(Row::with_children(tabs).spacing(10))
+            .direction(scrollable::Direction::Horizontal(
+                scrollable::Scrollbar::default(),
+            ))
+            .into()
+    }
+
     fn tables_component_section<'a>(
         &'a self,
         content: Element<'a, Message>,
@@ -243,7 +918,9 @@ impl TablesUI {
 
     fn tables_section<'a>(&'a self) -> Element<'a, Message> {
         let mut tables_display = Column::new().spacing(10).padding(10);
+        tables_display = tables_display.push(self.schema_picker());
         tables_display = tables_display.push(self.table_filter_input());
+        tables_display = tables_display.push(self.column_search_panel());
         tables_display = tables_display.push(self.tables_container());
 
         let scrollable_section = scrollable(
@@ -272,6 +949,34 @@ impl TablesUI {
         .on_press(<TablesUI as UIComponent>::EventType::ShowOrRemoveTableData.message())
         .padding(10);
 
+        let export_schema_controls = Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(
+                text_input("Schema file path", &self.schema_export_path)
+                    .on_input(|input| {
+                        <TablesUI as UIComponent>::EventType::UpdateSchemaExportPath(input)
+                            .message()
+                    })
+                    .padding(10)
+                    .style(|_, _| text_input_style()),
+            )
+            .push(
+                button("Export schema")
+                    .style(|_, _| button_style())
+                    .on_press_maybe(
+                        (!self.schema_export_path.is_empty())
+                            .then(|| <TablesUI as UIComponent>::EventType::ExportSchema.message()),
+                    )
+                    .padding(10),
+            );
+
+        // the statements of schema changes are written to the console instead of being run
+        let dry_run_checkbox = checkbox("Dry run schema changes", self.tables.is_dry_run())
+            .on_toggle(|dry_run| {
+                <TablesUI as UIComponent>::EventType::ToggleDryRun(dry_run).message()
+            });
+
         let toggle_tables_button = button("Remove tables")
             .style(|_, _| button_style())
             .on_press(<TablesUI as UIComponent>::EventType::ShowOrRemoveTables.message())
@@ -281,6 +986,8 @@ impl TablesUI {
             .push(scrollable_section)
             .push(toggle_form_button)
             .push(toggle_table_data_button)
+            .push(export_schema_controls)
+            .push(dry_run_checkbox)
             .push(toggle_tables_button)
             .spacing(10)
             .padding(10)
@@ -289,8 +996,29 @@ impl TablesUI {
             .into()
     }
 
+    fn schema_picker<'a>(&'a self) -> Row<'a, Message> {
+        Row::new()
+            .spacing(10)
+            .align_y(Vertical::Center)
+            .push(text("Schema"))
+            .push(pick_list(
+                self.schema_names.as_slice(),
+                Some(&self.schema),
+                |schema| <TablesUI as UIComponent>::EventType::SelectSchema(schema).message(),
+            ))
+    }
+
+    // an invalid pattern lists every table, the error tells the user why
     fn table_filter_input<'a>(&'a self) -> Element<'a, Message> {
-        text_input("Search Tables", &self.table_filter)
+        let filter_error = compile_table_filter(
+            &self.table_filter,
+            self.table_filter_mode,
+            self.table_filter_case_sensitive,
+        )
+        .err();
+        let is_invalid = filter_error.is_some();
+        let filter_input = text_input("Search Tables", &self.table_filter)
+            .id(text_input::Id::new(TABLE_FILTER_INPUT_ID))
             .on_input(|input| {
                 <TablesUI as UIComponent>::EventType::message(
                     <TablesUI as UIComponent>::EventType::UpdateTableFilter(input),
@@ -298,7 +1026,99 @@ impl TablesUI {
             })
             .width(Length::Fill)
             .padding(10)
-            .style(|_, _| text_input_style())
+            .style(move |_, _| {
+                if is_invalid {
+                    invalid_text_input_style()
+                } else {
+                    text_input_style()
+                }
+            });
+        let case_sensitive_checkbox = checkbox("Match case", self.table_filter_case_sensitive)
+            .on_toggle(|_| {
+                <TablesUI as UIComponent>::EventType::ToggleTableFilterCaseSensitive.message()
+            });
+        let (matched_table_count, total_table_count) = self.filtered_table_count();
+        let table_count_text = text(format!(
+            "{} of {} tables",
+            matched_table_count, total_table_count
+        ))
+        .size(14)
+        .color(Color::from_rgb(0.6, 0.6, 0.6));
+        let mode_picklist = pick_list(
+            TableFilterMode::ALL,
+            Some(self.table_filter_mode),
+            |table_filter_mode| {
+                <TablesUI as UIComponent>::EventType::SelectTableFilterMode(table_filter_mode)
+                    .message()
+            },
+        );
+        let mut filter_column = Column::new().spacing(5).push(
+            Row::new()
+                .spacing(10)
+                .align_y(Vertical::Center)
+                .push(filter_input)
+                .push(table_count_text)
+                .push(mode_picklist)
+                .push(case_sensitive_checkbox)
+                .push(pick_list(
+                    TableSort::ALL,
+                    Some(self.table_sort),
+                    |table_sort| {
+                        <TablesUI as UIComponent>::EventType::SetTableSort(table_sort).message()
+                    },
+                )),
+        );
+        if filter_error.is_some() {
+            filter_column = filter_column.push(
+                text("Invalid pattern, showing all tables")
+                    .size(12)
+                    .color(Color::from_rgb(1.0, 0.4, 0.4)),
+            );
+        }
+        filter_column.into()
+    }
+    // each match opens the table it was found in
+    fn column_search_panel<'a>(&'a self) -> Element<'a, Message> {
+        let search_input = text_input("Search Columns", &self.column_search)
+            .on_input(|input| {
+                <TablesUI as UIComponent>::EventType::UpdateColumnSearch(input).message()
+            })
+            .on_submit(<TablesUI as UIComponent>::EventType::SearchColumns.message())
+            .width(Length::Fill)
+            .padding(10)
+            .style(|_, _| text_input_style());
+        let search_button = button("Find")
+            .style(|_, _| button_style())
+            .on_press_maybe(
+                (!self.column_search.trim().is_empty())
+                    .then(|| <TablesUI as UIComponent>::EventType::SearchColumns.message()),
+            )
+            .padding(10);
+
+        self.column_search_results
+            .iter()
+            .fold(
+                Column::new().spacing(5).push(
+                    Row::new()
+                        .spacing(10)
+                        .align_y(Vertical::Center)
+                        .push(search_input)
+                        .push(search_button),
+                ),
+                |search_panel, (table_name, column_name)| {
+                    search_panel.push(
+                        button(text(format!("{}.{}", table_name, column_name)).size(14))
+                            .style(|_, _| column_search_result_button_style())
+                            .on_press(
+                                <TablesUI as UIComponent>::EventType::GetSingleTableInfo(
+                                    table_name.clone(),
+                                )
+                                .message(),
+                            )
+                            .padding(5),
+                    )
+                },
+            )
             .into()
     }
     fn delete_table_styled_confirmation_text<'a>(&'a self) -> Element<'a, Message> {
@@ -323,18 +1143,295 @@ impl TablesUI {
             .wrap()
             .into()
     }
+    fn delete_table_dependents_warning<'a>(&'a self) -> Element<'a, Message> {
+        let warning_text = Text::new("It is referenced by the foreign keys of these tables:")
+            .size(18)
+            .color(Color::from_rgb(1.0, 0.85, 0.3));
+
+        let dependent_names = self.table_to_delete_dependents.iter().fold(
+            Column::new().spacing(5),
+            |dependent_names, dependent_name| {
+                dependent_names.push(
+                    text(dependent_name.clone())
+                        .size(18)
+                        .color(Color::from_rgb(1.0, 0.4, 0.4)),
+                )
+            },
+        );
+
+        let cascade_checkbox = checkbox(
+            "Cascade, dropping their foreign keys to this table",
+            self.delete_table_cascade,
+        )
+        .on_toggle(|_| <TablesUI as UIComponent>::EventType::ToggleDeleteTableCascade.message());
+
+        Column::new()
+            .spacing(10)
+            .push(warning_text)
+            .push(dependent_names)
+            .push(cascade_checkbox)
+            .into()
+    }
     fn delete_table_confirmation_modal<'a>(&'a self) -> Element<'a, Message> {
+        // the drop would fail while other tables still reference it
+        let can_delete = self.table_to_delete_dependents.is_empty() || self.delete_table_cascade;
         let confirm_button = Button::new(text("Yes, delete"))
-            .on_press(<TablesUI as UIComponent>::EventType::ConfirmDeleteTable.message())
+            .on_press_maybe(
+                can_delete
+                    .then(|| <TablesUI as UIComponent>::EventType::ConfirmDeleteTable.message()),
+            )
             .style(|_, _| delete_button_style());
 
         let cancel_button = Button::new(text("Cancel"))
             .on_press(<TablesUI as UIComponent>::EventType::CancelDeleteTable.message());
 
+        let mut confirmation_content = Column::new()
+            .spacing(20)
+            .push(self.delete_table_styled_confirmation_text());
+        if !self.table_to_delete_dependents.is_empty() {
+            confirmation_content =
+                confirmation_content.push(self.delete_table_dependents_warning());
+        }
+
+        let modal_content = container(
+            confirmation_content.push(
+                Row::new()
+                    .spacing(10)
+                    .push(confirm_button)
+                    .push(cancel_button),
+            ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+    fn delete_selected_tables_confirmation_modal<'a>(
+        &'a self,
+        row_count: i64,
+    ) -> Element<'a, Message> {
+        let confirmation_text = Text::new(format!(
+            "Are you sure you want to delete {} tables ({} rows in total)?",
+            self.selected_tables.len(),
+            row_count
+        ))
+        .size(20)
+        .color(Color::from_rgb(0.9, 0.9, 0.9));
+
+        let table_names = self.selected_tables.iter().fold(
+            Column::new().spacing(5),
+            |table_names, table_name| {
+                table_names.push(
+                    text(table_name.clone())
+                        .size(18)
+                        .color(Color::from_rgb(1.0, 0.4, 0.4)),
+                )
+            },
+        );
+
+        let confirm_button = Button::new(text("Yes, delete all"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmDeleteSelectedTables.message())
+            .style(|_, _| delete_button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelDeleteSelectedTables.message());
+
+        let modal_content = container(
+            Column::new()
+                .spacing(20)
+                .push(confirmation_text)
+                .push(table_names)
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(confirm_button)
+                        .push(cancel_button),
+                ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+    fn clone_table_modal<'a>(&'a self, table_to_clone: &str) -> Element<'a, Message> {
+        let clone_table_name_input = text_input("New Table Name", &self.clone_table_name)
+            .on_input(|input| {
+                <TablesUI as UIComponent>::EventType::UpdateCloneTableName(input).message()
+            })
+            .padding(10)
+            .style(|_, _| text_input_style());
+
+        // CREATE TABLE AS only copies the rows, the constraints are added afterwards,
+        // CREATE TABLE LIKE only copies the structure, the rows are inserted afterwards
+        let (title, copy_checkbox) = if self.clone_table_structure {
+            (
+                format!("Clone the structure of table {}", table_to_clone),
+                checkbox("Also copy data", self.clone_table_rows).on_toggle(|_| {
+                    <TablesUI as UIComponent>::EventType::ToggleCloneTableRows.message()
+                }),
+            )
+        } else {
+            (
+                format!("Clone table {} with its data", table_to_clone),
+                checkbox("Also copy constraints", self.clone_table_constraints).on_toggle(|_| {
+                    <TablesUI as UIComponent>::EventType::ToggleCloneTableConstraints.message()
+                }),
+            )
+        };
+
+        let confirm_button = Button::new(text("Clone"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmCloneTable.message())
+            .style(|_, _| button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelCloneTable.message());
+
+        let modal_content = container(
+            Column::new()
+                .spacing(20)
+                .push(
+                    Text::new(title)
+                        .size(20)
+                        .color(Color::from_rgb(0.9, 0.9, 0.9)),
+                )
+                .push(clone_table_name_input)
+                .push(copy_checkbox)
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(confirm_button)
+                        .push(cancel_button),
+                ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+    fn rename_table_modal<'a>(&'a self, table_to_rename: &str) -> Element<'a, Message> {
+        let rename_table_name_input = text_input("New Table Name", &self.rename_table_name)
+            .on_input(|input| {
+                <TablesUI as UIComponent>::EventType::UpdateRenameTableName(input).message()
+            })
+            .padding(10)
+            .style(|_, _| text_input_style());
+
+        let confirm_button = Button::new(text("Rename"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmRenameTable.message())
+            .style(|_, _| button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelRenameTable.message());
+
+        let modal_content = container(
+            Column::new()
+                .spacing(20)
+                .push(
+                    Text::new(format!("Rename table {}", table_to_rename))
+                        .size(20)
+                        .color(Color::from_rgb(0.9, 0.9, 0.9)),
+                )
+                .push(rename_table_name_input)
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(confirm_button)
+                        .push(cancel_button),
+                ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+    fn refresh_confirmation_modal<'a>(&'a self) -> Element<'a, Message> {
+        let confirm_button = Button::new(text("Discard and refresh"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmRefresh.message())
+            .style(|_, _| delete_button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelRefresh.message());
+
+        let modal_content = container(
+            Column::new()
+                .spacing(20)
+                .push(
+                    Text::new("The table data has uncommitted changes, discard them and refresh?")
+                        .size(20)
+                        .color(Color::from_rgb(0.9, 0.9, 0.9)),
+                )
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(confirm_button)
+                        .push(cancel_button),
+                ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+
+    fn select_schema_confirmation_modal<'a>(&'a self, schema: &str) -> Element<'a, Message> {
+        let confirm_button = Button::new(text("Discard and switch"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmSelectSchema.message())
+            .style(|_, _| delete_button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelSelectSchema.message());
+
+        let modal_content = container(
+            Column::new()
+                .spacing(20)
+                .push(
+                    Text::new(format!(
+                        "The table data has uncommitted changes, discard them and switch to schema {}?",
+                        schema
+                    ))
+                    .size(20)
+                    .color(Color::from_rgb(0.9, 0.9, 0.9)),
+                )
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(confirm_button)
+                        .push(cancel_button),
+                ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+
+    // unlike the table data edits a truncate can't be undone
+    fn truncate_table_confirmation_modal<'a>(&'a self, table_name: &str) -> Element<'a, Message> {
+        let confirm_button = Button::new(text("Yes, truncate"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmTruncateTable.message())
+            .style(|_, _| delete_button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelTruncateTable.message());
+
+        let cascade_checkbox = checkbox(
+            "Cascade, also emptying the tables referencing this one",
+            self.truncate_table_cascade,
+        )
+        .on_toggle(|_| <TablesUI as UIComponent>::EventType::ToggleTruncateTableCascade.message());
+
         let modal_content = container(
             Column::new()
                 .spacing(20)
-                .push(self.delete_table_styled_confirmation_text())
+                .push(
+                    Text::new(format!(
+                        "Delete every row of {}? Uncommitted edits of its table data are discarded.",
+                        table_name
+                    ))
+                    .size(20)
+                    .color(Color::from_rgb(0.9, 0.9, 0.9)),
+                )
+                .push(cascade_checkbox)
                 .push(
                     Row::new()
                         .spacing(10)
@@ -347,23 +1444,95 @@ impl TablesUI {
 
         container(modal_content).padding(20).into()
     }
+
+    fn close_table_data_confirmation_modal<'a>(&'a self, table_name: &str) -> Element<'a, Message> {
+        let confirm_button = Button::new(text("Discard and close"))
+            .on_press(<TablesUI as UIComponent>::EventType::ConfirmCloseTableData.message())
+            .style(|_, _| delete_button_style());
+
+        let cancel_button = Button::new(text("Cancel"))
+            .on_press(<TablesUI as UIComponent>::EventType::CancelCloseTableData.message());
+
+        let modal_content = container(
+            Column::new()
+                .spacing(20)
+                .push(
+                    Text::new(format!(
+                        "The table {} has uncommitted changes, discard them and close it?",
+                        table_name
+                    ))
+                    .size(20)
+                    .color(Color::from_rgb(0.9, 0.9, 0.9)),
+                )
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(confirm_button)
+                        .push(cancel_button),
+                ),
+        )
+        .padding(20)
+        .style(|_| delete_table_confirmation_modal_style());
+
+        container(modal_content).padding(20).into()
+    }
+
+    // a filter matching nothing still shows the filtered list, this is only for a schema without tables
+    fn empty_tables_state<'a>(&'a self) -> Element<'a, Message> {
+        container(
+            Column::new()
+                .spacing(15)
+                .align_x(Alignment::Center)
+                .push(text("No tables yet — create one").size(18))
+                .push(
+                    button(text("➕ Create table"))
+                        .style(|theme, _| styles::button_style(theme, ButtonKind::Primary))
+                        .on_press(
+                            <TablesUI as UIComponent>::EventType::OpenCreateTableForm.message(),
+                        )
+                        .padding(12),
+                ),
+        )
+        .center_x(Length::Fill)
+        .padding(30)
+        .into()
+    }
+
     fn tables_container<'a>(&'a self) -> Element<'a, Message> {
+        if !self.tables_loaded {
+            return container(text("Loading tables...")).padding(10).into();
+        }
         let locked_tables_general_info = self.tables.tables_general_info.blocking_lock();
+        if locked_tables_general_info.is_empty() {
+            return self.empty_tables_state();
+        }
+        let locked_row_counts = self.tables.row_counts.blocking_lock();
         let mut tables_column = Column::new().spacing(10).padding(10);
         let table_filter_pattern = self.get_table_filter_regex();
-
-        for table in locked_tables_general_info
-            .clone()
-            .into_iter()
+        let mut tables: Vec<&BTableGeneral> = locked_tables_general_info
+            .iter()
             .filter(|t| table_filter_pattern.is_match(&t.table_name))
-        {
-            let view_button = button(text(table.table_name.clone())).on_press(
-                <TablesUI as UIComponent>::EventType::message(
+            .collect();
+        tables.sort_by(|table, other_table| {
+            compare_table_names(
+                self.table_sort,
+                &locked_row_counts,
+                &table.table_name,
+                &other_table.table_name,
+            )
+        });
+
+        for table in tables {
+            let mut table_label = highlighted_table_name(&table_filter_pattern, &table.table_name);
+            if let Some(row_count) = locked_row_counts.get(&table.table_name) {
+                table_label = table_label.push(text(format!(" ({} rows)", row_count)));
+            }
+            let view_button =
+                button(table_label).on_press(<TablesUI as UIComponent>::EventType::message(
                     <TablesUI as UIComponent>::EventType::GetSingleTableInfo(
                         table.table_name.clone(),
                     ),
-                ),
-            );
+                ));
 
             let delete_button = button(text("🗑️ Delete"))
                 .style(|_, _| delete_button_style())
@@ -373,22 +1542,173 @@ impl TablesUI {
                     ),
                 ));
 
-            let table_row = Row::new().spacing(10).push(view_button).push(delete_button);
+            let open_data_button = button(text("📋 Data"))
+                .style(|_, _| button_style())
+                .on_press(ComponentsMessage::OpenTableData(table.table_name.clone()).message());
+
+            let clone_button = button(text("📄 Clone"))
+                .style(|_, _| button_style())
+                .on_press(<TablesUI as UIComponent>::EventType::message(
+                    <TablesUI as UIComponent>::EventType::RequestCloneTable(
+                        table.table_name.clone(),
+                    ),
+                ));
+
+            let clone_structure_button = button(text("📐 Clone structure"))
+                .style(|_, _| button_style())
+                .on_press(
+                    <TablesUI as UIComponent>::EventType::RequestCloneTableStructure(
+                        table.table_name.clone(),
+                    )
+                    .message(),
+                );
+
+            let rename_button = button(text("✏️ Rename"))
+                .style(|_, _| button_style())
+                .on_press(<TablesUI as UIComponent>::EventType::message(
+                    <TablesUI as UIComponent>::EventType::RequestRenameTable(
+                        table.table_name.clone(),
+                    ),
+                ));
+
+            let table_name = table.table_name.clone();
+            let select_checkbox = checkbox("", self.selected_tables.contains(&table.table_name))
+                .on_toggle(move |_| {
+                    <TablesUI as UIComponent>::EventType::ToggleTableSelection(table_name.clone())
+                        .message()
+                });
+
+            let table_row = Row::new()
+                .spacing(10)
+                .align_y(Vertical::Center)
+                .push(select_checkbox)
+                .push(view_button)
+                .push(open_data_button)
+                .push(rename_button)
+                .push(clone_button)
+                .push(clone_structure_button)
+                .push(delete_button);
 
             tables_column = tables_column.push(table_row);
         }
 
+        if !self.selected_tables.is_empty() {
+            let delete_selected_button = button(text(format!(
+                "🗑️ Delete selected ({})",
+                self.selected_tables.len()
+            )))
+            .style(|_, _| delete_button_style())
+            .on_press(<TablesUI as UIComponent>::EventType::RequestDeleteSelectedTables.message());
+            tables_column = tables_column.push(delete_selected_button);
+        }
+
         if !self.table_to_delete.is_none() {
             return self.delete_table_confirmation_modal();
         }
+        if let Some(row_count) = self.selected_tables_row_count {
+            return self.delete_selected_tables_confirmation_modal(row_count);
+        }
+        if let Some(table_to_clone) = &self.table_to_clone {
+            return self.clone_table_modal(table_to_clone);
+        }
+        if let Some(table_to_rename) = &self.table_to_rename {
+            return self.rename_table_modal(table_to_rename);
+        }
 
         tables_column.into()
     } // ======================== SECTION: Create Table ========================
 
+    // (tables shown, tables in the schema)
+    fn filtered_table_count(&self) -> (usize, usize) {
+        let table_filter_pattern = self.get_table_filter_regex();
+        let locked_tables_general_info = self.tables.tables_general_info.blocking_lock();
+        let matched_table_count = locked_tables_general_info
+            .iter()
+            .filter(|table| table_filter_pattern.is_match(&table.table_name))
+            .count();
+        (matched_table_count, locked_tables_general_info.len())
+    }
+
     fn get_table_filter_regex(&self) -> Regex {
-        Regex::new(&format!(r"(?i){}", self.table_filter))
-            .unwrap_or_else(|_| Regex::new("").unwrap())
+        compile_table_filter(
+            &self.table_filter,
+            self.table_filter_mode,
+            self.table_filter_case_sensitive,
+        )
+        .unwrap_or_else(|_| Regex::new("").unwrap())
+    }
+}
+
+fn compile_table_filter(
+    table_filter: &str,
+    table_filter_mode: TableFilterMode,
+    case_sensitive: bool,
+) -> Result<Regex, regex::Error> {
+    let pattern = match table_filter_mode {
+        TableFilterMode::Contains => table_filter.to_string(),
+        TableFilterMode::Exact => format!("^(?:{})$", table_filter),
+        TableFilterMode::Prefix => format!("^(?:{})", table_filter),
+    };
+    if case_sensitive {
+        Regex::new(&pattern)
+    } else {
+        Regex::new(&format!(r"(?i){}", pattern))
+    }
+}
+
+// names are compared case insensitively, equal row counts fall back to the name order
+fn compare_table_names(
+    table_sort: TableSort,
+    row_counts: &HashMap<String, i64>,
+    table_name: &str,
+    other_table_name: &str,
+) -> Ordering {
+    let name_ordering = table_name
+        .to_lowercase()
+        .cmp(&other_table_name.to_lowercase())
+        .then_with(|| table_name.cmp(other_table_name));
+    match table_sort {
+        TableSort::NameAscending => name_ordering,
+        TableSort::NameDescending => name_ordering.reverse(),
+        // Option orders None first, so it is reversed with the counts to put unknown counts last
+        TableSort::RowCountDescending => row_counts
+            .get(other_table_name)
+            .cmp(&row_counts.get(table_name))
+            .then(name_ordering),
+    }
+}
+
+// byte ranges of the table name matched by the filter, empty matches (an empty filter) are skipped
+fn table_filter_match_spans(table_filter_pattern: &Regex, table_name: &str) -> Vec<(usize, usize)> {
+    table_filter_pattern
+        .find_iter(table_name)
+        .filter(|table_filter_match| !table_filter_match.is_empty())
+        .map(|table_filter_match| (table_filter_match.start(), table_filter_match.end()))
+        .collect()
+}
+
+fn highlighted_table_name<'a>(table_filter_pattern: &Regex, table_name: &str) -> Row<'a, Message> {
+    let mut table_name_row = Row::new();
+    let mut unmatched_start = 0;
+    for (match_start, match_end) in table_filter_match_spans(table_filter_pattern, table_name) {
+        if unmatched_start < match_start {
+            table_name_row =
+                table_name_row.push(text(table_name[unmatched_start..match_start].to_string()));
+        }
+        table_name_row = table_name_row.push(
+            text(table_name[match_start..match_end].to_string())
+                .font(Font {
+                    weight: Weight::Bold,
+                    ..Font::DEFAULT
+                })
+                .color(Color::from_rgb(1.0, 0.85, 0.3)),
+        );
+        unmatched_start = match_end;
     }
+    if unmatched_start < table_name.len() {
+        table_name_row = table_name_row.push(text(table_name[unmatched_start..].to_string()));
+    }
+    table_name_row
 }
 
 fn container_style() -> container::Style {
@@ -441,6 +1761,19 @@ fn delete_button_style() -> button::Style {
     }
 }
 
+fn column_search_result_button_style() -> button::Style {
+    button::Style {
+        background: None,
+        border: Border {
+            color: Color::from_rgb(0.0, 0.6, 0.5),
+            width: 1.0,
+            radius: Radius::from(5.0),
+        },
+        text_color: Color::from_rgb(0.0, 0.75, 0.65),
+        shadow: Shadow::default(),
+    }
+}
+
 fn text_input_style() -> text_input::Style {
     text_input::Style {
         background: Background::Color(Color::from_rgb(0.2, 0.2, 0.2)), // Darker input background
@@ -456,6 +1789,17 @@ fn text_input_style() -> text_input::Style {
     }
 }
 
+fn invalid_text_input_style() -> text_input::Style {
+    text_input::Style {
+        border: Border {
+            width: 1.5,
+            color: Color::from_rgb(1.0, 0.4, 0.4),
+            radius: Radius::from(5.0),
+        },
+        ..text_input_style()
+    }
+}
+
 fn delete_table_confirmation_modal_style() -> container::Style {
     container::Style {
         // Semi-transparent dark background
@@ -479,3 +1823,69 @@ fn delete_table_confirmation_modal_style() -> container::Style {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_table_filter() {
+        let regex = compile_table_filter("^user", TableFilterMode::Contains, false).unwrap();
+        assert!(regex.is_match("Users"));
+        assert!(!regex.is_match("orders"));
+        assert!(compile_table_filter("user(", TableFilterMode::Contains, false).is_err());
+    }
+
+    #[test]
+    fn test_compile_table_filter_modes() {
+        let exact = compile_table_filter("users", TableFilterMode::Exact, false).unwrap();
+        assert!(exact.is_match("Users"));
+        assert!(!exact.is_match("users_archive"));
+        let prefix = compile_table_filter("user", TableFilterMode::Prefix, false).unwrap();
+        assert!(prefix.is_match("users_archive"));
+        assert!(!prefix.is_match("app_users"));
+        let case_sensitive = compile_table_filter("users", TableFilterMode::Exact, true).unwrap();
+        assert!(!case_sensitive.is_match("Users"));
+    }
+
+    #[test]
+    fn test_compare_table_names() {
+        let row_counts = HashMap::from([(String::from("orders"), 10), (String::from("users"), 3)]);
+        let sorted_table_names = |table_sort| {
+            let mut table_names = vec!["users", "Accounts", "orders", "items"];
+            table_names.sort_by(|table_name, other_table_name| {
+                compare_table_names(table_sort, &row_counts, table_name, other_table_name)
+            });
+            table_names
+        };
+        assert_eq!(
+            sorted_table_names(TableSort::NameAscending),
+            vec!["Accounts", "items", "orders", "users"]
+        );
+        assert_eq!(
+            sorted_table_names(TableSort::NameDescending),
+            vec!["users", "orders", "items", "Accounts"]
+        );
+        assert_eq!(
+            sorted_table_names(TableSort::RowCountDescending),
+            vec!["orders", "users", "Accounts", "items"]
+        );
+    }
+
+    #[test]
+    fn test_table_filter_match_spans() {
+        let regex = compile_table_filter("s", TableFilterMode::Contains, false).unwrap();
+        assert_eq!(
+            table_filter_match_spans(&regex, "Sessions"),
+            vec![(0, 1), (2, 3), (3, 4), (7, 8)]
+        );
+        let prefix = compile_table_filter("user", TableFilterMode::Prefix, false).unwrap();
+        assert_eq!(
+            table_filter_match_spans(&prefix, "users_user"),
+            vec![(0, 4)]
+        );
+        // an empty filter matches every table without highlighting anything
+        let empty = compile_table_filter("", TableFilterMode::Contains, false).unwrap();
+        assert!(table_filter_match_spans(&empty, "users").is_empty());
+    }
+}
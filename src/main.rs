@@ -1,41 +1,165 @@
 mod components;
+use crate::components::business_components::{
+    component::{BSessionState, BThemeMode},
+    components::{BusinessAppearance, BusinessConnection, BusinessSession},
+};
 use crate::components::ui_components::{
     component::{Event, UIComponent},
     components::{ComponentsMessage, CurrentComponent, UIComponents},
+    connection::connection::ConnectionUI,
+    connection::events::ConnectionMessage,
     events::Message,
+    home::events::HomeMessage,
+    loading::{busy_task, loading_indicator},
+    tables::{
+        events::TablesMessage,
+        table_data::events::{CellFocusMove, TableDataMessage},
+    },
 };
 use iced::{
-    widget::{button, column, container, row, text, Column, Row, Text},
-    Element, Settings, Task, Theme,
+    alignment::Vertical,
+    keyboard::{self, key::Named, Key, Modifiers},
+    widget::{button, container, row, text, Column, Row, Text},
+    window, Background, Color, Element, Length, Settings, Size, Subscription, Task, Theme,
 };
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct Crm {
+    // shown until the database connection is made
+    connection_ui: ConnectionUI,
     components: Option<UIComponents>,
+    // shown in a banner above every view until dismissed
+    error: Option<String>,
+    // long running tasks in flight, the loading indicator is shown while any are
+    busy_task_count: usize,
+    loading_indicator_frame: usize,
+    appearance: BusinessAppearance,
+    theme_mode: BThemeMode,
+    session: BusinessSession,
+    window_size: Option<Size>,
+    // the table that was open when the app was last closed, opened once the tables are loaded
+    table_to_restore: Option<String>,
+    // listed on the home view, most recently opened first
+    recent_tables: Vec<String>,
 }
 
 impl Crm {
-    pub fn setup() -> (Self, Task<Message>) {
+    pub fn setup(session: BusinessSession, session_state: BSessionState) -> (Self, Task<Message>) {
+        let connection_ui = ConnectionUI::new(Arc::new(BusinessConnection::default()));
+        let startup_task = busy_task(connection_ui.startup_task());
+        let appearance = BusinessAppearance::default();
+        let theme_mode = appearance.theme_mode();
         (
-            Self { components: None },
-            Task::perform(UIComponents::new(), |components| {
-                Message::Components(ComponentsMessage::InitializeComponents(components))
-            }),
+            Self {
+                connection_ui,
+                components: None,
+                error: None,
+                busy_task_count: 0,
+                loading_indicator_frame: 0,
+                appearance,
+                theme_mode,
+                session,
+                window_size: session_state
+                    .window_size
+                    .map(|(width, height)| Size::new(width, height)),
+                table_to_restore: session_state.open_table,
+                recent_tables: session_state.recent_tables,
+            },
+            startup_task,
         )
     }
     pub fn title(&self) -> String {
         String::from("CRM")
     }
     pub fn theme(&self) -> Theme {
-        Theme::Dark
+        match self.theme_mode {
+            BThemeMode::Dark => Theme::Dark,
+            BThemeMode::Light => Theme::Light,
+        }
     }
     pub fn view(&self) -> Element<'_, Message> {
+        let mut view = Column::new();
+        if let Some(error) = &self.error {
+            view = view.push(error_banner(error));
+        }
+        if self.busy_task_count > 0 {
+            view = view.push(loading_indicator(self.loading_indicator_frame));
+        }
+        view = view
+            .push(self.theme_toggle_button())
+            .push(self.current_view());
+        // there is nothing to report on before the connection is made
+        match &self.components {
+            Some(components) => view.push(status_bar(components)).into(),
+            None => view.into(),
+        }
+    }
+    fn theme_toggle_button(&self) -> Element<'_, Message> {
+        button(match self.theme_mode {
+            BThemeMode::Dark => "Light Theme",
+            BThemeMode::Light => "Dark Theme",
+        })
+        .on_press(Message::ToggleTheme)
+        .into()
+    }
+    // the console only exists once connected, the error banner is used before that
+    fn report_warning(&self, warning: String) -> Task<Message> {
+        match &self.components {
+            Some(components) => {
+                components.console_ui.write_warning(warning);
+                Task::none()
+            }
+            None => Task::done(Message::ShowError(warning)),
+        }
+    }
+    fn session_state(&self) -> BSessionState {
+        let open_table =
+            self.components
+                .as_ref()
+                .and_then(|components| match &components.current_component {
+                    CurrentComponent::TableData => components.tables_ui.active_table_data_name(),
+                    CurrentComponent::Home => None,
+                });
+        BSessionState {
+            window_size: self.window_size.map(|size| (size.width, size.height)),
+            open_table,
+            recent_tables: self.recent_tables.clone(),
+        }
+    }
+    fn current_view(&self) -> Element<'_, Message> {
         if let Some(components) = &self.components {
             match components.current_component {
                 CurrentComponent::Home => {
                     let mut display = Row::new();
 
-                    // Add the main content
-                    display = display.push(Column::new().push(components.tables_ui.content()));
+                    // Add the home bar with the refresh button, tables dropped since they were
+                    // opened are left out of the recents
+                    let recent_tables = self
+                        .recent_tables
+                        .iter()
+                        .filter(|table_name| components.tables_ui.has_table(table_name))
+                        .cloned()
+                        .collect();
+                    display = display.push(components.home_ui.content(recent_tables));
+
+                    // Add the main content, the query runner is shown below the tables
+                    let mut main_content = Column::new().push(components.tables_ui.content());
+                    if components.show_query_runner {
+                        main_content = main_content.push(components.query_runner_ui.content());
+                    }
+                    display = display.push(main_content);
+
+                    display = display.push(
+                        button(if components.show_query_runner {
+                            "Remove Query Runner"
+                        } else {
+                            "Show Query Runner"
+                        })
+                        .on_press(ComponentsMessage::message(
+                            ComponentsMessage::ShowOrRemoveQueryRunner,
+                        )),
+                    );
 
                     // Add the "Show Console" button
                     display = display.push(
@@ -56,15 +180,102 @@ impl Crm {
 
                     display.into()
                 }
+                CurrentComponent::TableData => Column::new()
+                    .spacing(10)
+                    .padding(10)
+                    .push(
+                        button("← Back")
+                            .on_press(ComponentsMessage::message(ComponentsMessage::ShowHome)),
+                    )
+                    .push(components.tables_ui.table_data_content())
+                    .into(),
             }
         } else {
-            column![container("loading")].into()
+            self.connection_ui.content()
         }
     }
+    pub fn subscription(&self) -> Subscription<Message> {
+        let loading_indicator_subscription = if self.busy_task_count > 0 {
+            iced::time::every(Duration::from_millis(100)).map(|_| Message::AdvanceLoadingIndicator)
+        } else {
+            Subscription::none()
+        };
+        let window_subscription = Subscription::batch([
+            window::resize_events().map(|(_, size)| Message::WindowResized(size)),
+            window::close_requests().map(Message::WindowCloseRequested),
+        ]);
+        if let Some(components) = &self.components {
+            Subscription::batch([
+                components.subscription(),
+                keyboard::on_key_press(shortcut_message),
+                loading_indicator_subscription,
+                window_subscription,
+            ])
+        } else {
+            Subscription::batch([loading_indicator_subscription, window_subscription])
+        }
+    }
+    // problems logged while handling a message are raised once it is handled
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        let task = if is_long_running(&message) {
+            busy_task(self.handle_message(message))
+        } else {
+            self.handle_message(message)
+        };
+        match self
+            .components
+            .as_ref()
+            .and_then(|components| components.console_ui.take_error())
+        {
+            Some(error) => task.chain(Task::done(Message::ShowError(error))),
+            None => task,
+        }
+    }
+    fn handle_message(&mut self, message: Message) -> Task<Message> {
         match message {
+            Message::ShowError(error) => {
+                self.error = Some(error);
+                Task::none()
+            }
+            Message::DismissError => {
+                self.error = None;
+                Task::none()
+            }
+            Message::BusyStarted => {
+                self.busy_task_count += 1;
+                Task::none()
+            }
+            Message::BusyFinished => {
+                self.busy_task_count = self.busy_task_count.saturating_sub(1);
+                Task::none()
+            }
+            Message::AdvanceLoadingIndicator => {
+                self.loading_indicator_frame = self.loading_indicator_frame.wrapping_add(1);
+                Task::none()
+            }
+            Message::ToggleTheme => {
+                self.theme_mode = self.theme_mode.toggled();
+                match self.appearance.save_theme_mode(self.theme_mode) {
+                    Ok(()) => Task::none(),
+                    Err(error) => self.report_warning(error),
+                }
+            }
+            Message::WindowResized(size) => {
+                self.window_size = Some(size);
+                Task::none()
+            }
+            Message::WindowCloseRequested(id) => match self.session.save(&self.session_state()) {
+                Ok(()) => window::close(id),
+                Err(error) => self.report_warning(error).chain(window::close(id)),
+            },
             Message::Components(components_message) => {
                 if let Some(components) = &mut self.components {
+                    if let ComponentsMessage::OpenTableData(table_name) = &components_message {
+                        BSessionState::push_recent_table(
+                            &mut self.recent_tables,
+                            table_name.clone(),
+                        );
+                    }
                     components.update(components_message)
                 } else {
                     match components_message {
@@ -85,7 +296,23 @@ impl Crm {
             }
             Message::Tables(tables_message) => {
                 if let Some(components) = &mut self.components {
-                    components.tables_ui.update(tables_message)
+                    let is_initialized =
+                        matches!(tables_message, TablesMessage::ComponentInitialized);
+                    let task = components.tables_ui.update(tables_message);
+                    let restored_table = if is_initialized {
+                        // a table dropped since the last session leaves the home view open
+                        self.table_to_restore
+                            .take()
+                            .filter(|table_name| components.tables_ui.has_table(table_name))
+                    } else {
+                        None
+                    };
+                    match restored_table {
+                        Some(table_name) => task.chain(Task::done(ComponentsMessage::message(
+                            ComponentsMessage::OpenTableData(table_name),
+                        ))),
+                        None => task,
+                    }
                 } else {
                     Task::none()
                 }
@@ -97,13 +324,175 @@ impl Crm {
                     Task::none()
                 }
             }
+            Message::QueryRunner(query_runner_message) => {
+                if let Some(components) = &mut self.components {
+                    components.query_runner_ui.update(query_runner_message)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Connection(connection_message) => {
+                self.connection_ui.update(connection_message)
+            }
         }
     }
 }
 
+// messages whose task introspects the schema or loads table data
+fn is_long_running(message: &Message) -> bool {
+    matches!(
+        message,
+        Message::Connection(ConnectionMessage::Connect)
+            | Message::Home(HomeMessage::InitializeComponent | HomeMessage::Refresh)
+            | Message::Tables(
+                TablesMessage::InitializeComponent
+                    | TablesMessage::ConfirmSelectSchema
+                    | TablesMessage::ConfirmTruncateTable
+            )
+    ) || matches!(
+        message,
+        Message::Tables(
+            TablesMessage::SingleTableData(table_data_message)
+                | TablesMessage::TableData(_, table_data_message)
+        ) if matches!(
+            table_data_message,
+            TableDataMessage::GetTableData(_)
+                | TableDataMessage::RefreshTableData
+                | TableDataMessage::ChangePage(_)
+                | TableDataMessage::SortByColumn(_)
+                | TableDataMessage::AddFilter
+                | TableDataMessage::LookUpPrimaryKey
+                | TableDataMessage::RemoveFilter(_)
+                | TableDataMessage::FollowForeignKey(..)
+                | TableDataMessage::ConfirmUpdateTableData
+        )
+    )
+}
+
+fn error_banner<'a>(error: &str) -> Element<'a, Message> {
+    container(
+        row![
+            text(error.to_string()).width(Length::Fill),
+            button("Dismiss").on_press(Message::DismissError)
+        ]
+        .spacing(10)
+        .align_y(Vertical::Center),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(|_| error_banner_style())
+    .into()
+}
+
+// the keep-alive ping tells whether the connection is healthy, without it nothing is known
+fn status_bar<'a>(components: &UIComponents) -> Element<'a, Message> {
+    let database_name = components
+        .home_ui
+        .home
+        .database_name
+        .blocking_lock()
+        .clone()
+        .unwrap_or_default();
+    let (connection_health, connection_health_color) =
+        if components.home_ui.home.keep_alive_interval().is_none() {
+            ("● Not monitored", Color::from_rgb(0.6, 0.6, 0.6))
+        } else if components.reconnecting {
+            ("● Reconnecting", Color::from_rgb(0.9, 0.7, 0.1))
+        } else if components.connection_alive {
+            ("● Connected", Color::from_rgb(0.2, 0.8, 0.2))
+        } else {
+            ("● Connection lost", Color::from_rgb(0.9, 0.2, 0.2))
+        };
+    let mut status_bar = row![
+        text(format!("Database: {}", database_name)).size(12),
+        text(connection_health)
+            .size(12)
+            .color(connection_health_color),
+    ]
+    .spacing(20)
+    .align_y(Vertical::Center);
+    if let (CurrentComponent::TableData, Some(table_name)) = (
+        &components.current_component,
+        components.tables_ui.active_table_data_name(),
+    ) {
+        status_bar = status_bar
+            .push(text(format!("Table: {}", table_name)).size(12))
+            .push(
+                text(format!(
+                    "Pending changes: {}",
+                    components.tables_ui.active_pending_change_count()
+                ))
+                .size(12),
+            );
+    }
+    container(status_bar)
+        .padding(5)
+        .width(Length::Fill)
+        .style(|_| status_bar_style())
+        .into()
+}
+
+fn status_bar_style() -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color::from_rgb(0.15, 0.15, 0.2))),
+        text_color: Some(Color::WHITE),
+        ..container::Style::default()
+    }
+}
+
+fn error_banner_style() -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color::from_rgb(0.5, 0.1, 0.1))),
+        text_color: Some(Color::WHITE),
+        ..container::Style::default()
+    }
+}
+
+// keys a focused widget already handled never reach this
+fn shortcut_message(key: Key, modifiers: Modifiers) -> Option<Message> {
+    match key.as_ref() {
+        Key::Character("n") if modifiers.command() => {
+            Some(TablesMessage::OpenCreateTableForm.message())
+        }
+        Key::Character("s") if modifiers.command() => {
+            Some(TableDataMessage::UpdateTableData.message())
+        }
+        Key::Character("f") if modifiers.command() => {
+            Some(TablesMessage::FocusTableFilter.message())
+        }
+        Key::Named(Named::Escape) => Some(TablesMessage::CloseCreateTableForm.message()),
+        // text inputs leave these keys to the subscription so they move between the grid cells
+        Key::Named(Named::Tab) if modifiers.shift() => {
+            Some(TableDataMessage::MoveCellFocus(CellFocusMove::Previous).message())
+        }
+        Key::Named(Named::Tab) => {
+            Some(TableDataMessage::MoveCellFocus(CellFocusMove::Next).message())
+        }
+        Key::Named(Named::ArrowUp) => {
+            Some(TableDataMessage::MoveCellFocus(CellFocusMove::Up).message())
+        }
+        Key::Named(Named::ArrowDown) => {
+            Some(TableDataMessage::MoveCellFocus(CellFocusMove::Down).message())
+        }
+        _ => None,
+    }
+}
+
 pub fn main() -> iced::Result {
+    let session = BusinessSession::default();
+    let session_state = session.last_session_state();
+    let mut window_settings = window::Settings {
+        // the close request is handled so the session can be saved first
+        exit_on_close_request: false,
+        ..window::Settings::default()
+    };
+    if let Some((width, height)) = session_state.window_size {
+        window_settings.size = Size::new(width, height);
+    }
     iced::application(Crm::title, Crm::update, Crm::view)
         .settings(Settings::default())
+        .window(window_settings)
         .theme(Crm::theme)
-        .run_with(Crm::setup)
+        .subscription(Crm::subscription)
+        .run_with(move || Crm::setup(session, session_state))
 }